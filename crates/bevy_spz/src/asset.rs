@@ -1,12 +1,24 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use bevy::{
-	asset::{AssetLoader, LoadContext, io::Reader},
+	asset::{AssetLoader, Assets, LoadContext, io::Reader},
+	ecs::{
+		component::Component,
+		entity::Entity,
+		query::Without,
+		system::{Commands, Query, Res},
+	},
 	reflect::TypePath,
 	tasks::ConditionalSendFuture,
 };
 use serde::{Deserialize, Serialize};
-use spz::{gaussian_splat::GaussianSplat, packed::PackedGaussianSplat};
+use spz::{
+	coord::CoordinateSystem,
+	gaussian_splat::{BoundingBox, GaussianSplat},
+	packed::PackedGaussianSplat,
+};
+
+use crate::render::GaussianSplatHandle;
 use thiserror::Error;
 
 #[derive(Default, TypePath)]
@@ -30,12 +42,11 @@ impl AssetLoader for SpzLoader {
 			reader.read_to_end(&mut buf).await?;
 
 			let gs = GaussianSplat::new_from_packed_gaussians(
-				&PackedGaussianSplat::from_bytes(&buf).map_err(Error::LoadError)?,
-				&settings.load_opts,
-			)
-			.map_err(Error::LoadError)?;
+				&PackedGaussianSplat::from_bytes(&buf)?,
+				&settings.resolved_load_opts(),
+			)?;
 
-			Ok(crate::GaussianSplat(gs))
+			Ok(crate::GaussianSplat::new(gs))
 		}
 	}
 
@@ -45,11 +56,100 @@ impl AssetLoader for SpzLoader {
 	}
 }
 
+/// Summary of a loaded splat, for scene management like culling or camera
+/// framing without touching the raw arrays.
+///
+/// [`attach_splat_metadata`] inserts it on entities with a
+/// [`GaussianSplatHandle`] once the asset is loaded.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct SpzMetadata {
+	pub num_points: i32,
+	pub sh_degree: u8,
+	pub antialiased: bool,
+	/// Bounding box of the gaussian centers, in the loaded coordinate system.
+	pub bbox: BoundingBox,
+}
+
+impl From<&GaussianSplat> for SpzMetadata {
+	#[inline]
+	fn from(gs: &GaussianSplat) -> Self {
+		Self {
+			num_points: gs.header.num_points,
+			sh_degree: gs.header.spherical_harmonics_degree,
+			antialiased: gs.header.flags.is_antialiased(),
+			bbox: gs.bbox(),
+		}
+	}
+}
+
+/// Inserts the [`SpzMetadata`] of the loaded asset on every entity with a
+/// [`GaussianSplatHandle`].
+pub fn attach_splat_metadata(
+	mut commands: Commands,
+	splats: Res<Assets<crate::GaussianSplat>>,
+	query: Query<(Entity, &GaussianSplatHandle), Without<SpzMetadata>>,
+) {
+	for (entity, handle) in &query {
+		let Some(splat) = splats.get(&handle.0) else {
+			continue;
+		};
+		commands.entity(entity).insert(splat.metadata().clone());
+	}
+}
+
 /// SPZ asset loader settings.
-#[derive(Default, Clone, Serialize, Deserialize)]
+///
+/// Missing fields keep their defaults, so a `.meta` file can set just the
+/// target coordinate system:
+///
+/// ```ron
+/// (
+///     loader: "bevy_spz::asset::SpzLoader",
+///     settings: (
+///         coordinate_system: "LUF",
+///     ),
+/// )
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Settings {
 	/// Options for loading the Gaussian Splat.
+	///
+	/// Defaults to converting into Right-Up-Back, Bevy's coordinate system,
+	/// see [`render`](crate::render#coordinate-system).
 	pub load_opts: spz::gaussian_splat::LoadOptions,
+	/// Coordinate system to convert into on load, overriding
+	/// `load_opts.coord_sys` unless `Unspecified`, the default.
+	///
+	/// Accepts every form [`CoordinateSystem`] parses from, e.g. `"LUF"` or
+	/// `"LeftUpFront"`. Unknown names fail instead of silently turning the
+	/// override off.
+	pub coordinate_system: CoordinateSystem,
+}
+
+impl Settings {
+	/// Returns `load_opts` with [`Self::coordinate_system`] applied.
+	#[inline]
+	pub fn resolved_load_opts(&self) -> spz::gaussian_splat::LoadOptions {
+		let mut load_opts = self.load_opts.clone();
+
+		if self.coordinate_system != CoordinateSystem::Unspecified {
+			load_opts.coord_sys = self.coordinate_system;
+		}
+		load_opts
+	}
+}
+
+impl Default for Settings {
+	#[inline]
+	fn default() -> Self {
+		Self {
+			load_opts: spz::gaussian_splat::LoadOptions::builder()
+				.coord_sys(CoordinateSystem::RightUpBack)
+				.build(),
+			coordinate_system: CoordinateSystem::Unspecified,
+		}
+	}
 }
 
 #[derive(Error, Debug)]
@@ -57,5 +157,48 @@ pub enum Error {
 	#[error("io error: {0}")]
 	IoError(#[from] std::io::Error),
 	#[error("failed to load SPZ asset: {0}")]
-	LoadError(anyhow::Error),
+	LoadError(#[from] spz::error::SpzError),
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_settings_coordinate_system() {
+		let settings: Settings = ron::from_str(r#"(coordinate_system: "LUF")"#)
+			.expect("failed to parse settings");
+
+		assert_eq!(settings.coordinate_system, CoordinateSystem::LeftUpFront);
+		assert_eq!(
+			settings.resolved_load_opts().coord_sys,
+			CoordinateSystem::LeftUpFront
+		);
+		assert_eq!(settings.load_opts.coord_sys, CoordinateSystem::RightUpBack);
+	}
+
+	#[test]
+	fn test_settings_unknown_coordinate_system() {
+		let err = ron::from_str::<Settings>(r#"(coordinate_system: "LUFF")"#)
+			.err()
+			.expect("unknown coordinate system was accepted");
+
+		assert!(err.to_string().contains("LUFF"), "{err}");
+
+		let settings: Settings = ron::from_str(r#"(coordinate_system: "Unspecified")"#)
+			.expect("failed to parse settings");
+
+		assert_eq!(settings.coordinate_system, CoordinateSystem::Unspecified);
+	}
+
+	#[test]
+	fn test_settings_default_keeps_load_opts() {
+		let settings: Settings = ron::from_str("()").expect("failed to parse settings");
+
+		assert_eq!(settings.coordinate_system, CoordinateSystem::Unspecified);
+		assert_eq!(
+			settings.resolved_load_opts().coord_sys,
+			CoordinateSystem::RightUpBack
+		);
+	}
 }
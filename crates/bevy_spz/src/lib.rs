@@ -9,23 +9,68 @@
 use bevy::asset::Asset;
 use bevy::asset::AssetApp;
 use bevy::{
-	app::{App, Plugin},
+	app::{App, Plugin, Update},
 	reflect::TypePath,
 };
 
 pub mod asset;
+pub mod render;
 
 #[derive(Default)]
 pub struct SpzPlugin;
 
+/// Registers the SPZ asset loader and the systems attaching meshes and
+/// [`asset::SpzMetadata`] to entities of loaded splats, see [`render`]. The
+/// meshes need Bevy's `MeshPlugin`, part of the default plugins.
 impl Plugin for SpzPlugin {
 	fn build(&self, app: &mut App) {
-		app.init_asset_loader::<asset::SpzLoader>();
+		app.init_asset::<GaussianSplat>()
+			.init_asset_loader::<asset::SpzLoader>()
+			.add_systems(
+				Update,
+				(render::attach_splat_meshes, asset::attach_splat_metadata),
+			);
 	}
 }
 
+/// A loaded Gaussian Splat asset.
+///
+/// Dereferences to the decoded [`spz::gaussian_splat::GaussianSplat`].
 #[derive(Asset, TypePath)]
-#[repr(transparent)]
-pub struct GaussianSplat(spz::gaussian_splat::GaussianSplat);
+pub struct GaussianSplat {
+	splat: spz::gaussian_splat::GaussianSplat,
+	metadata: asset::SpzMetadata,
+}
+
+impl GaussianSplat {
+	/// Wraps a decoded splat, computing its [`asset::SpzMetadata`].
+	#[inline]
+	pub fn new(splat: spz::gaussian_splat::GaussianSplat) -> Self {
+		let metadata = asset::SpzMetadata::from(&splat);
+
+		Self { splat, metadata }
+	}
+
+	/// Point count, SH degree, antialiasing and bounding box of the splat,
+	/// computed once on load.
+	#[inline]
+	pub fn metadata(&self) -> &asset::SpzMetadata {
+		&self.metadata
+	}
+
+	#[inline]
+	pub fn into_inner(self) -> spz::gaussian_splat::GaussianSplat {
+		self.splat
+	}
+}
+
+impl std::ops::Deref for GaussianSplat {
+	type Target = spz::gaussian_splat::GaussianSplat;
+
+	#[inline]
+	fn deref(&self) -> &Self::Target {
+		&self.splat
+	}
+}
 
 pub const EXTENSIONS: &[&str] = &["spz", "SPZ"];
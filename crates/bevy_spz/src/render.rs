@@ -0,0 +1,183 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Conversion of loaded [`GaussianSplat`](crate::GaussianSplat) assets into
+//! renderable point data.
+//!
+//! Each gaussian becomes one vertex of a [`PrimitiveTopology::PointList`]
+//! [`Mesh`], carrying its position, linear scale, rotation and color with
+//! opacity. A custom instanced quad or point pipeline reads these as
+//! per-instance attributes.
+//!
+//! # Coordinate system
+//!
+//! Bevy is Y-up, right-handed, with -Z forward, i.e. Right-Up-Back (RUB),
+//! the same as SPZ's internal coordinate system. The loader
+//! [`Settings`](crate::asset::Settings) default to converting into RUB, so
+//! the mesh attributes can be used as-is. Loading with another
+//! `coord_sys` produces data in that system instead.
+
+use bevy::{
+	asset::{Assets, Handle, RenderAssetUsages},
+	ecs::{
+		bundle::Bundle,
+		component::Component,
+		entity::Entity,
+		query::Without,
+		system::{Commands, Query, Res, ResMut},
+	},
+	mesh::{Mesh, Mesh3d, MeshVertexAttribute, PrimitiveTopology, VertexFormat},
+	transform::components::Transform,
+};
+
+use crate::GaussianSplat;
+
+/// Zeroth order spherical harmonics basis constant, converts the DC color
+/// coefficient to RGB.
+const SH_C0: f32 = 0.282_094_8;
+
+/// Per-gaussian linear scale (`exp` of the stored log-scale).
+pub const ATTRIBUTE_SPLAT_SCALE: MeshVertexAttribute =
+	MeshVertexAttribute::new("Splat_Scale", 0x5b7a_2c01, VertexFormat::Float32x3);
+
+/// Per-gaussian rotation quaternion, `(x, y, z, w)` like [`bevy::math::Quat`].
+pub const ATTRIBUTE_SPLAT_ROTATION: MeshVertexAttribute =
+	MeshVertexAttribute::new("Splat_Rotation", 0x5b7a_2c02, VertexFormat::Float32x4);
+
+/// Handle to the [`GaussianSplat`] asset an entity renders.
+///
+/// [`attach_splat_meshes`] inserts the matching [`Mesh3d`] once the asset is
+/// loaded.
+#[derive(Component, Clone, Debug, Default)]
+pub struct GaussianSplatHandle(pub Handle<GaussianSplat>);
+
+/// Components to spawn a Gaussian Splat.
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct GaussianSplatBundle {
+	pub splat: GaussianSplatHandle,
+	pub transform: Transform,
+}
+
+impl GaussianSplat {
+	/// Builds a point list [`Mesh`] with one vertex per gaussian.
+	///
+	/// # Attributes
+	///
+	/// * [`Mesh::ATTRIBUTE_POSITION`] - position.
+	/// * [`ATTRIBUTE_SPLAT_SCALE`] - linear scale.
+	/// * [`ATTRIBUTE_SPLAT_ROTATION`] - normalized rotation quaternion.
+	/// * [`Mesh::ATTRIBUTE_COLOR`] - RGB from the DC color, alpha is the
+	///   opacity after the sigmoid activation.
+	pub fn to_mesh(&self) -> Mesh {
+		let gs: &spz::gaussian_splat::GaussianSplat = self;
+
+		let positions = gs
+			.positions
+			.chunks_exact(3)
+			.map(|p| [p[0], p[1], p[2]])
+			.collect::<Vec<_>>();
+		let scales = gs
+			.scales
+			.chunks_exact(3)
+			.map(|s| [s[0].exp(), s[1].exp(), s[2].exp()])
+			.collect::<Vec<_>>();
+		let rotations = gs
+			.rotations
+			.chunks_exact(4)
+			.map(|r| {
+				let norm = (r[0] * r[0] + r[1] * r[1] + r[2] * r[2] + r[3] * r[3])
+					.sqrt();
+
+				if norm > 0.0 {
+					[r[0] / norm, r[1] / norm, r[2] / norm, r[3] / norm]
+				} else {
+					[0.0, 0.0, 0.0, 1.0]
+				}
+			})
+			.collect::<Vec<_>>();
+		let colors = gs
+			.colors
+			.chunks_exact(3)
+			.zip(&gs.alphas)
+			.map(|(c, &a)| {
+				[
+					0.5 + SH_C0 * c[0],
+					0.5 + SH_C0 * c[1],
+					0.5 + SH_C0 * c[2],
+					spz::math::sigmoid(a),
+				]
+			})
+			.collect::<Vec<_>>();
+
+		Mesh::new(PrimitiveTopology::PointList, RenderAssetUsages::default())
+			.with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+			.with_inserted_attribute(ATTRIBUTE_SPLAT_SCALE, scales)
+			.with_inserted_attribute(ATTRIBUTE_SPLAT_ROTATION, rotations)
+			.with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+	}
+}
+
+impl From<&GaussianSplat> for Mesh {
+	#[inline]
+	fn from(splat: &GaussianSplat) -> Self {
+		splat.to_mesh()
+	}
+}
+
+/// Inserts a [`Mesh3d`] built with [`GaussianSplat::to_mesh`] for every
+/// [`GaussianSplatHandle`] whose asset finished loading.
+pub fn attach_splat_meshes(
+	mut commands: Commands,
+	splats: Res<Assets<GaussianSplat>>,
+	mut meshes: ResMut<Assets<Mesh>>,
+	query: Query<(Entity, &GaussianSplatHandle), Without<Mesh3d>>,
+) {
+	for (entity, handle) in &query {
+		let Some(splat) = splats.get(&handle.0) else {
+			continue;
+		};
+		let mesh = meshes.add(splat.to_mesh());
+
+		commands.entity(entity).insert(Mesh3d(mesh));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bevy::mesh::VertexAttributeValues;
+	use spz::header::Header;
+
+	#[test]
+	fn test_to_mesh() {
+		let splat = GaussianSplat::new(spz::gaussian_splat::GaussianSplat {
+			header: Header {
+				num_points: 2,
+				..Default::default()
+			},
+			positions: vec![1.0, 2.0, 3.0, -1.0, -2.0, -3.0],
+			scales: vec![0.0; 6],
+			rotations: vec![0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 0.0],
+			alphas: vec![0.0, 0.0],
+			colors: vec![0.0; 6],
+			spherical_harmonics: vec![],
+		});
+		let mesh = splat.to_mesh();
+
+		assert_eq!(mesh.primitive_topology(), PrimitiveTopology::PointList);
+		assert_eq!(mesh.count_vertices(), 2);
+
+		let Some(VertexAttributeValues::Float32x4(rotations)) =
+			mesh.attribute(ATTRIBUTE_SPLAT_ROTATION)
+		else {
+			panic!("missing rotation attribute");
+		};
+		assert_eq!(rotations, &[[0.0, 0.0, 0.0, 1.0]; 2]);
+
+		let Some(VertexAttributeValues::Float32x4(colors)) =
+			mesh.attribute(Mesh::ATTRIBUTE_COLOR)
+		else {
+			panic!("missing color attribute");
+		};
+		assert_eq!(colors, &[[0.5, 0.5, 0.5, 0.5]; 2]);
+	}
+}
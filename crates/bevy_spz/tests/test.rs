@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::path::PathBuf;
+
+use bevy_spz::{GaussianSplat, asset::SpzMetadata};
+
+fn assets_dir() -> PathBuf {
+	PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../assets")
+}
+
+#[test]
+fn test_metadata_matches_splat() {
+	let gs = spz::gaussian_splat::GaussianSplat::load(assets_dir().join("racoonfamily.spz"))
+		.expect("failed to load splat");
+	let bbox = gs.bbox();
+	let asset = GaussianSplat::new(gs);
+
+	assert_eq!(
+		asset.metadata(),
+		&SpzMetadata {
+			num_points: 932560,
+			sh_degree: asset.header.spherical_harmonics_degree,
+			antialiased: asset.header.flags.is_antialiased(),
+			bbox,
+		}
+	);
+	assert_eq!(asset.metadata().bbox, asset.bbox());
+}
@@ -73,6 +73,24 @@ typedef enum SpzResult
          * An I/O or parsing error occurred.
          */
 	SpzResult_IoError = 3,
+	/**
+         * The data is not an SPZ file: the decompressed data doesn't start with
+         * the SPZ magic number.
+         */
+	SpzResult_BadMagic = 4,
+	/**
+         * The SPZ file has a version this library doesn't support.
+         */
+	SpzResult_UnsupportedVersion = 5,
+	/**
+         * The data could not be decompressed, e.g. it is not gzip compressed.
+         */
+	SpzResult_Decompress = 6,
+	/**
+         * The attribute arrays don't match the number of points and the
+         * spherical harmonics degree.
+         */
+	SpzResult_InconsistentSizes = 7,
 } SpzResult;
 
 /**
@@ -105,11 +123,56 @@ typedef struct SpzBoundingBox
 	float max_z;
 } SpzBoundingBox;
 
+/**
+ * Options for `spz_gaussian_splat_save_ex`.
+ *
+ * Get the defaults with `spz_save_options_default()`.
+ */
+typedef struct SpzSaveOptions
+{
+	/**
+         * Coordinate system of the splat data, converted to the SPZ internal
+         * RightUpBack on save.
+         */
+	enum SpzCoordinateSystem coord_sys;
+	/**
+         * SPZ version to write. Version 2 stores less precise rotations.
+         */
+	enum SpzVersion version;
+	/**
+         * Number of fractional bits of the fixed point positions, 0 to 23.
+         */
+	uint8_t fractional_bits;
+} SpzSaveOptions;
+
 #ifdef __cplusplus
 extern "C"
 {
 #endif	// __cplusplus
 
+	/**
+ * Parses a coordinate system name, case-insensitively.
+ *
+ * Accepts the short names like `"RDF"` as well as the long names like
+ * `"RightDownFront"`, `"right-down-front"` or `"RIGHT_DOWN_FRONT"`.
+ * Returns `SpzCoordinateSystem_Unspecified` for null, non-UTF-8 or unknown
+ * names.
+ *
+ * # Safety
+ *
+ * `name` must be null or a valid pointer to a NUL-terminated string for the
+ * duration of this call.
+ */
+	enum SpzCoordinateSystem spz_coordinate_system_from_str(const char *name);
+
+	/**
+ * Returns the 3-letter abbreviation of the coordinate system, e.g. `"RDF"`
+ * for `SpzCoordinateSystem_RightDownFront`, or `"UNSPECIFIED"`.
+ *
+ * The returned string is static and must NOT be freed.
+ */
+	const char *spz_coordinate_system_short_name(enum SpzCoordinateSystem cs);
+
 	/**
  * Reads a header from an SPZ file without loading the full splat data.
  *
@@ -138,6 +201,20 @@ extern "C"
  */
 	struct SpzHeader *spz_header_from_bytes(const uint8_t *data, uintptr_t len);
 
+	/**
+ * Creates a new header with the given fields.
+ *
+ * Returns NULL if `version` is `SpzVersion_V1`, `num_points` is negative,
+ * `sh_degree` is greater than 3 or `fractional_bits` is greater than 23.
+ * Call `spz_last_error()` for error details.
+ * The caller must free the returned handle with `spz_header_free`.
+ */
+	struct SpzHeader *spz_header_new(enum SpzVersion version,
+	    int32_t num_points,
+	    uint8_t sh_degree,
+	    uint8_t fractional_bits,
+	    bool antialiased);
+
 	/**
  * Frees a header handle.
  *
@@ -218,6 +295,20 @@ extern "C"
  */
 	char *spz_header_pretty_fmt(const struct SpzHeader *header);
 
+	/**
+ * Serializes the header to its raw, uncompressed 16-byte form.
+ *
+ * Returns `SpzResult_Success` on success. Call `spz_last_error()` on failure.
+ * The caller must free the returned buffer with `spz_free_bytes`.
+ *
+ * # Safety
+ *
+ * `header` must be a valid live header handle returned by this library.
+ * `out_data` and `out_len` must be valid writable pointers for this call.
+ */
+	enum SpzResult spz_header_to_bytes(
+	    const struct SpzHeader *header, uint8_t **out_data, uintptr_t *out_len);
+
 	/**
  * Creates a new, empty GaussianSplat (zero points).
  *
@@ -226,6 +317,39 @@ extern "C"
  */
 	struct SpzGaussianSplat *spz_gaussian_splat_new(void);
 
+	/**
+ * Creates a GaussianSplat by copying caller-owned arrays.
+ *
+ * Expected element counts, with `N = num_points` and
+ * `D = 0, 3, 8, 15` for `sh_degree = 0, 1, 2, 3`:
+ *
+ * - `positions`: `N * 3` (x, y, z)
+ * - `scales`: `N * 3` (log scale)
+ * - `rotations`: `N * 4` (x, y, z, w)
+ * - `alphas`: `N` (opacity before sigmoid)
+ * - `colors`: `N * 3` (DC color)
+ * - `spherical_harmonics`: `N * D * 3`, may be NULL if `sh_degree` is 0
+ *
+ * Returns NULL on failure, e.g. a negative `num_points`, `sh_degree`
+ * above 3 or a NULL array. Call `spz_last_error()` for error details.
+ * The caller must free the returned handle with `spz_gaussian_splat_free`.
+ *
+ * # Safety
+ *
+ * Every non-null array pointer must point to at least the number of readable
+ * floats listed above for the duration of this call.
+ */
+
+	struct SpzGaussianSplat *spz_gaussian_splat_from_arrays(const float *positions,
+	    const float *scales,
+	    const float *rotations,
+	    const float *alphas,
+	    const float *colors,
+	    int32_t num_points,
+	    const float *spherical_harmonics,
+	    uint8_t sh_degree,
+	    bool antialiased);
+
 	/**
  * Loads a GaussianSplat from an SPZ file.
  *
@@ -256,7 +380,7 @@ extern "C"
 	spz_gaussian_splat_load_from_bytes(const uint8_t *data, uintptr_t len, enum SpzCoordinateSystem coord_sys);
 
 	/**
- * Saves a GaussianSplat to an SPZ file.
+ * Saves a GaussianSplat to an SPZ file, creating missing parent directories.
  *
  * Returns `SpzResult_Success` on success. Call `spz_last_error()` on failure.
  *
@@ -269,6 +393,51 @@ extern "C"
 	enum SpzResult spz_gaussian_splat_save(
 	    const struct SpzGaussianSplat *splat, const char *filepath, enum SpzCoordinateSystem coord_sys);
 
+	/**
+ * Saves a GaussianSplat to the file `filename` in the directory `dir`,
+ * creating `dir` and its missing parents.
+ *
+ * Returns `SpzResult_Success` on success. Returns `SpzResult_InvalidArgument`
+ * if `filename` isn't a bare file name, e.g. contains a path separator.
+ * Call `spz_last_error()` on failure.
+ *
+ * # Safety
+ *
+ * `splat` must be a valid live handle returned by this library, and `dir`
+ * and `filename` must be valid, non-null pointers to NUL-terminated strings
+ * for this call.
+ */
+	enum SpzResult spz_gaussian_splat_save_to_dir(const struct SpzGaussianSplat *splat,
+	    const char *dir,
+	    const char *filename,
+	    enum SpzCoordinateSystem coord_sys);
+
+	/**
+ * Saves a GaussianSplat to an SPZ file with the given options.
+ *
+ * A null `opts` saves with the defaults of [`spz_save_options_default`].
+ *
+ * Returns `SpzResult_Success` on success. Returns
+ * `SpzResult_InvalidArgument` if `opts->fractional_bits` is greater than 23,
+ * and `SpzResult_UnsupportedVersion` for `SpzVersion_V1`.
+ * Call `spz_last_error()` on failure.
+ *
+ * # Safety
+ *
+ * `splat` must be a valid live handle returned by this library, `filepath`
+ * must be a valid, non-null pointer to a NUL-terminated string, and `opts`
+ * must be null or a valid pointer to an `SpzSaveOptions` for this call.
+ */
+
+	enum SpzResult spz_gaussian_splat_save_ex(
+	    const struct SpzGaussianSplat *splat, const char *filepath, const struct SpzSaveOptions *opts);
+
+	/**
+ * Returns the default save options: no coordinate conversion, version 3
+ * and 12 fractional bits.
+ */
+	struct SpzSaveOptions spz_save_options_default(void);
+
 	/**
  * Serializes a GaussianSplat to a heap-allocated byte buffer.
  *
@@ -288,12 +457,13 @@ extern "C"
 	    uintptr_t *out_len);
 
 	/**
- * Frees a byte buffer previously returned by `spz_gaussian_splat_to_bytes`.
+ * Frees a byte buffer previously returned by `spz_gaussian_splat_to_bytes`
+ * or `spz_header_to_bytes`.
  *
  * # Safety
  *
  * `data` and `len` must match a buffer previously returned by
- * `spz_gaussian_splat_to_bytes` and not yet freed.
+ * `spz_gaussian_splat_to_bytes` or `spz_header_to_bytes` and not yet freed.
  */
 	void spz_free_bytes(uint8_t *data, uintptr_t len);
 
@@ -515,6 +685,16 @@ extern "C"
  */
 	const char *spz_last_error(void);
 
+	/**
+ * Returns the [`SpzResult`] of the last failed call on this thread, or
+ * `SpzResult_Success` if it succeeded.
+ *
+ * Useful after functions returning NULL on failure, e.g. to tell
+ * `SpzResult_BadMagic` ("not an SPZ file") from `SpzResult_IoError` after
+ * `spz_gaussian_splat_load`. Every failing function records a code.
+ */
+	enum SpzResult spz_last_error_code(void);
+
 	/**
  * Returns the library version as a static null-terminated string.
  */
@@ -9,6 +9,8 @@
 //!
 //! Functions that can fail return [`SpzResult`]. On failure, call
 //! [`spz_last_error`] to retrieve a human-readable, thread-local error message.
+//! Functions returning a handle return NULL on failure instead; call
+//! [`spz_last_error_code`] for the matching [`SpzResult`].
 //!
 //! # Memory management
 //!
@@ -21,16 +23,19 @@
 #![deny(clippy::undocumented_unsafe_blocks)]
 #![deny(unsafe_op_in_unsafe_fn)]
 
-use std::ffi::{CStr, c_char};
+use std::cell::Cell;
+use std::ffi::{CStr, OsStr, c_char};
+use std::path::Path;
 use std::ptr;
 use std::slice;
 
 use spz::coord::CoordinateSystem as RustCoordinateSystem;
+use spz::error::SpzError;
 use spz::gaussian_splat::{
 	BoundingBox as RustBoundingBox, GaussianSplat as RustGaussianSplat, LoadOptions,
 	SaveOptions,
 };
-use spz::header::{Header as RustHeader, Version as RustVersion};
+use spz::header::{Flags, Header as RustHeader, Version as RustVersion};
 use spz::packed::PackedGaussianSplat;
 
 // ---------------------------------------------------------------------------
@@ -39,14 +44,23 @@ use spz::packed::PackedGaussianSplat;
 
 thread_local! {
 static LAST_ERROR: std::cell::RefCell<Option<String>> = const { std::cell::RefCell::new(None) };
+static LAST_ERROR_CODE: Cell<SpzResult> = const { Cell::new(SpzResult::Success) };
 }
 
-fn set_last_error(msg: String) {
+/// Sets the last error message along with its code, and returns the code.
+///
+/// Every failure goes through here, so [`spz_last_error_code`] never reports
+/// `SpzResult_Success` after a failed call.
+fn set_last_error_with_code(code: SpzResult, msg: String) -> SpzResult {
+	LAST_ERROR_CODE.with(|c| c.set(code));
 	LAST_ERROR.with(|e| *e.borrow_mut() = Some(msg));
+
+	code
 }
 
 fn clear_last_error() {
 	LAST_ERROR.with(|e| *e.borrow_mut() = None);
+	LAST_ERROR_CODE.with(|c| c.set(SpzResult::Success));
 }
 
 fn cstr_arg<'a>(ptr: *const c_char, name: &str) -> std::result::Result<&'a str, String> {
@@ -71,6 +85,25 @@ fn byte_slice_arg<'a>(data: *const u8, len: usize) -> std::result::Result<&'a [u
 	Ok(unsafe { slice::from_raw_parts(data, len) })
 }
 
+/// Like [`byte_slice_arg`] for `f32` arrays, but allows a null `data` when
+/// `len` is `0`.
+fn float_slice_arg<'a>(
+	data: *const f32,
+	len: usize,
+	name: &str,
+) -> std::result::Result<&'a [f32], String> {
+	if len == 0 {
+		return Ok(&[]);
+	}
+	if data.is_null() {
+		return Err(format!("{name} is null"));
+	}
+
+	// SAFETY: `data` is checked for null above, and the FFI contract for callers
+	// requires that it points to `len` readable floats for the duration of the call.
+	Ok(unsafe { slice::from_raw_parts(data, len) })
+}
+
 fn header_ref(header: *const SpzHeader) -> Option<&'static SpzHeader> {
 	if header.is_null() {
 		return None;
@@ -159,6 +192,28 @@ pub enum SpzResult {
 	InvalidArgument = 2,
 	/// An I/O or parsing error occurred.
 	IoError = 3,
+	/// The data is not an SPZ file: the decompressed data doesn't start with
+	/// the SPZ magic number.
+	BadMagic = 4,
+	/// The SPZ file has a version this library doesn't support.
+	UnsupportedVersion = 5,
+	/// The data could not be decompressed, e.g. it is not gzip compressed.
+	Decompress = 6,
+	/// The attribute arrays don't match the number of points and the
+	/// spherical harmonics degree.
+	InconsistentSizes = 7,
+}
+
+impl From<&SpzError> for SpzResult {
+	fn from(e: &SpzError) -> Self {
+		match e {
+			SpzError::BadMagic(_) => SpzResult::BadMagic,
+			SpzError::UnsupportedVersion(_) => SpzResult::UnsupportedVersion,
+			SpzError::Decompress(_) => SpzResult::Decompress,
+			SpzError::InconsistentSizes => SpzResult::InconsistentSizes,
+			_ => SpzResult::IoError,
+		}
+	}
 }
 
 // ---------------------------------------------------------------------------
@@ -215,6 +270,46 @@ impl From<RustCoordinateSystem> for SpzCoordinateSystem {
 	}
 }
 
+/// Parses a coordinate system name, case-insensitively.
+///
+/// Accepts the short names like `"RDF"` as well as the long names like
+/// `"RightDownFront"`, `"right-down-front"` or `"RIGHT_DOWN_FRONT"`.
+/// Returns `SpzCoordinateSystem_Unspecified` for null, non-UTF-8 or unknown
+/// names.
+///
+/// # Safety
+///
+/// `name` must be null or a valid pointer to a NUL-terminated string for the
+/// duration of this call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spz_coordinate_system_from_str(
+	name: *const c_char,
+) -> SpzCoordinateSystem {
+	cstr_arg(name, "name")
+		.map(|name| RustCoordinateSystem::from(name).into())
+		.unwrap_or(SpzCoordinateSystem::Unspecified)
+}
+
+/// Returns the 3-letter abbreviation of the coordinate system, e.g. `"RDF"`
+/// for `SpzCoordinateSystem_RightDownFront`, or `"UNSPECIFIED"`.
+///
+/// The returned string is static and must NOT be freed.
+#[unsafe(no_mangle)]
+pub extern "C" fn spz_coordinate_system_short_name(cs: SpzCoordinateSystem) -> *const c_char {
+	let name = match cs {
+		SpzCoordinateSystem::Unspecified => c"UNSPECIFIED",
+		SpzCoordinateSystem::LeftDownBack => c"LDB",
+		SpzCoordinateSystem::RightDownBack => c"RDB",
+		SpzCoordinateSystem::LeftUpBack => c"LUB",
+		SpzCoordinateSystem::RightUpBack => c"RUB",
+		SpzCoordinateSystem::LeftDownFront => c"LDF",
+		SpzCoordinateSystem::RightDownFront => c"RDF",
+		SpzCoordinateSystem::LeftUpFront => c"LUF",
+		SpzCoordinateSystem::RightUpFront => c"RUF",
+	};
+	name.as_ptr()
+}
+
 // ---------------------------------------------------------------------------
 // Version
 // ---------------------------------------------------------------------------
@@ -254,6 +349,36 @@ impl From<SpzVersion> for RustVersion {
 	}
 }
 
+// ---------------------------------------------------------------------------
+// Save options
+// ---------------------------------------------------------------------------
+
+/// Options for `spz_gaussian_splat_save_ex`.
+///
+/// Get the defaults with `spz_save_options_default()`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct SpzSaveOptions {
+	/// Coordinate system of the splat data, converted to the SPZ internal
+	/// RightUpBack on save.
+	pub coord_sys: SpzCoordinateSystem,
+	/// SPZ version to write. Version 2 stores less precise rotations.
+	pub version: SpzVersion,
+	/// Number of fractional bits of the fixed point positions, 0 to 23.
+	pub fractional_bits: u8,
+}
+
+impl From<SpzSaveOptions> for SaveOptions {
+	fn from(opts: SpzSaveOptions) -> Self {
+		SaveOptions {
+			coord_sys: opts.coord_sys.into(),
+			version: opts.version.into(),
+			fractional_bits: opts.fractional_bits,
+			..Default::default()
+		}
+	}
+}
+
 // ---------------------------------------------------------------------------
 // Bounding box
 // ---------------------------------------------------------------------------
@@ -315,7 +440,7 @@ pub unsafe extern "C" fn spz_header_from_file(filepath: *const c_char) -> *mut S
 	let path = match cstr_arg(filepath, "filepath") {
 		Ok(path) => path,
 		Err(message) => {
-			set_last_error(message);
+			set_last_error_with_code(SpzResult::InvalidArgument, message);
 			return ptr::null_mut();
 		},
 	};
@@ -323,7 +448,11 @@ pub unsafe extern "C" fn spz_header_from_file(filepath: *const c_char) -> *mut S
 	match RustHeader::from_file(path) {
 		Ok(h) => Box::into_raw(Box::new(SpzHeader { inner: h })),
 		Err(e) => {
-			set_last_error(format!("failed to read SPZ header: {e}"));
+			set_last_error_with_code(
+				e.downcast_ref::<SpzError>()
+					.map_or(SpzResult::IoError, SpzResult::from),
+				format!("failed to read SPZ header: {e}"),
+			);
 			ptr::null_mut()
 		},
 	}
@@ -345,7 +474,7 @@ pub unsafe extern "C" fn spz_header_from_bytes(data: *const u8, len: usize) -> *
 	let bytes = match byte_slice_arg(data, len) {
 		Ok(bytes) => bytes,
 		Err(message) => {
-			set_last_error(message);
+			set_last_error_with_code(SpzResult::InvalidArgument, message);
 			return ptr::null_mut();
 		},
 	};
@@ -353,12 +482,79 @@ pub unsafe extern "C" fn spz_header_from_bytes(data: *const u8, len: usize) -> *
 	match RustHeader::from_compressed_bytes(bytes) {
 		Ok(h) => Box::into_raw(Box::new(SpzHeader { inner: h })),
 		Err(e) => {
-			set_last_error(format!("failed to parse SPZ header: {e}"));
+			set_last_error_with_code(
+				e.downcast_ref::<SpzError>()
+					.map_or(SpzResult::IoError, SpzResult::from),
+				format!("failed to parse SPZ header: {e}"),
+			);
 			ptr::null_mut()
 		},
 	}
 }
 
+/// Creates a new header with the given fields.
+///
+/// Returns NULL if `version` is `SpzVersion_V1`, `num_points` is negative,
+/// `sh_degree` is greater than 3 or `fractional_bits` is greater than 23.
+/// Call `spz_last_error()` for error details.
+/// The caller must free the returned handle with `spz_header_free`.
+#[unsafe(no_mangle)]
+pub extern "C" fn spz_header_new(
+	version: SpzVersion,
+	num_points: i32,
+	sh_degree: u8,
+	fractional_bits: u8,
+	antialiased: bool,
+) -> *mut SpzHeader {
+	clear_last_error();
+
+	if matches!(version, SpzVersion::V1) {
+		set_last_error_with_code(
+			SpzResult::UnsupportedVersion,
+			"unsupported version: 1".to_string(),
+		);
+		return ptr::null_mut();
+	}
+	if num_points < 0 {
+		set_last_error_with_code(
+			SpzResult::InvalidArgument,
+			format!("invalid num_points: {num_points}"),
+		);
+		return ptr::null_mut();
+	}
+	if sh_degree > 3 {
+		set_last_error_with_code(
+			SpzResult::InvalidArgument,
+			format!("invalid sh_degree: {sh_degree} (max 3)"),
+		);
+		return ptr::null_mut();
+	}
+	if fractional_bits > spz::consts::MAX_FRACTIONAL_BITS {
+		set_last_error_with_code(
+			SpzResult::InvalidArgument,
+			format!(
+				"invalid fractional_bits: {} (max {})",
+				fractional_bits,
+				spz::consts::MAX_FRACTIONAL_BITS
+			),
+		);
+		return ptr::null_mut();
+	}
+	let header = RustHeader {
+		version: version.into(),
+		num_points,
+		spherical_harmonics_degree: sh_degree,
+		fractional_bits,
+		flags: if antialiased {
+			Flags::ANTIALIASED
+		} else {
+			Flags::none()
+		},
+		..Default::default()
+	};
+	Box::into_raw(Box::new(SpzHeader { inner: header }))
+}
+
 /// Frees a header handle.
 ///
 /// # Safety
@@ -467,6 +663,58 @@ pub unsafe extern "C" fn spz_header_pretty_fmt(header: *const SpzHeader) -> *mut
 	}
 }
 
+/// Serializes the header to its raw, uncompressed 16-byte form.
+///
+/// Returns `SpzResult_Success` on success. Call `spz_last_error()` on failure.
+/// The caller must free the returned buffer with `spz_free_bytes`.
+///
+/// # Safety
+///
+/// `header` must be a valid live header handle returned by this library.
+/// `out_data` and `out_len` must be valid writable pointers for this call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spz_header_to_bytes(
+	header: *const SpzHeader,
+	out_data: *mut *mut u8,
+	out_len: *mut usize,
+) -> SpzResult {
+	clear_last_error();
+
+	let Some(header) = header_ref(header) else {
+		return set_last_error_with_code(
+			SpzResult::NullPointer,
+			"null pointer argument".to_string(),
+		);
+	};
+	if out_data.is_null() || out_len.is_null() {
+		return set_last_error_with_code(
+			SpzResult::NullPointer,
+			"null pointer argument".to_string(),
+		);
+	}
+	let mut bytes = Vec::with_capacity(spz::header::HEADER_SIZE);
+
+	match header.inner.serialize_to(&mut bytes) {
+		Ok(()) => {
+			let len = bytes.len();
+			let boxed = bytes.into_boxed_slice();
+			let ptr = Box::into_raw(boxed) as *mut u8;
+
+			// SAFETY: `out_data` and `out_len` were checked for null above and the
+			// FFI contract requires them to be valid writable pointers for this call.
+			unsafe {
+				*out_data = ptr;
+				*out_len = len;
+			}
+			SpzResult::Success
+		},
+		Err(e) => set_last_error_with_code(
+			SpzResult::IoError,
+			format!("failed to serialize SPZ header: {e}"),
+		),
+	}
+}
+
 // ---------------------------------------------------------------------------
 // GaussianSplat
 // ---------------------------------------------------------------------------
@@ -490,6 +738,96 @@ pub extern "C" fn spz_gaussian_splat_new() -> *mut SpzGaussianSplat {
 	}))
 }
 
+/// Creates a GaussianSplat by copying caller-owned arrays.
+///
+/// Expected element counts, with `N = num_points` and
+/// `D = 0, 3, 8, 15` for `sh_degree = 0, 1, 2, 3`:
+///
+/// - `positions`: `N * 3` (x, y, z)
+/// - `scales`: `N * 3` (log scale)
+/// - `rotations`: `N * 4` (x, y, z, w)
+/// - `alphas`: `N` (opacity before sigmoid)
+/// - `colors`: `N * 3` (DC color)
+/// - `spherical_harmonics`: `N * D * 3`, may be NULL if `sh_degree` is 0
+///
+/// Returns NULL on failure, e.g. a negative `num_points`, `sh_degree`
+/// above 3 or a NULL array. Call `spz_last_error()` for error details.
+/// The caller must free the returned handle with `spz_gaussian_splat_free`.
+///
+/// # Safety
+///
+/// Every non-null array pointer must point to at least the number of readable
+/// floats listed above for the duration of this call.
+#[unsafe(no_mangle)]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn spz_gaussian_splat_from_arrays(
+	positions: *const f32,
+	scales: *const f32,
+	rotations: *const f32,
+	alphas: *const f32,
+	colors: *const f32,
+	num_points: i32,
+	spherical_harmonics: *const f32,
+	sh_degree: u8,
+	antialiased: bool,
+) -> *mut SpzGaussianSplat {
+	clear_last_error();
+
+	let Ok(n) = usize::try_from(num_points) else {
+		set_last_error_with_code(
+			SpzResult::InvalidArgument,
+			format!("num_points is negative: {num_points}"),
+		);
+		return ptr::null_mut();
+	};
+	if sh_degree > 3 {
+		set_last_error_with_code(
+			SpzResult::InvalidArgument,
+			format!("sh_degree must be between 0 and 3, got {sh_degree}"),
+		);
+		return ptr::null_mut();
+	}
+	let sh_len = n * spz::math::dim_for_degree(sh_degree) as usize * 3;
+
+	let arrays = (|| {
+		Ok::<_, String>(RustGaussianSplat {
+			header: RustHeader {
+				num_points,
+				spherical_harmonics_degree: sh_degree,
+				flags: if antialiased {
+					Flags::ANTIALIASED
+				} else {
+					Flags::none()
+				},
+				..Default::default()
+			},
+			positions: float_slice_arg(positions, n * 3, "positions")?.to_vec(),
+			scales: float_slice_arg(scales, n * 3, "scales")?.to_vec(),
+			rotations: float_slice_arg(rotations, n * 4, "rotations")?.to_vec(),
+			alphas: float_slice_arg(alphas, n, "alphas")?.to_vec(),
+			colors: float_slice_arg(colors, n * 3, "colors")?.to_vec(),
+			spherical_harmonics: float_slice_arg(
+				spherical_harmonics,
+				sh_len,
+				"spherical_harmonics",
+			)?
+			.to_vec(),
+		})
+	})();
+
+	match arrays {
+		Ok(gs) => {
+			debug_assert!(gs.check_sizes());
+
+			Box::into_raw(Box::new(SpzGaussianSplat { inner: gs }))
+		},
+		Err(message) => {
+			set_last_error_with_code(SpzResult::NullPointer, message);
+			ptr::null_mut()
+		},
+	}
+}
+
 /// Loads a GaussianSplat from an SPZ file.
 ///
 /// Returns NULL on failure. Call `spz_last_error()` for error details.
@@ -509,19 +847,23 @@ pub unsafe extern "C" fn spz_gaussian_splat_load(
 	let path = match cstr_arg(filepath, "filepath") {
 		Ok(path) => path,
 		Err(message) => {
-			set_last_error(message);
+			set_last_error_with_code(SpzResult::InvalidArgument, message);
 			return ptr::null_mut();
 		},
 	};
 
 	let opts = LoadOptions {
 		coord_sys: coord_sys.into(),
+		..Default::default()
 	};
 
 	match RustGaussianSplat::load_with(path, &opts) {
 		Ok(gs) => Box::into_raw(Box::new(SpzGaussianSplat { inner: gs })),
 		Err(e) => {
-			set_last_error(format!("failed to load SPZ file: {e}"));
+			set_last_error_with_code(
+				(&e).into(),
+				format!("failed to load SPZ file: {e}"),
+			);
 			ptr::null_mut()
 		},
 	}
@@ -547,31 +889,38 @@ pub unsafe extern "C" fn spz_gaussian_splat_load_from_bytes(
 	let bytes = match byte_slice_arg(data, len) {
 		Ok(bytes) => bytes,
 		Err(message) => {
-			set_last_error(message);
+			set_last_error_with_code(SpzResult::InvalidArgument, message);
 			return ptr::null_mut();
 		},
 	};
 
 	let opts = LoadOptions {
 		coord_sys: coord_sys.into(),
+		..Default::default()
 	};
 
 	match PackedGaussianSplat::from_bytes(bytes) {
 		Ok(packed) => match RustGaussianSplat::new_from_packed_gaussians(&packed, &opts) {
 			Ok(gs) => Box::into_raw(Box::new(SpzGaussianSplat { inner: gs })),
 			Err(e) => {
-				set_last_error(format!("failed to unpack SPZ data: {e}"));
+				set_last_error_with_code(
+					(&e).into(),
+					format!("failed to unpack SPZ data: {e}"),
+				);
 				ptr::null_mut()
 			},
 		},
 		Err(e) => {
-			set_last_error(format!("failed to decompress SPZ data: {e}"));
+			set_last_error_with_code(
+				(&e).into(),
+				format!("failed to decompress SPZ data: {e}"),
+			);
 			ptr::null_mut()
 		},
 	}
 }
 
-/// Saves a GaussianSplat to an SPZ file.
+/// Saves a GaussianSplat to an SPZ file, creating missing parent directories.
 ///
 /// Returns `SpzResult_Success` on success. Call `spz_last_error()` on failure.
 ///
@@ -588,27 +937,162 @@ pub unsafe extern "C" fn spz_gaussian_splat_save(
 	clear_last_error();
 
 	let Some(splat) = splat_ref(splat) else {
-		set_last_error("splat handle is null".to_string());
-		return SpzResult::NullPointer;
+		return set_last_error_with_code(
+			SpzResult::NullPointer,
+			"splat handle is null".to_string(),
+		);
 	};
 	let path = match cstr_arg(filepath, "filepath") {
 		Ok(path) => path,
 		Err(message) => {
-			set_last_error(message);
-			return SpzResult::InvalidArgument;
+			return set_last_error_with_code(SpzResult::InvalidArgument, message);
 		},
 	};
 
 	let opts = SaveOptions {
 		coord_sys: coord_sys.into(),
+		..Default::default()
 	};
 
 	match splat.inner.save(path, &opts) {
 		Ok(()) => SpzResult::Success,
-		Err(e) => {
-			set_last_error(format!("failed to save SPZ file: {e}"));
-			SpzResult::IoError
+		Err(e) => set_last_error_with_code(
+			e.downcast_ref::<SpzError>()
+				.map_or(SpzResult::IoError, SpzResult::from),
+			format!("failed to save SPZ file: {e}"),
+		),
+	}
+}
+
+/// Saves a GaussianSplat to the file `filename` in the directory `dir`,
+/// creating `dir` and its missing parents.
+///
+/// Returns `SpzResult_Success` on success. Returns `SpzResult_InvalidArgument`
+/// if `filename` isn't a bare file name, e.g. contains a path separator.
+/// Call `spz_last_error()` on failure.
+///
+/// # Safety
+///
+/// `splat` must be a valid live handle returned by this library, and `dir`
+/// and `filename` must be valid, non-null pointers to NUL-terminated strings
+/// for this call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spz_gaussian_splat_save_to_dir(
+	splat: *const SpzGaussianSplat,
+	dir: *const c_char,
+	filename: *const c_char,
+	coord_sys: SpzCoordinateSystem,
+) -> SpzResult {
+	clear_last_error();
+
+	let Some(splat) = splat_ref(splat) else {
+		return set_last_error_with_code(
+			SpzResult::NullPointer,
+			"splat handle is null".to_string(),
+		);
+	};
+	let dir = match cstr_arg(dir, "dir") {
+		Ok(dir) => dir,
+		Err(message) => {
+			return set_last_error_with_code(SpzResult::InvalidArgument, message);
+		},
+	};
+	let filename = match cstr_arg(filename, "filename") {
+		Ok(filename) => filename,
+		Err(message) => {
+			return set_last_error_with_code(SpzResult::InvalidArgument, message);
+		},
+	};
+	if Path::new(filename).file_name() != Some(OsStr::new(filename)) {
+		return set_last_error_with_code(
+			SpzResult::InvalidArgument,
+			format!("invalid filename: {filename:?}"),
+		);
+	}
+	let opts = SaveOptions {
+		coord_sys: coord_sys.into(),
+		..Default::default()
+	};
+
+	match splat.inner.save(Path::new(dir).join(filename), &opts) {
+		Ok(()) => SpzResult::Success,
+		Err(e) => set_last_error_with_code(
+			e.downcast_ref::<SpzError>()
+				.map_or(SpzResult::IoError, SpzResult::from),
+			format!("failed to save SPZ file: {e}"),
+		),
+	}
+}
+
+/// Saves a GaussianSplat to an SPZ file with the given options.
+///
+/// A null `opts` saves with the defaults of [`spz_save_options_default`].
+///
+/// Returns `SpzResult_Success` on success. Returns
+/// `SpzResult_InvalidArgument` if `opts->fractional_bits` is greater than 23,
+/// and `SpzResult_UnsupportedVersion` for `SpzVersion_V1`.
+/// Call `spz_last_error()` on failure.
+///
+/// # Safety
+///
+/// `splat` must be a valid live handle returned by this library, `filepath`
+/// must be a valid, non-null pointer to a NUL-terminated string, and `opts`
+/// must be null or a valid pointer to an `SpzSaveOptions` for this call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn spz_gaussian_splat_save_ex(
+	splat: *const SpzGaussianSplat,
+	filepath: *const c_char,
+	opts: *const SpzSaveOptions,
+) -> SpzResult {
+	clear_last_error();
+
+	let Some(splat) = splat_ref(splat) else {
+		return set_last_error_with_code(
+			SpzResult::NullPointer,
+			"splat handle is null".to_string(),
+		);
+	};
+	let path = match cstr_arg(filepath, "filepath") {
+		Ok(path) => path,
+		Err(message) => {
+			return set_last_error_with_code(SpzResult::InvalidArgument, message);
 		},
+	};
+	let opts = if opts.is_null() {
+		spz_save_options_default()
+	} else {
+		// SAFETY: `opts` is checked for null above, and the FFI contract
+		// requires it to point to a valid `SpzSaveOptions` for this call.
+		unsafe { *opts }
+	};
+	if opts.fractional_bits > spz::consts::MAX_FRACTIONAL_BITS {
+		return set_last_error_with_code(
+			SpzResult::InvalidArgument,
+			format!(
+				"invalid fractional_bits: {} (max {})",
+				opts.fractional_bits,
+				spz::consts::MAX_FRACTIONAL_BITS
+			),
+		);
+	}
+	match splat.inner.save(path, &opts.into()) {
+		Ok(()) => SpzResult::Success,
+		Err(e) => set_last_error_with_code(
+			e.downcast_ref::<SpzError>()
+				.map_or(SpzResult::IoError, SpzResult::from),
+			format!("failed to save SPZ file: {e}"),
+		),
+	}
+}
+
+/// Returns the default save options: no coordinate conversion, version 3
+/// and 12 fractional bits.
+#[unsafe(no_mangle)]
+pub extern "C" fn spz_save_options_default() -> SpzSaveOptions {
+	SpzSaveOptions {
+		coord_sys: SpzCoordinateSystem::Unspecified,
+		version: SpzVersion::V3,
+		fractional_bits: spz::consts::DEFAULT_FRACTIONAL_BITS,
 	}
 }
 
@@ -631,15 +1115,20 @@ pub unsafe extern "C" fn spz_gaussian_splat_to_bytes(
 	clear_last_error();
 
 	let Some(splat) = splat_ref(splat) else {
-		set_last_error("null pointer argument".to_string());
-		return SpzResult::NullPointer;
+		return set_last_error_with_code(
+			SpzResult::NullPointer,
+			"null pointer argument".to_string(),
+		);
 	};
 	if out_data.is_null() || out_len.is_null() {
-		set_last_error("null pointer argument".to_string());
-		return SpzResult::NullPointer;
+		return set_last_error_with_code(
+			SpzResult::NullPointer,
+			"null pointer argument".to_string(),
+		);
 	}
 	let opts = SaveOptions {
 		coord_sys: coord_sys.into(),
+		..Default::default()
 	};
 
 	match splat.inner.serialize_to_packed_bytes(&opts) {
@@ -656,19 +1145,21 @@ pub unsafe extern "C" fn spz_gaussian_splat_to_bytes(
 			}
 			SpzResult::Success
 		},
-		Err(e) => {
-			set_last_error(format!("failed to serialize SPZ data: {e}"));
-			SpzResult::IoError
-		},
+		Err(e) => set_last_error_with_code(
+			e.downcast_ref::<SpzError>()
+				.map_or(SpzResult::IoError, SpzResult::from),
+			format!("failed to serialize SPZ data: {e}"),
+		),
 	}
 }
 
-/// Frees a byte buffer previously returned by `spz_gaussian_splat_to_bytes`.
+/// Frees a byte buffer previously returned by `spz_gaussian_splat_to_bytes`
+/// or `spz_header_to_bytes`.
 ///
 /// # Safety
 ///
 /// `data` and `len` must match a buffer previously returned by
-/// `spz_gaussian_splat_to_bytes` and not yet freed.
+/// `spz_gaussian_splat_to_bytes` or `spz_header_to_bytes` and not yet freed.
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn spz_free_bytes(data: *mut u8, len: usize) {
 	free_byte_buffer(data, len);
@@ -1025,8 +1516,157 @@ pub extern "C" fn spz_last_error() -> *const c_char {
 	})
 }
 
+/// Returns the [`SpzResult`] of the last failed call on this thread, or
+/// `SpzResult_Success` if it succeeded.
+///
+/// Useful after functions returning NULL on failure, e.g. to tell
+/// `SpzResult_BadMagic` ("not an SPZ file") from `SpzResult_IoError` after
+/// `spz_gaussian_splat_load`. Every failing function records a code.
+#[unsafe(no_mangle)]
+pub extern "C" fn spz_last_error_code() -> SpzResult {
+	LAST_ERROR_CODE.with(|c| c.get())
+}
+
 /// Returns the library version as a static null-terminated string.
 #[unsafe(no_mangle)]
 pub extern "C" fn spz_version() -> *const c_char {
 	concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr() as *const c_char
 }
+
+#[cfg(test)]
+mod tests {
+	use std::ffi::CString;
+
+	use super::*;
+
+	#[test]
+	fn test_coordinate_system_short_name_matches_rust() {
+		use SpzCoordinateSystem::*;
+
+		for cs in [
+			Unspecified,
+			LeftDownBack,
+			RightDownBack,
+			LeftUpBack,
+			RightUpBack,
+			LeftDownFront,
+			RightDownFront,
+			LeftUpFront,
+			RightUpFront,
+		] {
+			// SAFETY: the returned pointer is a static NUL-terminated string.
+			let name = unsafe { CStr::from_ptr(spz_coordinate_system_short_name(cs)) };
+
+			assert_eq!(
+				name.to_str().unwrap(),
+				RustCoordinateSystem::from(cs).as_short_str(),
+				"{cs:?}"
+			);
+		}
+	}
+
+	/// A splat whose header claims more points than its arrays hold.
+	fn inconsistent_splat() -> SpzGaussianSplat {
+		let mut inner = RustGaussianSplat::default();
+
+		inner.header.num_points = 2;
+
+		SpzGaussianSplat { inner }
+	}
+
+	#[test]
+	fn test_header_from_bytes_error_codes() {
+		// SAFETY: a null `data` is rejected before it is read.
+		let header = unsafe { spz_header_from_bytes(ptr::null(), 4) };
+
+		assert!(header.is_null());
+		assert_eq!(spz_last_error_code(), SpzResult::InvalidArgument);
+
+		let not_gzip = [0_u8; 64];
+
+		// SAFETY: `not_gzip` is a live local of `len` bytes.
+		let header = unsafe { spz_header_from_bytes(not_gzip.as_ptr(), not_gzip.len()) };
+
+		assert!(header.is_null());
+		assert_ne!(spz_last_error_code(), SpzResult::Success);
+
+		let mut bad_magic = Vec::new();
+
+		spz::compression::gzip::compress_bytes(&[0xff; 16], &mut bad_magic).unwrap();
+
+		// SAFETY: `bad_magic` is a live local of `len` bytes.
+		let header = unsafe { spz_header_from_bytes(bad_magic.as_ptr(), bad_magic.len()) };
+
+		assert!(header.is_null());
+		assert_eq!(spz_last_error_code(), SpzResult::BadMagic);
+	}
+
+	#[test]
+	fn test_header_new_error_codes() {
+		assert!(spz_header_new(SpzVersion::V1, 1, 0, 12, false).is_null());
+		assert_eq!(spz_last_error_code(), SpzResult::UnsupportedVersion);
+
+		assert!(spz_header_new(SpzVersion::V3, -1, 0, 12, false).is_null());
+		assert_eq!(spz_last_error_code(), SpzResult::InvalidArgument);
+
+		let header = spz_header_new(SpzVersion::V3, 1, 0, 12, false);
+
+		assert!(!header.is_null());
+		assert_eq!(spz_last_error_code(), SpzResult::Success);
+
+		// SAFETY: `header` was just returned by `spz_header_new`.
+		unsafe { spz_header_free(header) };
+	}
+
+	#[test]
+	fn test_to_bytes_inconsistent_sizes() {
+		let splat = inconsistent_splat();
+		let mut data = ptr::null_mut();
+		let mut len = 0;
+
+		// SAFETY: `splat`, `data` and `len` are live locals for this call.
+		let result = unsafe {
+			spz_gaussian_splat_to_bytes(
+				&splat,
+				SpzCoordinateSystem::Unspecified,
+				&mut data,
+				&mut len,
+			)
+		};
+		assert_eq!(result, SpzResult::InconsistentSizes);
+		assert_eq!(spz_last_error_code(), SpzResult::InconsistentSizes);
+		assert!(data.is_null());
+	}
+
+	#[test]
+	fn test_save_inconsistent_sizes() {
+		let splat = inconsistent_splat();
+		let dir = std::env::temp_dir().join("spz_c_test_save_inconsistent_sizes");
+		let c_dir = CString::new(dir.to_str().unwrap()).unwrap();
+		let c_path = CString::new(dir.join("out.spz").to_str().unwrap()).unwrap();
+
+		// SAFETY: `splat` and the strings are live locals for these calls,
+		// a null `opts` is allowed.
+		let results = unsafe {
+			[
+				spz_gaussian_splat_save(
+					&splat,
+					c_path.as_ptr(),
+					SpzCoordinateSystem::Unspecified,
+				),
+				spz_gaussian_splat_save_ex(&splat, c_path.as_ptr(), ptr::null()),
+				spz_gaussian_splat_save_to_dir(
+					&splat,
+					c_dir.as_ptr(),
+					c"out.spz".as_ptr(),
+					SpzCoordinateSystem::Unspecified,
+				),
+			]
+		};
+		for result in results {
+			assert_eq!(result, SpzResult::InconsistentSizes);
+		}
+		assert_eq!(spz_last_error_code(), SpzResult::InconsistentSizes);
+		assert!(!dir.join("out.spz").exists());
+	}
+}
@@ -15,20 +15,27 @@ from ._context_managers import (
 from ._spz import (
     BoundingBox,
     CoordinateSystem,
+    Gaussian,
     GaussianSplat,
     Header,
+    SplatFile,
     Version,
     load,
+    open,
     read_header,
 )
 
 __all__ = [
     "GaussianSplat",
+    "Gaussian",
     "CoordinateSystem",
     "BoundingBox",
     "Header",
     "Version",
+    "SplatFile",
     "load",
+    # `open` is left out so `from spz import *` doesn't shadow the builtin,
+    # use `spz.open` instead.
     "read_header",
     # Context managers
     "SplatReader",
@@ -5,6 +5,8 @@
 Python implementation (in Rust) of the .SPZ file format.
 """
 
+from collections.abc import Iterator
+
 import numpy as np
 import numpy.typing as npt
 
@@ -105,6 +107,47 @@ class BoundingBox:
 
     def __repr__(self) -> str: ...
 
+class Gaussian:
+    """A single gaussian of a ``GaussianSplat``, copied out of its arrays.
+
+    This class cannot be instantiated directly.
+    It is returned by indexing or iterating a ``GaussianSplat``.
+    """
+
+    @property
+    def position(self) -> tuple[float, float, float]:
+        """The (x, y, z) position."""
+        ...
+
+    @property
+    def scale(self) -> tuple[float, float, float]:
+        """The (x, y, z) log-scale."""
+        ...
+
+    @property
+    def rotation(self) -> tuple[float, float, float, float]:
+        """The (x, y, z, w) rotation quaternion."""
+        ...
+
+    @property
+    def alpha(self) -> float:
+        """The opacity logit."""
+        ...
+
+    @property
+    def color(self) -> tuple[float, float, float]:
+        """The (r, g, b) DC color."""
+        ...
+
+    @property
+    def spherical_harmonics(self) -> list[float]:
+        """The spherical harmonics coefficients, ``sh_dim * 3`` values with
+        interleaved channels."""
+        ...
+
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+
 class GaussianSplat:
     """A 3D Gaussian Splat point cloud.
 
@@ -113,7 +156,9 @@ class GaussianSplat:
     rotation, scale, color, alpha (opacity), and spherical harmonics
     coefficients for view-dependent appearance.
 
-    All array data is returned as numpy arrays for efficient processing.
+    All array data is returned as read-only numpy arrays viewing the splat's
+    memory, so accessing e.g. ``positions`` doesn't copy. Use ``.copy()`` to
+    get a writeable array.
 
     Example:
         Load from file::
@@ -232,6 +277,72 @@ class GaussianSplat:
         """
         ...
 
+    @staticmethod
+    def from_ply(
+        path: str, coordinate_system=CoordinateSystem.UNSPECIFIED
+    ) -> GaussianSplat:
+        """Load a GaussianSplat from a binary PLY file.
+
+        Args:
+            path: Path to the PLY file.
+            coordinate_system: The coordinate system to convert to from the
+                PLY one (RDF).
+                Defaults to UNSPECIFIED (no conversion).
+
+        Returns:
+            The loaded Gaussian splat.
+
+        Raises:
+            ValueError: If the file cannot be read or is invalid.
+        """
+        ...
+
+    @staticmethod
+    def from_ply_bytes(
+        data: bytes, coordinate_system=CoordinateSystem.UNSPECIFIED
+    ) -> GaussianSplat:
+        """Load a GaussianSplat from binary PLY bytes.
+
+        Args:
+            data: The PLY file contents as bytes.
+            coordinate_system: The coordinate system to convert to from the
+                PLY one (RDF).
+                Defaults to UNSPECIFIED (no conversion).
+
+        Returns:
+            The loaded Gaussian splat.
+
+        Raises:
+            ValueError: If the data is invalid.
+        """
+        ...
+
+    def save_ply(
+        self, path: str, coordinate_system=CoordinateSystem.UNSPECIFIED
+    ) -> None:
+        """Save the GaussianSplat to a binary PLY file.
+
+        Args:
+            path: Path to save the PLY file.
+            coordinate_system: The coordinate system the data is in, it is
+                converted to the PLY one (RDF).
+                Defaults to UNSPECIFIED (no conversion).
+        """
+        ...
+
+    def to_ply_bytes(self, coordinate_system=CoordinateSystem.UNSPECIFIED) -> bytes:
+        """Serialize the GaussianSplat to binary PLY bytes.
+
+        Args:
+            coordinate_system: The coordinate system the data is in, it is
+                converted to the PLY one (RDF).
+                Defaults to UNSPECIFIED (no conversion).
+
+        Returns:
+            The PLY file contents as bytes.
+        """
+        ...
+
     def convert_coordinates(
         self, source: CoordinateSystem, target: CoordinateSystem
     ) -> None:
@@ -260,32 +371,42 @@ class GaussianSplat:
 
     @property
     def positions(self) -> npt.NDArray[np.float32]:
-        """(N, 3) array of (x, y, z) positions."""
+        """(N, 3) read-only array of (x, y, z) positions."""
         ...
 
     @property
     def scales(self) -> npt.NDArray[np.float32]:
-        """(N, 3) array of (x, y, z) log-scale values."""
+        """(N, 3) read-only array of (x, y, z) log-scale values."""
         ...
 
     @property
     def rotations(self) -> npt.NDArray[np.float32]:
-        """(N, 4) array of (w, x, y, z) quaternion rotations."""
+        """(N, 4) read-only array of (w, x, y, z) quaternion rotations."""
         ...
 
     @property
     def alphas(self) -> npt.NDArray[np.float32]:
-        """(N,) array of inverse-sigmoid opacity values."""
+        """(N,) read-only array of inverse-sigmoid opacity values."""
+        ...
+
+    @property
+    def opacities(self) -> npt.NDArray[np.float32]:
+        """(N,) array of opacities in [0, 1], sigmoid of alphas. A new copy."""
+        ...
+
+    @property
+    def linear_scales(self) -> npt.NDArray[np.float32]:
+        """(N, 3) array of world space scales, exp of scales. A new copy."""
         ...
 
     @property
     def colors(self) -> npt.NDArray[np.float32]:
-        """(N, 3) array of (r, g, b) SH0 color values."""
+        """(N, 3) read-only array of (r, g, b) SH0 color values."""
         ...
 
     @property
     def spherical_harmonics(self) -> npt.NDArray[np.float32]:
-        """(N, sh_dim * 3) array of spherical harmonics coefficients.
+        """(N, sh_dim * 3) read-only array of spherical harmonics coefficients.
 
         The sh_dim depends on sh_degree: 0→0, 1→3, 2→8, 3→15.
 
@@ -298,6 +419,24 @@ class GaussianSplat:
         """The bounding box of the splat."""
         ...
 
+    @property
+    def center(self) -> tuple[float, float, float]:
+        """The (x, y, z) center of the splat's bounding box."""
+        ...
+
+    @property
+    def extent(self) -> tuple[float, float, float]:
+        """The (width, height, depth) of the splat's bounding box."""
+        ...
+
+    @property
+    def memory_footprint(self) -> dict[str, int]:
+        """Bytes each attribute array takes, keyed by attribute name.
+
+        The ``total`` key holds the sum of all arrays.
+        """
+        ...
+
     @property
     def median_volume(self) -> float:
         """The median ellipsoid volume of the Gaussians.
@@ -310,6 +449,162 @@ class GaussianSplat:
     def __repr__(self) -> str: ...
     def __str__(self) -> str: ...
     def __len__(self) -> int: ...
+    def __getitem__(self, i: int) -> Gaussian:
+        """Get a copy of the ``i``-th gaussian.
+
+        Negative indices count from the end.
+
+        Raises:
+            IndexError: If ``i`` is out of range.
+        """
+        ...
+    def __iter__(self) -> Iterator[Gaussian]:
+        """Iterate over copies of the gaussians."""
+        ...
+    def __eq__(self, other: object) -> bool:
+        """Compare the header and all attribute arrays exactly."""
+        ...
+    __hash__: None  # type: ignore[assignment]
+    def __reduce__(self) -> tuple[object, tuple[bytes]]:
+        """Pickle the splat losslessly, unlike ``to_bytes``."""
+        ...
+
+class SplatFile:
+    """An SPZ file opened with ``open()``, decoded lazily.
+
+    The header is read when the file is opened, so ``header``,
+    ``num_points``, ``sh_degree``, ``antialiased``, ``version`` and
+    ``fractional_bits`` are cheap.
+
+    Accessing ``splat``, ``bbox`` or any of the array properties
+    (``positions``, ``scales``, ``rotations``, ``alphas``, ``colors``,
+    ``spherical_harmonics``) decompresses and unpacks the whole file on
+    first use; later accesses reuse the result. Leaving the ``with`` block
+    releases the decoded splat, arrays returned earlier stay valid.
+    """
+
+    def __enter__(self) -> SplatFile: ...
+    def __exit__(self, exc_type, exc_val, exc_tb) -> bool: ...
+    @property
+    def path(self) -> str:
+        """The path the file was opened from."""
+        ...
+
+    @property
+    def header(self) -> Header:
+        """The file header. Doesn't force a full load."""
+        ...
+
+    @property
+    def num_points(self) -> int:
+        """The number of Gaussians. Doesn't force a full load."""
+        ...
+
+    @property
+    def sh_degree(self) -> int:
+        """The spherical harmonics degree. Doesn't force a full load."""
+        ...
+
+    @property
+    def antialiased(self) -> bool:
+        """Whether the splat was trained with antialiasing. Doesn't force a full load."""
+        ...
+
+    @property
+    def version(self) -> Version:
+        """The SPZ format version. Doesn't force a full load."""
+        ...
+
+    @property
+    def fractional_bits(self) -> int:
+        """The number of fractional bits used in position encoding. Doesn't force a full load."""
+        ...
+
+    @property
+    def is_loaded(self) -> bool:
+        """Whether the file has been decoded already."""
+        ...
+
+    @property
+    def splat(self) -> GaussianSplat:
+        """The decoded splat. Forces a full load."""
+        ...
+
+    @property
+    def bbox(self) -> BoundingBox:
+        """The bounding box of the splat. Forces a full load."""
+        ...
+
+    @property
+    def positions(self) -> npt.NDArray[np.float32]:
+        """(N, 3) array of positions. Forces a full load."""
+        ...
+
+    @property
+    def scales(self) -> npt.NDArray[np.float32]:
+        """(N, 3) array of log-scales. Forces a full load."""
+        ...
+
+    @property
+    def rotations(self) -> npt.NDArray[np.float32]:
+        """(N, 4) array of quaternion rotations. Forces a full load."""
+        ...
+
+    @property
+    def alphas(self) -> npt.NDArray[np.float32]:
+        """(N,) array of inverse-sigmoid opacities. Forces a full load."""
+        ...
+
+    @property
+    def opacities(self) -> npt.NDArray[np.float32]:
+        """(N,) array of opacities in [0, 1]. Forces a full load."""
+        ...
+
+    @property
+    def linear_scales(self) -> npt.NDArray[np.float32]:
+        """(N, 3) array of world space scales. Forces a full load."""
+        ...
+
+    @property
+    def colors(self) -> npt.NDArray[np.float32]:
+        """(N, 3) array of SH0 colors. Forces a full load."""
+        ...
+
+    @property
+    def spherical_harmonics(self) -> npt.NDArray[np.float32]:
+        """(N, sh_dim * 3) array of SH coefficients. Forces a full load."""
+        ...
+
+    def __repr__(self) -> str: ...
+    def __len__(self) -> int: ...
+
+def open(
+    path: str,
+    coordinate_system=CoordinateSystem.UNSPECIFIED,
+) -> SplatFile:
+    """Opens an SPZ file, reading only its header.
+
+    The arrays are decoded on first access, see ``SplatFile`` for which
+    properties force a full load.
+
+    Args:
+        path: Path to the SPZ file.
+        coordinate_system: The coordinate system to convert to once the
+            data is loaded.
+            Defaults to UNSPECIFIED (no conversion).
+
+    Returns:
+        The opened file, usable as a context manager.
+
+    Raises:
+        ValueError: If the file cannot be read or the header is invalid.
+
+    Example:
+        >>> import spz
+        >>> with spz.open("scene.spz") as f:
+        ...     print(f.num_points)
+    """
+    ...
 
 def load(
     path: str,
@@ -2,6 +2,7 @@
 
 """Tests for the spz Python bindings."""
 
+import pickle
 from pathlib import Path
 from tempfile import TemporaryDirectory
 
@@ -135,6 +136,23 @@ class TestGaussianSplatCreation:
 
         assert splat.antialiased is True
 
+    def test_create_too_many_points(self):
+        """More than i32::MAX points should raise ValueError."""
+        num_points = 2**31
+        # Broadcast views, nothing is allocated.
+        vec3 = np.broadcast_to(np.zeros(3, dtype=np.float32), (num_points, 3))
+        vec4 = np.broadcast_to(np.zeros(4, dtype=np.float32), (num_points, 4))
+        scalar = np.broadcast_to(np.zeros(1, dtype=np.float32), (num_points,))
+
+        with pytest.raises(ValueError, match="Too many points"):
+            spz.GaussianSplat(
+                positions=vec3,
+                scales=vec3,
+                rotations=vec4,
+                alphas=scalar,
+                colors=vec3,
+            )
+
 
 class TestGaussianSplatArrayAccess:
     """Tests for accessing array data from GaussianSplat."""
@@ -179,6 +197,25 @@ class TestGaussianSplatArrayAccess:
         assert colors.shape == (100, 3)
         assert colors.dtype == np.float32
 
+    def test_opacities(self):
+        """opacities should be the sigmoid of alphas, in [0, 1]."""
+        splat = util.create_test_splat(100)
+        opacities = splat.opacities
+
+        assert opacities.shape == (100,)
+        assert opacities.dtype == np.float32
+        assert np.allclose(opacities, 1.0 / (1.0 + np.exp(-splat.alphas)), atol=1e-6)
+        assert np.all((opacities >= 0.0) & (opacities <= 1.0))
+
+    def test_linear_scales(self):
+        """linear_scales should be exp of the log-scales."""
+        splat = util.create_test_splat(100)
+        scales = splat.linear_scales
+
+        assert scales.shape == (100, 3)
+        assert scales.dtype == np.float32
+        assert np.allclose(scales, np.exp(splat.scales), rtol=1e-5)
+
     def test_spherical_harmonics_empty_for_degree_0(self):
         """spherical_harmonics should be empty for sh_degree=0."""
         splat = util.create_test_splat(100, sh_degree=0)
@@ -187,6 +224,53 @@ class TestGaussianSplatArrayAccess:
         assert sh.shape[0] == 100
         assert sh.shape[1] == 0  # Empty for degree 0
 
+    @pytest.mark.parametrize(
+        "name", ["positions", "scales", "rotations", "alphas", "colors"]
+    )
+    def test_arrays_are_readonly_views(self, name: str):
+        """Array getters should return read-only views sharing the splat's memory."""
+        splat = util.create_test_splat(100)
+        a = getattr(splat, name)
+        b = getattr(splat, name)
+
+        assert np.shares_memory(a, b)
+        assert not a.flags.writeable
+        assert not a.flags.owndata
+
+        with pytest.raises(ValueError):
+            a[0] = 1.0
+
+    def test_array_view_outlives_splat(self):
+        """A view should keep the splat's memory alive."""
+        positions = util.create_test_splat(10).positions
+
+        assert positions.shape == (10, 3)
+        assert np.all(np.isfinite(positions))
+
+    def test_array_view_reflects_in_place_changes(self):
+        """Views should see in-place changes like coordinate conversion."""
+        splat = util.create_test_splat(10)
+        positions = splat.positions
+        before = positions.copy()
+
+        splat.convert_coordinates(spz.CoordinateSystem.RUB, spz.CoordinateSystem.RDF)
+
+        assert np.allclose(positions[:, 1], -before[:, 1])
+
+    def test_positions_access_does_not_copy(self):
+        """Accessing the arrays of a 1M point splat shouldn't allocate ~12 MB per call."""
+        import tracemalloc
+
+        splat = util.create_test_splat(1_000_000)
+
+        tracemalloc.start()
+        for _ in range(10):
+            _ = splat.positions
+        _, peak = tracemalloc.get_traced_memory()
+        tracemalloc.stop()
+
+        assert peak < 1_000_000
+
 
 class TestGaussianSplatProperties:
     """Tests for GaussianSplat properties."""
@@ -218,6 +302,23 @@ class TestGaussianSplatProperties:
 
         assert isinstance(bbox, spz.BoundingBox)
 
+    def test_center_extent(self):
+        """center and extent should match the bounding box."""
+        splat = util.create_test_splat(100)
+        bbox = splat.bbox
+
+        assert splat.center == pytest.approx(bbox.center)
+        assert splat.extent == pytest.approx(bbox.size)
+
+    def test_memory_footprint(self):
+        """memory_footprint should count 4 bytes per stored value."""
+        splat = util.create_test_splat(100)
+        report = splat.memory_footprint
+
+        assert report["positions"] == 100 * 3 * 4
+        assert report["alphas"] == 100 * 4
+        assert report["total"] == sum(v for k, v in report.items() if k != "total")
+
     def test_median_volume(self):
         """median_volume should return a positive float."""
         splat = util.create_test_splat(100)
@@ -241,10 +342,75 @@ class TestGaussianSplatProperties:
 
         assert "GaussianSplat" in str_repr
 
+    def test_getitem(self):
+        """Indexing should return single gaussians, negative from the end."""
+        splat = util.create_test_splat(2)
+
+        first = splat[0]
+
+        assert isinstance(first, spz.Gaussian)
+        assert first.position == pytest.approx(tuple(splat.positions[0]))
+        assert first.rotation == (1.0, 0.0, 0.0, 0.0)
+        assert splat[-1] == splat[1]
+
+        with pytest.raises(IndexError):
+            splat[2]
+        with pytest.raises(IndexError):
+            splat[-3]
+
+    def test_iter(self):
+        """Iterating should yield every gaussian in order."""
+        splat = util.create_test_splat(2)
+        gaussians = list(splat)
+
+        assert len(gaussians) == 2
+        assert gaussians[0].position == pytest.approx(tuple(splat.positions[0]))
+        assert gaussians[1] == splat[1]
+
+    def test_eq(self):
+        """A loaded splat should equal itself and differ once modified."""
+        data = util.create_test_splat(50).to_bytes()
+        splat = spz.GaussianSplat.from_bytes(data)
+        other = spz.GaussianSplat.from_bytes(data)
+
+        assert splat == splat
+        assert splat == other
+
+        other.convert_coordinates(spz.CoordinateSystem.RUB, spz.CoordinateSystem.LDB)
+
+        assert splat != other
+        assert splat != "not a splat"
+
+    def test_unhashable(self):
+        """GaussianSplat should not be hashable."""
+        splat = util.create_test_splat(10)
+
+        with pytest.raises(TypeError):
+            hash(splat)
+
 
 class TestGaussianSplatSerialization:
     """Tests for saving and loading GaussianSplat."""
 
+    def test_pickle_roundtrip(self):
+        """Pickling should preserve all data exactly."""
+        original = util.create_test_splat(50, sh_degree=1)
+        restored = pickle.loads(pickle.dumps(original))
+
+        assert isinstance(restored, spz.GaussianSplat)
+        assert restored == original
+        for name in (
+            "positions",
+            "scales",
+            "rotations",
+            "alphas",
+            "colors",
+            "spherical_harmonics",
+        ):
+            np.testing.assert_array_equal(
+                getattr(restored, name), getattr(original, name)
+            )
+
     def test_to_bytes_and_from_bytes_roundtrip(self):
         """Serialization to bytes and back should preserve data."""
         original = util.create_test_splat(50)
@@ -325,6 +491,91 @@ class TestGaussianSplatSerialization:
             assert restored.num_points == original.num_points
 
 
+class TestPly:
+    """Tests for PLY import and export."""
+
+    @staticmethod
+    def make_ply(num_points: int) -> tuple[bytes, dict[str, np.ndarray]]:
+        """Build a small synthetic 3DGS PLY file with SH degree 1."""
+        rng = np.random.default_rng(0)
+        names = (
+            ["x", "y", "z", "f_dc_0", "f_dc_1", "f_dc_2"]
+            + [f"f_rest_{i}" for i in range(9)]
+            + ["opacity", "scale_0", "scale_1", "scale_2"]
+            + ["rot_0", "rot_1", "rot_2", "rot_3"]
+        )
+        data = rng.uniform(-1.0, 1.0, (num_points, len(names))).astype("<f4")
+        data[:, names.index("rot_0")] = 1.0
+        data[:, 16:19] -= 4.0  # scales
+
+        header = (
+            "ply\nformat binary_little_endian 1.0\n"
+            f"element vertex {num_points}\n"
+            + "".join(f"property float {n}\n" for n in names)
+            + "end_header\n"
+        )
+        columns = {n: data[:, i] for i, n in enumerate(names)}
+
+        return header.encode() + data.tobytes(), columns
+
+    def test_from_ply_bytes(self):
+        """Loading a PLY should map its properties to the splat arrays."""
+        data, columns = self.make_ply(10)
+        splat = spz.GaussianSplat.from_ply_bytes(data)
+
+        assert splat.num_points == 10
+        assert splat.sh_degree == 1
+        np.testing.assert_array_equal(splat.positions[:, 0], columns["x"])
+        np.testing.assert_array_equal(splat.alphas, columns["opacity"])
+        np.testing.assert_array_equal(splat.colors[:, 2], columns["f_dc_2"])
+
+    def test_ply_to_spz_and_back(self):
+        """A PLY converted to SPZ and back should match within tolerance."""
+        data, _ = self.make_ply(50)
+        original = spz.GaussianSplat.from_ply_bytes(data)
+
+        with TemporaryDirectory() as tmpdir:
+            spz_path = Path(tmpdir) / "test.spz"
+            ply_path = Path(tmpdir) / "test.ply"
+
+            original.save(str(spz_path))
+            spz.load(str(spz_path)).save_ply(str(ply_path))
+
+            restored = spz.GaussianSplat.from_ply(str(ply_path))
+
+        assert restored.num_points == original.num_points
+        assert restored.sh_degree == original.sh_degree
+        np.testing.assert_allclose(restored.positions, original.positions, atol=1e-3)
+        np.testing.assert_allclose(restored.scales, original.scales, atol=0.1)
+        np.testing.assert_allclose(restored.alphas, original.alphas, atol=0.05)
+        np.testing.assert_allclose(restored.colors, original.colors, atol=0.05)
+        np.testing.assert_allclose(
+            restored.spherical_harmonics, original.spherical_harmonics, atol=0.1
+        )
+
+    def test_to_ply_bytes_roundtrip(self):
+        """PLY export and import should be lossless."""
+        original = util.create_test_splat(20)
+        data = original.to_ply_bytes(spz.CoordinateSystem.RUB)
+
+        assert data.startswith(b"ply\n")
+
+        restored = spz.GaussianSplat.from_ply_bytes(data, spz.CoordinateSystem.RUB)
+
+        np.testing.assert_array_equal(restored.positions, original.positions)
+        np.testing.assert_array_equal(restored.rotations, original.rotations)
+
+    def test_from_ply_bytes_invalid(self):
+        """Invalid PLY data should raise ValueError."""
+        with pytest.raises(ValueError, match="Failed to parse PLY"):
+            spz.GaussianSplat.from_ply_bytes(b"not a ply file")
+
+    def test_from_ply_nonexistent(self):
+        """A missing PLY file should raise ValueError."""
+        with pytest.raises(ValueError, match="Failed to load PLY"):
+            spz.GaussianSplat.from_ply("/nonexistent/path.ply")
+
+
 class TestGaussianSplatCoordinateConversion:
     """Tests for coordinate system conversion."""
 
@@ -858,6 +1109,14 @@ class TestHeader:
 
         assert splat.header.antialiased is True
 
+    def test_header_pickle(self):
+        """Header should survive a pickle roundtrip."""
+        header = util.create_test_splat(42, sh_degree=2).header
+        restored = pickle.loads(pickle.dumps(header))
+
+        assert isinstance(restored, spz.Header)
+        assert repr(restored) == repr(header)
+
     def test_header_is_valid(self):
         """Header.is_valid should return True for a valid header."""
         splat = util.create_test_splat(10)
@@ -942,6 +1201,66 @@ class TestReadHeader:
             spz.read_header("/nonexistent/path.spz")
 
 
+class TestOpen:
+    """Tests for the lazy spz.open() loader."""
+
+    def test_open_reads_header_only(self):
+        """Header properties should not decode the arrays."""
+        original = util.create_test_splat(40, sh_degree=1)
+
+        with TemporaryDirectory() as tmpdir:
+            filepath = Path(tmpdir) / "test.spz"
+            original.save(str(filepath))
+
+            with spz.open(str(filepath)) as f:
+                assert isinstance(f, spz.SplatFile)
+                assert f.num_points == 40
+                assert f.sh_degree == 1
+                assert len(f) == 40
+                assert f.header.num_points == 40
+                assert not f.is_loaded
+
+    def test_open_loads_on_array_access(self):
+        """Array properties should force a full load."""
+        original = util.create_test_splat(40)
+
+        with TemporaryDirectory() as tmpdir:
+            filepath = Path(tmpdir) / "test.spz"
+            original.save(str(filepath))
+
+            with spz.open(str(filepath)) as f:
+                positions = f.positions
+
+                assert f.is_loaded
+                assert positions.shape == (40, 3)
+                np.testing.assert_allclose(
+                    positions, original.positions, atol=1e-3
+                )
+                assert f.bbox.min_x == f.splat.bbox.min_x
+
+            assert not f.is_loaded
+            assert positions.shape == (40, 3)
+
+    def test_open_nonexistent(self):
+        """spz.open() should raise ValueError for nonexistent files."""
+        with pytest.raises(ValueError, match="Failed to read"):
+            spz.open("/nonexistent/path.spz")
+
+    def test_open_invalid_header(self):
+        """spz.open() should raise ValueError for invalid files."""
+        with TemporaryDirectory() as tmpdir:
+            filepath = Path(tmpdir) / "invalid.spz"
+            filepath.write_bytes(b"not valid spz data at all!!" * 100)
+
+            with pytest.raises(ValueError, match="Failed"):
+                spz.open(str(filepath))
+
+    def test_open_not_star_exported(self):
+        """`from spz import *` should not shadow the builtin open()."""
+        assert "open" not in spz.__all__
+        assert callable(spz.open)
+
+
 class TestGaussianSplatNewProperties:
     """Tests for newly added GaussianSplat properties and methods."""
 
@@ -5,11 +5,14 @@
 //! This crate provides Python bindings using PyO3 and numpy for efficient
 //! array handling.
 
+use std::collections::HashMap;
+
+use numpy::ndarray::{ArrayView, Dimension, Ix1, Ix2};
 use numpy::{
-	PyArray1, PyArray2, PyArrayMethods, PyReadonlyArray1, PyReadonlyArray2,
+	PyArray, PyArray1, PyArray2, PyArrayMethods, PyReadonlyArray1, PyReadonlyArray2,
 	PyUntypedArrayMethods,
 };
-use pyo3::exceptions::PyValueError;
+use pyo3::exceptions::{PyIndexError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::PyBytes;
 
@@ -224,6 +227,41 @@ impl Header {
 	pub fn __str__(&self) -> String {
 		format!("{}", self.inner)
 	}
+
+	/// Pickles the header as its raw 16 bytes.
+	pub fn __reduce__<'py>(
+		slf: &Bound<'py, Self>,
+	) -> PyResult<(Bound<'py, PyAny>, (Bound<'py, PyBytes>,))> {
+		let mut state = Vec::with_capacity(header::HEADER_SIZE);
+
+		slf.get().inner.serialize_to(&mut state).map_err(|e| {
+			PyValueError::new_err(format!("Failed to pickle header: {}", e))
+		})?;
+
+		Ok((
+			slf.get_type().getattr("_from_state")?,
+			(PyBytes::new(slf.py(), &state),),
+		))
+	}
+
+	/// Unpickles a header written by `__reduce__`.
+	#[staticmethod]
+	#[pyo3(name = "_from_state")]
+	pub fn from_state(state: &[u8]) -> PyResult<Self> {
+		Ok(Self {
+			inner: parse_header_state(state)?,
+		})
+	}
+}
+
+/// Parses the raw header at the start of a pickled state.
+fn parse_header_state(state: &[u8]) -> PyResult<header::Header> {
+	let bytes = state
+		.first_chunk::<{ header::HEADER_SIZE }>()
+		.ok_or_else(|| PyValueError::new_err("Failed to unpickle: data too short"))?;
+
+	header::Header::parse(bytes)
+		.map_err(|e| PyValueError::new_err(format!("Failed to unpickle header: {}", e)))
 }
 
 #[pyclass(eq, frozen)]
@@ -440,6 +478,162 @@ impl BoundingBox {
 	}
 }
 
+/// A single gaussian of a `GaussianSplat`, copied out of its arrays.
+///
+/// Returned by indexing or iterating a `GaussianSplat`.
+///
+/// # Examples
+///
+/// ```python
+/// splat = spz.load("scene.spz")
+///
+/// print(splat[0].position)
+/// print(splat[-1].alpha)
+///
+/// for g in splat:
+///     print(g.color)
+/// ```
+#[pyclass(eq, frozen)]
+#[derive(Clone, PartialEq)]
+pub struct Gaussian {
+	position: [f32; 3],
+	scale: [f32; 3],
+	rotation: [f32; 4],
+	alpha: f32,
+	color: [f32; 3],
+	spherical_harmonics: Vec<f32>,
+}
+
+impl From<spz_rs::gaussian_splat::GaussianView<'_>> for Gaussian {
+	#[inline]
+	fn from(view: spz_rs::gaussian_splat::GaussianView<'_>) -> Self {
+		Self {
+			position: *view.position,
+			scale: *view.scale,
+			rotation: *view.rotation,
+			alpha: view.alpha,
+			color: *view.color,
+			spherical_harmonics: view.spherical_harmonics.to_vec(),
+		}
+	}
+}
+
+#[pymethods]
+impl Gaussian {
+	/// `(x, y, z)` position.
+	#[getter]
+	#[inline]
+	pub fn position(&self) -> (f32, f32, f32) {
+		let [x, y, z] = self.position;
+		(x, y, z)
+	}
+
+	/// `(x, y, z)` log-scale.
+	#[getter]
+	#[inline]
+	pub fn scale(&self) -> (f32, f32, f32) {
+		let [x, y, z] = self.scale;
+		(x, y, z)
+	}
+
+	/// `(x, y, z, w)` rotation quaternion.
+	#[getter]
+	#[inline]
+	pub fn rotation(&self) -> (f32, f32, f32, f32) {
+		let [x, y, z, w] = self.rotation;
+		(x, y, z, w)
+	}
+
+	/// Opacity logit.
+	#[getter]
+	#[inline]
+	pub fn alpha(&self) -> f32 {
+		self.alpha
+	}
+
+	/// `(r, g, b)` DC color.
+	#[getter]
+	#[inline]
+	pub fn color(&self) -> (f32, f32, f32) {
+		let [r, g, b] = self.color;
+		(r, g, b)
+	}
+
+	/// Spherical harmonics coefficients, `sh_dim * 3` values with
+	/// interleaved channels.
+	#[getter]
+	#[inline]
+	pub fn spherical_harmonics(&self) -> Vec<f32> {
+		self.spherical_harmonics.clone()
+	}
+
+	#[inline]
+	pub fn __repr__(&self) -> String {
+		format!(
+			"Gaussian(position={:?}, scale={:?}, rotation={:?}, alpha={}, color={:?}, ..)",
+			self.position, self.scale, self.rotation, self.alpha, self.color
+		)
+	}
+}
+
+/// Iterator over the gaussians of a `GaussianSplat`, see
+/// `GaussianSplat.__iter__`.
+#[pyclass]
+pub struct GaussianIterator {
+	splat: Py<GaussianSplat>,
+	index: usize,
+}
+
+#[pymethods]
+impl GaussianIterator {
+	#[inline]
+	pub fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+		slf
+	}
+
+	pub fn __next__(mut slf: PyRefMut<'_, Self>) -> Option<Gaussian> {
+		let gaussian = {
+			let splat = slf.splat.borrow(slf.py());
+
+			if slf.index >= splat.__len__() {
+				return None;
+			}
+			splat.inner.gaussian(slf.index)?.into()
+		};
+		slf.index += 1;
+
+		Some(gaussian)
+	}
+}
+
+/// Returns a read-only numpy array of `shape` viewing the array of `slf`
+/// selected by `field`, without copying it.
+///
+/// The returned array keeps `slf` alive. The splat's arrays are only ever
+/// modified in place (e.g. by `convert_coordinates`), never reallocated, so
+/// the view stays valid and reflects such modifications.
+fn readonly_view<'py, D>(
+	slf: &Bound<'py, GaussianSplat>,
+	shape: D,
+	field: fn(&spz_rs::gaussian_splat::GaussianSplat) -> &Vec<f32>,
+) -> PyResult<Bound<'py, PyArray<f32, D>>>
+where
+	D: Dimension,
+{
+	let this = slf.borrow();
+	let view = ArrayView::from_shape(shape, field(&this.inner))
+		.map_err(|e| PyValueError::new_err(format!("Inconsistent array size: {}", e)))?;
+
+	// SAFETY: the viewed buffer is owned by `slf`, which is set as the base
+	// object of the returned array and so outlives it. `GaussianSplat` never
+	// reallocates its arrays, see above.
+	let arr = unsafe { PyArray::borrow_from_array(&view, slf.clone().into_any()) };
+
+	arr.readwrite().make_nonwriteable();
+
+	Ok(arr)
+}
+
 /// A 3D Gaussian Splat point cloud.
 ///
 /// This class represents a collection of 3D Gaussians used for
@@ -447,7 +641,9 @@ impl BoundingBox {
 /// rotation, scale, color, alpha (opacity), and spherical harmonics
 /// coefficients for view-dependent appearance.
 ///
-/// All array data is returned as numpy arrays for efficient processing.
+/// All array data is returned as read-only numpy arrays viewing the splat's
+/// memory, so accessing e.g. `positions` doesn't copy. Use `.copy()` to get
+/// a writeable array.
 ///
 /// # Examples
 ///
@@ -520,7 +716,9 @@ impl GaussianSplat {
 		antialiased: bool,
 	) -> PyResult<Self> {
 		// Get num_points from the first dimension of positions array (shape is (N, 3))
-		let num_points = positions.shape()[0];
+		let num_points = i32::try_from(positions.shape()[0]).map_err(|_| {
+			PyValueError::new_err(format!("Too many points: {}", positions.shape()[0]))
+		})?;
 		let positions_vec = positions.as_slice()?.to_vec();
 		let scales_vec = scales.as_slice()?.to_vec();
 		let rotations_vec = rotations.as_slice()?.to_vec();
@@ -535,7 +733,7 @@ impl GaussianSplat {
 		Ok(Self {
 			inner: spz_rs::gaussian_splat::GaussianSplat {
 				header: header::Header {
-					num_points: num_points as i32,
+					num_points,
 					spherical_harmonics_degree: sh_degree,
 					flags: if antialiased {
 						header::Flags::ANTIALIASED
@@ -576,6 +774,7 @@ impl GaussianSplat {
 	pub fn load(path: &str, coordinate_system: CoordinateSystem) -> PyResult<Self> {
 		let opts = spz_rs::gaussian_splat::LoadOptions {
 			coord_sys: coordinate_system.inner,
+			..Default::default()
 		};
 		let inner = spz_rs::gaussian_splat::GaussianSplat::load_with(path, &opts).map_err(
 			|e| PyValueError::new_err(format!("Failed to load SPZ file: {}", e)),
@@ -601,6 +800,7 @@ impl GaussianSplat {
 	pub fn from_bytes(data: &[u8], coordinate_system: CoordinateSystem) -> PyResult<Self> {
 		let opts = spz_rs::gaussian_splat::LoadOptions {
 			coord_sys: coordinate_system.inner,
+			..Default::default()
 		};
 		let packed =
 			spz_rs::packed::PackedGaussianSplat::from_bytes(data).map_err(|e| {
@@ -626,6 +826,7 @@ impl GaussianSplat {
 	pub fn save(&self, path: &str, coordinate_system: CoordinateSystem) -> PyResult<()> {
 		let pack_opts = spz_rs::gaussian_splat::SaveOptions {
 			coord_sys: coordinate_system.inner,
+			..Default::default()
 		};
 		self.inner.save(path, &pack_opts).map_err(|e| {
 			PyValueError::new_err(format!("Failed to save SPZ file: {}", e))
@@ -651,6 +852,7 @@ impl GaussianSplat {
 	) -> PyResult<Bound<'py, PyBytes>> {
 		let pack_opts = spz_rs::gaussian_splat::SaveOptions {
 			coord_sys: coordinate_system.inner,
+			..Default::default()
 		};
 		let bytes = self
 			.inner
@@ -662,6 +864,112 @@ impl GaussianSplat {
 		Ok(PyBytes::new(py, &bytes))
 	}
 
+	/// Loads a `GaussianSplat` from a binary PLY file, as written by 3D
+	/// Gaussian Splatting training code.
+	///
+	/// # Args
+	///
+	/// * `path` - Path to the PLY file.
+	/// * `coordinate_system` - The coordinate system to convert the data to
+	///   from the PLY one (RDF).
+	///   Defaults to `UNSPECIFIED` (no conversion).
+	///
+	/// # Errors
+	///
+	/// Returns `ValueError` if the file cannot be read or is invalid.
+	#[staticmethod]
+	#[pyo3(signature = (path, coordinate_system=CoordinateSystem::UNSPECIFIED()))]
+	#[inline]
+	pub fn from_ply(path: &str, coordinate_system: CoordinateSystem) -> PyResult<Self> {
+		let opts = spz_rs::gaussian_splat::LoadOptions {
+			coord_sys: coordinate_system.inner,
+			..Default::default()
+		};
+		let inner = spz_rs::gaussian_splat::GaussianSplat::from_ply_file(path, &opts)
+			.map_err(|e| {
+				PyValueError::new_err(format!("Failed to load PLY file: {}", e))
+			})?;
+
+		Ok(Self { inner })
+	}
+
+	/// Loads a `GaussianSplat` from binary PLY bytes.
+	///
+	/// # Args
+	///
+	/// * `data` - The PLY file contents as bytes.
+	/// * `coordinate_system` - The coordinate system to convert the data to
+	///   from the PLY one (RDF).
+	///   Defaults to `UNSPECIFIED` (no conversion).
+	///
+	/// # Errors
+	///
+	/// Returns `ValueError` if the data is invalid.
+	#[staticmethod]
+	#[pyo3(signature = (data, coordinate_system=CoordinateSystem::UNSPECIFIED()))]
+	#[inline]
+	pub fn from_ply_bytes(data: &[u8], coordinate_system: CoordinateSystem) -> PyResult<Self> {
+		let opts = spz_rs::gaussian_splat::LoadOptions {
+			coord_sys: coordinate_system.inner,
+			..Default::default()
+		};
+		let inner = spz_rs::gaussian_splat::GaussianSplat::from_ply_bytes(data, &opts)
+			.map_err(|e| {
+				PyValueError::new_err(format!("Failed to parse PLY data: {}", e))
+			})?;
+
+		Ok(Self { inner })
+	}
+
+	/// Saves the `GaussianSplat` to a binary PLY file.
+	///
+	/// # Args
+	///
+	/// * `path` - Path to save the PLY file.
+	/// * `coordinate_system` - The coordinate system the data is in, it's
+	///   converted to the PLY one (RDF).
+	///   Defaults to `UNSPECIFIED` (no conversion).
+	#[pyo3(signature = (path, coordinate_system=CoordinateSystem::UNSPECIFIED()))]
+	#[inline]
+	pub fn save_ply(&self, path: &str, coordinate_system: CoordinateSystem) -> PyResult<()> {
+		let opts = spz_rs::gaussian_splat::SaveOptions {
+			coord_sys: coordinate_system.inner,
+			..Default::default()
+		};
+		self.inner.save_ply(path, &opts).map_err(|e| {
+			PyValueError::new_err(format!("Failed to save PLY file: {}", e))
+		})
+	}
+
+	/// Serializes the `GaussianSplat` to binary PLY bytes.
+	///
+	/// # Args
+	///
+	/// * `coordinate_system` - The coordinate system the data is in, it's
+	///   converted to the PLY one (RDF).
+	///   Defaults to `UNSPECIFIED` (no conversion).
+	///
+	/// # Returns
+	///
+	/// The PLY file contents as bytes.
+	#[pyo3(signature = (coordinate_system=CoordinateSystem::UNSPECIFIED()))]
+	#[inline]
+	pub fn to_ply_bytes<'py>(
+		&self,
+		py: Python<'py>,
+		coordinate_system: CoordinateSystem,
+	) -> PyResult<Bound<'py, PyBytes>> {
+		let opts = spz_rs::gaussian_splat::SaveOptions {
+			coord_sys: coordinate_system.inner,
+			..Default::default()
+		};
+		let bytes = self.inner.to_ply_bytes(&opts).map_err(|e| {
+			PyValueError::new_err(format!("Failed to serialize PLY: {}", e))
+		})?;
+
+		Ok(PyBytes::new(py, &bytes))
+	}
+
 	/// Converts coordinates to a different coordinate system.
 	///
 	/// # Args
@@ -695,54 +1003,79 @@ impl GaussianSplat {
 	}
 
 	/// Returns an `(N, 3)` array of `(x, y, z)` positions.
+	///
+	/// The array is a read-only view of the splat's data, see the class
+	/// docs.
 	#[inline]
 	#[getter]
-	pub fn positions<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyArray2<f32>>> {
-		let n = self.inner.header.num_points as usize;
-		let arr = PyArray1::from_slice(py, &self.inner.positions);
-		let reshaped = arr.reshape([n, 3])?;
+	pub fn positions<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, PyArray2<f32>>> {
+		let n = slf.borrow().inner.len();
 
-		Ok(reshaped)
+		readonly_view(slf, Ix2(n, 3), |gs| &gs.positions)
 	}
 
 	/// Returns an `(N, 3)` array of `(x, y, z)` log-scale values.
+	///
+	/// The array is a read-only view of the splat's data.
 	#[inline]
 	#[getter]
-	pub fn scales<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyArray2<f32>>> {
-		let n = self.inner.header.num_points as usize;
-		let arr = PyArray1::from_slice(py, &self.inner.scales);
-		let reshaped = arr.reshape([n, 3])?;
+	pub fn scales<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, PyArray2<f32>>> {
+		let n = slf.borrow().inner.len();
 
-		Ok(reshaped)
+		readonly_view(slf, Ix2(n, 3), |gs| &gs.scales)
 	}
 
 	/// Returns an `(N, 4)` array of `(w, x, y, z)` quaternion rotations.
+	///
+	/// The array is a read-only view of the splat's data.
 	#[inline]
 	#[getter]
-	pub fn rotations<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyArray2<f32>>> {
-		let n = self.inner.header.num_points as usize;
-		let arr = PyArray1::from_slice(py, &self.inner.rotations);
-		let reshaped = arr.reshape([n, 4])?;
+	pub fn rotations<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, PyArray2<f32>>> {
+		let n = slf.borrow().inner.len();
 
-		Ok(reshaped)
+		readonly_view(slf, Ix2(n, 4), |gs| &gs.rotations)
 	}
 
 	/// Returns an `(N,)` array of inverse-sigmoid opacity values.
+	///
+	/// The array is a read-only view of the splat's data.
 	#[inline]
 	#[getter]
-	pub fn alphas<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f32>> {
-		PyArray1::from_slice(py, &self.inner.alphas)
+	pub fn alphas<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, PyArray1<f32>>> {
+		let n = slf.borrow().inner.len();
+
+		readonly_view(slf, Ix1(n), |gs| &gs.alphas)
+	}
+
+	/// Returns an `(N,)` array of opacities in `[0, 1]`, i.e. `alphas` with
+	/// the sigmoid activation applied.
+	///
+	/// The array is a new copy, computed on every access.
+	#[getter]
+	pub fn opacities<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f32>> {
+		PyArray1::from_vec(py, self.inner.opacities())
+	}
+
+	/// Returns an `(N, 3)` array of world space scales, i.e. `exp` of
+	/// `scales`.
+	///
+	/// The array is a new copy, computed on every access.
+	#[getter]
+	pub fn linear_scales<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyArray2<f32>>> {
+		let n = self.inner.len();
+
+		PyArray1::from_vec(py, self.inner.linear_scales()).reshape([n, 3])
 	}
 
 	/// Returns an `(N, 3)` array of `(r, g, b)` SH0 color values.
+	///
+	/// The array is a read-only view of the splat's data.
 	#[inline]
 	#[getter]
-	pub fn colors<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyArray2<f32>>> {
-		let n = self.inner.header.num_points as usize;
-		let arr = PyArray1::from_slice(py, &self.inner.colors);
-		let reshaped = arr.reshape([n, 3])?;
+	pub fn colors<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, PyArray2<f32>>> {
+		let n = slf.borrow().inner.len();
 
-		Ok(reshaped)
+		readonly_view(slf, Ix2(n, 3), |gs| &gs.colors)
 	}
 
 	/// Returns an `(N, sh_dim * 3)` array of spherical harmonics coefficients.
@@ -750,29 +1083,27 @@ impl GaussianSplat {
 	/// The `sh_dim` depends on `sh_degree`: 0→0, 1→3, 2→8, 3→15.
 	///
 	/// Returns an empty `(N, 0)` array if `sh_degree` is 0.
+	///
+	/// The array is a read-only view of the splat's data.
 	#[inline]
 	#[getter]
 	pub fn spherical_harmonics<'py>(
-		&self,
-		py: Python<'py>,
+		slf: &Bound<'py, Self>,
 	) -> PyResult<Bound<'py, PyArray2<f32>>> {
-		let n = self.inner.header.num_points as usize;
-		let sh_dim =
-			spz_rs::math::dim_for_degree(self.inner.header.spherical_harmonics_degree);
-
-		if sh_dim == 0 {
-			// Return empty (N, 0) array
-			let arr = PyArray1::<f32>::from_slice(py, &[]);
-			let reshaped = arr.reshape([n, 0])?;
-
-			Ok(reshaped)
-		} else {
-			let arr = PyArray1::from_slice(py, &self.inner.spherical_harmonics);
-			// Return as (N, sh_dim * 3) for simplicity
-			let reshaped = arr.reshape([n, sh_dim as usize * 3])?;
-
-			Ok(reshaped)
-		}
+		let (n, sh_dim) = {
+			let this = slf.borrow();
+
+			(
+				this.inner.len(),
+				spz_rs::math::dim_for_degree(
+					this.inner.header.spherical_harmonics_degree,
+				),
+			)
+		};
+		// Return as (N, sh_dim * 3) for simplicity
+		readonly_view(slf, Ix2(n, sh_dim as usize * 3), |gs| {
+			&gs.spherical_harmonics
+		})
 	}
 
 	/// Returns the bounding box of the splat.
@@ -784,6 +1115,45 @@ impl GaussianSplat {
 		BoundingBox { inner }
 	}
 
+	/// Returns the center of the splat's bounding box.
+	///
+	/// Returns a tuple of `(x, y, z)` center coordinates.
+	#[getter]
+	#[inline]
+	pub fn center(&self) -> (f32, f32, f32) {
+		let [x, y, z] = self.inner.center();
+
+		(x, y, z)
+	}
+
+	/// Returns the extent of the splat's bounding box.
+	///
+	/// Returns a tuple of `(width, height, depth)`.
+	#[getter]
+	#[inline]
+	pub fn extent(&self) -> (f32, f32, f32) {
+		let [x, y, z] = self.inner.extent();
+
+		(x, y, z)
+	}
+
+	/// Returns the bytes each attribute array takes, keyed by attribute
+	/// name, plus their `total`.
+	#[getter]
+	pub fn memory_footprint(&self) -> HashMap<&'static str, usize> {
+		let report = self.inner.memory_footprint();
+
+		HashMap::from([
+			("positions", report.positions),
+			("scales", report.scales),
+			("rotations", report.rotations),
+			("alphas", report.alphas),
+			("colors", report.colors),
+			("spherical_harmonics", report.spherical_harmonics),
+			("total", report.total),
+		])
+	}
+
 	/// Returns the median ellipsoid volume of the Gaussians.
 	///
 	/// This is useful for understanding the typical size of the
@@ -857,8 +1227,374 @@ impl GaussianSplat {
 
 	#[inline]
 	pub fn __len__(&self) -> usize {
-		self.inner.header.num_points as usize
+		self.inner.len()
+	}
+
+	/// Returns a copy of the `i`-th gaussian, negative indices count from
+	/// the end.
+	///
+	/// Raises `IndexError` if `i` is out of range.
+	pub fn __getitem__(&self, i: isize) -> PyResult<Gaussian> {
+		let index = if i < 0 {
+			i.checked_add(self.__len__() as isize)
+		} else {
+			Some(i)
+		};
+		index.and_then(|index| usize::try_from(index).ok())
+			.filter(|&index| index < self.__len__())
+			.and_then(|index| self.inner.gaussian(index))
+			.map(Gaussian::from)
+			.ok_or_else(|| PyIndexError::new_err("gaussian index out of range"))
+	}
+
+	/// Iterates over copies of the gaussians.
+	#[inline]
+	pub fn __iter__(slf: Bound<'_, Self>) -> GaussianIterator {
+		GaussianIterator {
+			splat: slf.unbind(),
+			index: 0,
+		}
+	}
+
+	/// Compares the header and every attribute array exactly.
+	#[inline]
+	pub fn __eq__(&self, other: &Self) -> bool {
+		self.inner == other.inner
+	}
+
+	/// Pickles the splat losslessly as its raw header followed by the
+	/// attribute arrays as little-endian `f32`.
+	///
+	/// Unlike `to_bytes`, nothing is quantized, so the unpickled splat
+	/// compares equal to this one.
+	pub fn __reduce__<'py>(
+		slf: &Bound<'py, Self>,
+	) -> PyResult<(Bound<'py, PyAny>, (Bound<'py, PyBytes>,))> {
+		let this = slf.borrow();
+		let gs = &this.inner;
+
+		if !gs.check_sizes() {
+			return Err(PyValueError::new_err(
+				"Failed to pickle GaussianSplat: inconsistent sizes",
+			));
+		}
+		let arrays = [
+			&gs.positions,
+			&gs.scales,
+			&gs.rotations,
+			&gs.alphas,
+			&gs.colors,
+			&gs.spherical_harmonics,
+		];
+		let len = arrays.iter().map(|a| a.len()).sum::<usize>();
+		let mut state = Vec::with_capacity(header::HEADER_SIZE + len * 4);
+
+		gs.header.serialize_to(&mut state).map_err(|e| {
+			PyValueError::new_err(format!("Failed to pickle GaussianSplat: {}", e))
+		})?;
+
+		for v in arrays.into_iter().flatten() {
+			state.extend_from_slice(&v.to_le_bytes());
+		}
+		Ok((
+			slf.get_type().getattr("_from_state")?,
+			(PyBytes::new(slf.py(), &state),),
+		))
+	}
+
+	/// Unpickles a splat written by `__reduce__`.
+	#[staticmethod]
+	#[pyo3(name = "_from_state")]
+	pub fn from_state(state: &[u8]) -> PyResult<Self> {
+		let header = parse_header_state(state)?;
+		let num_points = header.num_points as usize;
+		let sh_dim =
+			spz_rs::math::dim_for_degree(header.spherical_harmonics_degree) as usize;
+		let lens = [3, 3, 4, 1, 3, sh_dim * 3].map(|stride| stride * num_points);
+		let body = &state[header::HEADER_SIZE..];
+
+		if body.len() != lens.iter().sum::<usize>() * 4 {
+			return Err(PyValueError::new_err(
+				"Failed to unpickle GaussianSplat: inconsistent sizes",
+			));
+		}
+		let mut floats = body
+			.chunks_exact(4)
+			.map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+		let [
+			positions,
+			scales,
+			rotations,
+			alphas,
+			colors,
+			spherical_harmonics,
+		] = lens.map(|len| floats.by_ref().take(len).collect::<Vec<_>>());
+
+		Ok(Self {
+			inner: spz_rs::gaussian_splat::GaussianSplat {
+				header,
+				positions,
+				scales,
+				rotations,
+				alphas,
+				colors,
+				spherical_harmonics,
+			},
+		})
 	}
+
+	/// Splats are mutable and may hold millions of points, so they are
+	/// not hashable.
+	#[classattr]
+	const __hash__: Option<Py<PyAny>> = None;
+}
+
+/// An SPZ file opened with [`open`], decoded lazily.
+///
+/// The header is read when the file is opened, so `header`, `num_points`,
+/// `sh_degree`, `antialiased`, `version` and `fractional_bits` are cheap.
+///
+/// Accessing `splat`, `bbox` or any of the array properties (`positions`,
+/// `scales`, `rotations`, `alphas`, `colors`, `spherical_harmonics`)
+/// decompresses and unpacks the whole file on first use; later accesses
+/// reuse the result. Leaving the `with` block releases the decoded splat,
+/// arrays returned earlier stay valid.
+///
+/// # Examples
+///
+/// ```python
+/// import spz
+///
+/// with spz.open("scene.spz") as f:
+///     print(f.num_points, f.sh_degree)  # header only
+///     positions = f.positions  # full load
+/// ```
+#[pyclass]
+pub struct SplatFile {
+	path: String,
+	coord_sys: CoordinateSystem,
+	header: header::Header,
+	splat: Option<Py<GaussianSplat>>,
+}
+
+impl SplatFile {
+	fn loaded<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, GaussianSplat>> {
+		if let Some(splat) = &self.splat {
+			return Ok(splat.bind(py).clone());
+		}
+		let splat =
+			Bound::new(py, GaussianSplat::load(&self.path, self.coord_sys.clone())?)?;
+
+		self.splat = Some(splat.clone().unbind());
+
+		Ok(splat)
+	}
+}
+
+#[pymethods]
+impl SplatFile {
+	#[inline]
+	pub fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+		slf
+	}
+
+	/// Releases the decoded splat, if any.
+	#[inline]
+	pub fn __exit__(
+		&mut self,
+		_exc_type: Option<Bound<'_, PyAny>>,
+		_exc_val: Option<Bound<'_, PyAny>>,
+		_exc_tb: Option<Bound<'_, PyAny>>,
+	) -> bool {
+		self.splat = None;
+
+		false
+	}
+
+	/// The path the file was opened from.
+	#[getter]
+	#[inline]
+	pub fn path(&self) -> &str {
+		&self.path
+	}
+
+	/// The file header. Doesn't force a full load.
+	#[getter]
+	#[inline]
+	pub fn header(&self) -> Header {
+		Header { inner: self.header }
+	}
+
+	/// The number of Gaussians. Doesn't force a full load.
+	#[getter]
+	#[inline]
+	pub fn num_points(&self) -> i32 {
+		self.header.num_points
+	}
+
+	/// The spherical harmonics degree. Doesn't force a full load.
+	#[getter]
+	#[inline]
+	pub fn sh_degree(&self) -> u8 {
+		self.header.spherical_harmonics_degree
+	}
+
+	/// Whether the splat was trained with antialiasing. Doesn't force a full
+	/// load.
+	#[getter]
+	#[inline]
+	pub fn antialiased(&self) -> bool {
+		self.header.flags.is_antialiased()
+	}
+
+	/// The SPZ format version. Doesn't force a full load.
+	#[getter]
+	#[inline]
+	pub fn version(&self) -> Version {
+		self.header.version.into()
+	}
+
+	/// The number of fractional bits used in position encoding. Doesn't
+	/// force a full load.
+	#[getter]
+	#[inline]
+	pub fn fractional_bits(&self) -> u8 {
+		self.header.fractional_bits
+	}
+
+	/// Whether the file has been decoded already.
+	#[getter]
+	#[inline]
+	pub fn is_loaded(&self) -> bool {
+		self.splat.is_some()
+	}
+
+	/// The decoded splat. Forces a full load.
+	#[getter]
+	#[inline]
+	pub fn splat<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, GaussianSplat>> {
+		self.loaded(py)
+	}
+
+	/// The bounding box of the splat. Forces a full load, since it's
+	/// computed from the positions.
+	#[getter]
+	#[inline]
+	pub fn bbox(&mut self, py: Python<'_>) -> PyResult<BoundingBox> {
+		Ok(self.loaded(py)?.borrow().bbox())
+	}
+
+	/// `(N, 3)` array of positions. Forces a full load.
+	#[getter]
+	#[inline]
+	pub fn positions<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyArray2<f32>>> {
+		GaussianSplat::positions(&self.loaded(py)?)
+	}
+
+	/// `(N, 3)` array of log-scales. Forces a full load.
+	#[getter]
+	#[inline]
+	pub fn scales<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyArray2<f32>>> {
+		GaussianSplat::scales(&self.loaded(py)?)
+	}
+
+	/// `(N, 4)` array of quaternion rotations. Forces a full load.
+	#[getter]
+	#[inline]
+	pub fn rotations<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyArray2<f32>>> {
+		GaussianSplat::rotations(&self.loaded(py)?)
+	}
+
+	/// `(N,)` array of inverse-sigmoid opacities. Forces a full load.
+	#[getter]
+	#[inline]
+	pub fn alphas<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyArray1<f32>>> {
+		GaussianSplat::alphas(&self.loaded(py)?)
+	}
+
+	/// `(N,)` array of opacities in `[0, 1]`. Forces a full load.
+	#[getter]
+	#[inline]
+	pub fn opacities<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyArray1<f32>>> {
+		Ok(self.loaded(py)?.borrow().opacities(py))
+	}
+
+	/// `(N, 3)` array of world space scales. Forces a full load.
+	#[getter]
+	#[inline]
+	pub fn linear_scales<'py>(
+		&mut self,
+		py: Python<'py>,
+	) -> PyResult<Bound<'py, PyArray2<f32>>> {
+		self.loaded(py)?.borrow().linear_scales(py)
+	}
+
+	/// `(N, 3)` array of SH0 colors. Forces a full load.
+	#[getter]
+	#[inline]
+	pub fn colors<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyArray2<f32>>> {
+		GaussianSplat::colors(&self.loaded(py)?)
+	}
+
+	/// `(N, sh_dim * 3)` array of spherical harmonics coefficients. Forces
+	/// a full load.
+	#[getter]
+	#[inline]
+	pub fn spherical_harmonics<'py>(
+		&mut self,
+		py: Python<'py>,
+	) -> PyResult<Bound<'py, PyArray2<f32>>> {
+		GaussianSplat::spherical_harmonics(&self.loaded(py)?)
+	}
+
+	#[inline]
+	pub fn __repr__(&self) -> String {
+		format!(
+			"SplatFile(path={:?}, num_points={}, sh_degree={}, loaded={})",
+			self.path,
+			self.header.num_points,
+			self.header.spherical_harmonics_degree,
+			self.splat.is_some()
+		)
+	}
+
+	#[inline]
+	pub fn __len__(&self) -> usize {
+		self.header.num_points.max(0) as usize
+	}
+}
+
+/// Opens an SPZ file, reading only its header.
+///
+/// The arrays are decoded on first access, see [`SplatFile`] for which
+/// properties force a full load. Use it to inspect the metadata of many
+/// files without paying for decompression.
+///
+/// # Args
+///
+/// * `path` - Path to the SPZ file.
+/// * `coordinate_system` - Target coordinate system for the data once it's
+///   loaded.
+///
+/// # Returns
+///
+/// The opened file, usable as a context manager.
+///
+/// # Errors
+///
+/// Returns `ValueError` if the file cannot be read or the header is invalid.
+#[inline]
+#[pyfunction]
+#[pyo3(name = "open", signature = (path, coordinate_system=CoordinateSystem::UNSPECIFIED()))]
+pub fn open_file(path: &str, coordinate_system: CoordinateSystem) -> PyResult<SplatFile> {
+	let header = Header::from_file(path)?.inner;
+
+	Ok(SplatFile {
+		path: path.to_owned(),
+		coord_sys: coordinate_system,
+		header,
+		splat: None,
+	})
 }
 
 /// Loads a [`GaussianSplat`] from an SPZ file.
@@ -910,6 +1646,7 @@ pub fn read_header(path: &str) -> PyResult<Header> {
 /// * [`CoordinateSystem`] - Enumeration of coordinate systems (RUB, RDF, etc.).
 /// * [`BoundingBox`] - Axis-aligned bounding box.
 /// * [`load`] - Load a GaussianSplat from an SPZ file.
+/// * [`open_file`] - Open an SPZ file lazily, as `spz.open`.
 ///
 /// # Examples
 ///
@@ -927,12 +1664,15 @@ pub fn read_header(path: &str) -> PyResult<Header> {
 #[inline]
 pub fn spz(m: &Bound<'_, PyModule>) -> PyResult<()> {
 	m.add_class::<GaussianSplat>()?;
+	m.add_class::<Gaussian>()?;
 	m.add_class::<CoordinateSystem>()?;
 	m.add_class::<BoundingBox>()?;
 	m.add_class::<Header>()?;
 	m.add_class::<Version>()?;
+	m.add_class::<SplatFile>()?;
 	m.add_function(wrap_pyfunction!(load, m)?)?;
 	m.add_function(wrap_pyfunction!(read_header, m)?)?;
+	m.add_function(wrap_pyfunction!(open_file, m)?)?;
 
 	Ok(())
 }
@@ -39,6 +39,11 @@ pub enum ConvertError {
 	/// The `n` value from Cap'n Proto exceeds `i32::MAX`.
 	#[error("n value {0} exceeds i32::MAX")]
 	NumPointsOverflow(u64),
+
+	/// The body list lengths don't match the header, see
+	/// [`spz::gaussian_splat::GaussianSplat::validate`].
+	#[error("inconsistent sizes: {0}")]
+	InconsistentSizes(String),
 }
 
 impl From<spz::header::Version> for spz_capnp::Version {
@@ -227,6 +232,9 @@ pub fn gaussian_splat_to_message(
 
 /// Reads a [`spz::gaussian_splat::GaussianSplat`] from a Cap'n Proto
 /// [`gaussian_splat::Reader`](spz_capnp::gaussian_splat::Reader).
+///
+/// Fails with [`ConvertError::InconsistentSizes`] if the body lists don't
+/// match the header, so malformed messages can't cause panics later on.
 pub fn gaussian_splat_from_reader(
 	reader: spz_capnp::gaussian_splat::Reader<'_>,
 ) -> Result<spz::gaussian_splat::GaussianSplat, ConvertError> {
@@ -234,23 +242,28 @@ pub fn gaussian_splat_from_reader(
 	let header = read_header_from_reader(hdr_reader)?;
 
 	let body = reader.get_body()?;
+	let positions = body.get_positions()?;
 
-	let positions = read_f32_list(body.get_positions()?);
-	let scales = read_f32_list(body.get_scales()?);
-	let rotations = read_f32_list(body.get_rotations()?);
-	let alphas = read_f32_list(body.get_alphas()?);
-	let colors = read_f32_list(body.get_colors()?);
-	let spherical_harmonics = read_f32_list(body.get_spherical_harmonics()?);
-
-	Ok(spz::gaussian_splat::GaussianSplat {
+	let mut ret = spz::gaussian_splat::GaussianSplat {
 		header,
-		positions,
-		scales,
-		rotations,
-		alphas,
-		colors,
-		spherical_harmonics,
-	})
+		..Default::default()
+	};
+	// Only preallocate from the header if the message backs it up, so a
+	// bogus point count can't trigger a huge allocation.
+	if positions.len() as usize == ret.header.num_points.max(0) as usize * 3 {
+		ret.reserve_exact_for_header();
+	}
+	read_f32_list_into(positions, &mut ret.positions);
+	read_f32_list_into(body.get_scales()?, &mut ret.scales);
+	read_f32_list_into(body.get_rotations()?, &mut ret.rotations);
+	read_f32_list_into(body.get_alphas()?, &mut ret.alphas);
+	read_f32_list_into(body.get_colors()?, &mut ret.colors);
+	read_f32_list_into(body.get_spherical_harmonics()?, &mut ret.spherical_harmonics);
+
+	ret.validate()
+		.map_err(|e| ConvertError::InconsistentSizes(e.to_string()))?;
+
+	Ok(ret)
 }
 
 /// Serializes an [`spz::gaussian_splat::GaussianSplat`] to Cap'n Proto wire
@@ -281,6 +294,122 @@ pub fn deserialize_from_bytes(
 	gaussian_splat_from_reader(root)
 }
 
+/// Serializes an [`spz::gaussian_splat::GaussianSplat`] to packed Cap'n
+/// Proto wire format bytes.
+///
+/// The packed encoding compresses zero bytes, which makes the message
+/// noticeably smaller than [`serialize_to_bytes`] for the large f32 lists,
+/// at a small CPU cost.
+pub fn serialize_to_packed_bytes(
+	splat: &spz::gaussian_splat::GaussianSplat,
+) -> Result<Vec<u8>, ConvertError> {
+	let msg = gaussian_splat_to_message(splat);
+	let mut buf = Vec::new();
+
+	capnp::serialize_packed::write_message(&mut buf, &msg).map_err(|e| {
+		ConvertError::CapnpErrorWithCtx {
+			error: e,
+			context: "unable to serialize GaussianSplat to packed bytes".to_string(),
+		}
+	})?;
+	Ok(buf)
+}
+
+/// Deserializes an [`spz::gaussian_splat::GaussianSplat`] from packed
+/// Cap'n Proto wire format bytes, as written by
+/// [`serialize_to_packed_bytes`].
+pub fn deserialize_from_packed_bytes(
+	bytes: &[u8],
+) -> Result<spz::gaussian_splat::GaussianSplat, ConvertError> {
+	let reader =
+		capnp::serialize_packed::read_message(bytes, message::ReaderOptions::default())?;
+	let root = reader.get_root::<spz_capnp::gaussian_splat::Reader<'_>>()?;
+
+	gaussian_splat_from_reader(root)
+}
+
+/// Serializes `splats` into a single Cap'n Proto stream, one message per
+/// splat, e.g. to store an asset bundle in one file.
+///
+/// Read them back with [`GaussianSplatStream`].
+pub fn serialize_many_to_writer<W>(
+	splats: &[spz::gaussian_splat::GaussianSplat],
+	w: &mut W,
+) -> Result<(), ConvertError>
+where
+	W: std::io::Write,
+{
+	for (i, splat) in splats.iter().enumerate() {
+		let msg = gaussian_splat_to_message(splat);
+
+		capnp::serialize::write_message(&mut *w, &msg).map_err(|e| {
+			ConvertError::CapnpErrorWithCtx {
+				error: e,
+				context: format!(
+					"unable to serialize GaussianSplat {i} to the stream"
+				),
+			}
+		})?;
+	}
+	Ok(())
+}
+
+/// Iterator over the splats of a Cap'n Proto stream written by
+/// [`serialize_many_to_writer`], yielding one splat per message until the
+/// stream ends.
+///
+/// Iteration stops after the first error. Messages are read in many small
+/// reads, so wrap unbuffered readers like files in a
+/// [`BufReader`](std::io::BufReader).
+pub struct GaussianSplatStream<R> {
+	reader: R,
+	done: bool,
+}
+
+impl<R> GaussianSplatStream<R>
+where
+	R: std::io::Read,
+{
+	#[inline]
+	pub fn new(reader: R) -> Self {
+		Self {
+			reader,
+			done: false,
+		}
+	}
+}
+
+impl<R> Iterator for GaussianSplatStream<R>
+where
+	R: std::io::Read,
+{
+	type Item = Result<spz::gaussian_splat::GaussianSplat, ConvertError>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done {
+			return None;
+		}
+		let ret = match capnp::serialize::try_read_message(
+			&mut self.reader,
+			message::ReaderOptions::default(),
+		) {
+			Ok(Some(msg)) => msg
+				.get_root::<spz_capnp::gaussian_splat::Reader<'_>>()
+				.map_err(ConvertError::from)
+				.and_then(gaussian_splat_from_reader),
+			Ok(None) => {
+				self.done = true;
+
+				return None;
+			},
+			Err(e) => Err(e.into()),
+		};
+		self.done = ret.is_err();
+
+		Some(ret)
+	}
+}
+
 /// Which body field to set. Avoids duplicating the init+copy pattern six times.
 enum BodyField {
 	Positions,
@@ -310,8 +439,12 @@ fn set_gaussian_splat_body_f32_list(
 	}
 }
 
-fn read_f32_list(list: capnp::primitive_list::Reader<'_, f32>) -> Vec<f32> {
-	(0..list.len()).map(|i| list.get(i)).collect()
+/// Replaces the contents of `dst` with `list`, reserving the exact length up
+/// front so that filling it doesn't reallocate.
+fn read_f32_list_into(list: capnp::primitive_list::Reader<'_, f32>, dst: &mut Vec<f32>) {
+	dst.clear();
+	dst.reserve_exact(list.len() as usize);
+	dst.extend((0..list.len()).map(|i| list.get(i)));
 }
 
 #[cfg(test)]
@@ -657,6 +790,47 @@ mod tests {
 		assert_eq!(original.spherical_harmonics, restored.spherical_harmonics);
 	}
 
+	#[test]
+	fn gaussian_splat_serialize_deserialize_packed_roundtrip() {
+		let mut original = make_test_splat();
+
+		original.header.fractional_bits = 16;
+
+		let bytes = serialize_to_packed_bytes(&original)
+			.expect("unable to serialize to packed bytes");
+		let restored = deserialize_from_packed_bytes(&bytes).unwrap();
+
+		assert_eq!(original.header.version, restored.header.version);
+		assert_eq!(original.header.num_points, restored.header.num_points);
+		assert_eq!(
+			original.header.spherical_harmonics_degree,
+			restored.header.spherical_harmonics_degree
+		);
+		assert_eq!(restored.header.fractional_bits, 16);
+		assert_eq!(original.header.flags, restored.header.flags);
+		assert_eq!(original.positions, restored.positions);
+		assert_eq!(original.scales, restored.scales);
+		assert_eq!(original.rotations, restored.rotations);
+		assert_eq!(original.alphas, restored.alphas);
+		assert_eq!(original.colors, restored.colors);
+		assert_eq!(original.spherical_harmonics, restored.spherical_harmonics);
+	}
+
+	#[test]
+	fn gaussian_splat_packed_is_smaller() {
+		let splat = make_test_splat();
+		let unpacked = serialize_to_bytes(&splat).expect("unable to serialize to bytes");
+		let packed = serialize_to_packed_bytes(&splat)
+			.expect("unable to serialize to packed bytes");
+
+		assert!(
+			packed.len() < unpacked.len(),
+			"packed {} bytes, unpacked {} bytes",
+			packed.len(),
+			unpacked.len()
+		);
+	}
+
 	#[test]
 	fn gaussian_splat_empty_body() {
 		let splat = spz::gaussian_splat::GaussianSplat {
@@ -919,4 +1093,102 @@ mod tests {
 		assert_eq!(reader.get_fractional_bits(), 8);
 		assert_eq!(reader.get_flags(), 0x01);
 	}
+
+	#[test]
+	fn gaussian_splat_large_roundtrip() {
+		const N: usize = 10_000;
+
+		let splat = spz::gaussian_splat::GaussianSplat {
+			header: spz::header::Header {
+				num_points: N as i32,
+				spherical_harmonics_degree: 3,
+				..Default::default()
+			},
+			positions: (0..N * 3).map(|i| i as f32).collect(),
+			scales: (0..N * 3).map(|i| -(i as f32)).collect(),
+			rotations: (0..N * 4).map(|i| i as f32 * 0.5).collect(),
+			alphas: (0..N).map(|i| i as f32 * 0.25).collect(),
+			colors: (0..N * 3).map(|i| i as f32 * 2.0).collect(),
+			spherical_harmonics: (0..N * 45).map(|i| i as f32 * 0.01).collect(),
+		};
+
+		let bytes = serialize_to_bytes(&splat).expect("unable to serialize to bytes");
+		let restored =
+			deserialize_from_bytes(&bytes).expect("unable to deserialize from bytes");
+
+		assert!(restored.check_sizes());
+		assert_eq!(restored.positions, splat.positions);
+		assert_eq!(restored.scales, splat.scales);
+		assert_eq!(restored.rotations, splat.rotations);
+		assert_eq!(restored.alphas, splat.alphas);
+		assert_eq!(restored.colors, splat.colors);
+		assert_eq!(restored.spherical_harmonics, splat.spherical_harmonics);
+	}
+
+	#[test]
+	fn gaussian_splat_stream_roundtrip() {
+		let splats: Vec<_> = [0_u8, 1, 3]
+			.into_iter()
+			.map(|degree| {
+				let sh_len = [0, 9, 24, 45][degree as usize] * 2;
+				let mut splat = make_test_splat();
+
+				splat.header.spherical_harmonics_degree = degree;
+				splat.spherical_harmonics =
+					(0..sh_len).map(|i| i as f32 * 0.01).collect();
+				splat
+			})
+			.collect();
+		let mut bytes = Vec::new();
+
+		serialize_many_to_writer(&splats, &mut bytes).expect("unable to serialize stream");
+
+		let restored = GaussianSplatStream::new(bytes.as_slice())
+			.collect::<Result<Vec<_>, _>>()
+			.expect("unable to deserialize stream");
+
+		assert_eq!(restored, splats);
+
+		// a truncated trailing message is an error, after which the
+		// iteration ends
+		bytes.truncate(bytes.len() - 4);
+
+		let mut stream = GaussianSplatStream::new(bytes.as_slice());
+
+		assert!(stream.next().unwrap().is_ok());
+		assert!(stream.next().unwrap().is_ok());
+		assert!(stream.next().unwrap().is_err());
+		assert!(stream.next().is_none());
+	}
+
+	#[test]
+	fn gaussian_splat_stream_empty() {
+		let mut bytes = Vec::new();
+
+		serialize_many_to_writer(&[], &mut bytes).expect("unable to serialize stream");
+
+		assert!(bytes.is_empty());
+		assert!(GaussianSplatStream::new(bytes.as_slice()).next().is_none());
+	}
+
+	#[rstest]
+	#[case::positions_short(2, 3)]
+	#[case::positions_long(1, 9)]
+	fn gaussian_splat_inconsistent_sizes(
+		#[case] num_points: i32,
+		#[case] positions_len: usize,
+	) {
+		let mut splat = make_test_splat();
+
+		splat.header.num_points = num_points;
+		splat.positions = vec![1.0; positions_len];
+
+		let bytes = serialize_to_bytes(&splat).expect("unable to serialize to bytes");
+		let err = deserialize_from_bytes(&bytes).unwrap_err();
+
+		assert!(
+			matches!(&err, ConvertError::InconsistentSizes(msg) if msg.contains("positions")),
+			"expected InconsistentSizes, got: {err:?}"
+		);
+	}
 }
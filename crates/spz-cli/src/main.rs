@@ -3,8 +3,9 @@
 use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use clap::{Parser, Subcommand};
+use serde::Serialize;
 
 use spz::prelude::*;
 
@@ -33,7 +34,108 @@ enum Commands {
 	Info {
 		/// Path to the SPZ file.
 		spz_path: PathBuf,
+		/// Print machine-readable JSON instead of the human-readable text.
+		#[arg(long)]
+		json: bool,
 	},
+	/// Display distribution statistics of an SPZ file, to help diagnose
+	/// bad captures.
+	Stats {
+		/// Path to the SPZ file.
+		spz_path: PathBuf,
+	},
+	/// Convert between SPZ and PLY files, picked by the file extensions,
+	/// optionally changing the coordinate system of the data.
+	///
+	/// Each format's own storage coordinate system (RUB for SPZ, RDF for
+	/// PLY) is handled automatically. `--from-coord` and `--to-coord`
+	/// re-orient the scene on top of that.
+	Convert {
+		/// Path to the input `.spz` or `.ply` file.
+		input: PathBuf,
+		/// Path to the output `.spz` or `.ply` file.
+		output: PathBuf,
+		/// Coordinate system of the scene in the input, e.g. `RDF`.
+		#[arg(long, value_parser = parse_coord, default_value = "unspecified")]
+		from_coord: CoordinateSystem,
+		/// Coordinate system to convert the scene to, e.g. `RUB`.
+		#[arg(long, value_parser = parse_coord, default_value = "unspecified")]
+		to_coord: CoordinateSystem,
+	},
+	/// Reduce the number of gaussians of an SPZ or PLY file, e.g. to produce
+	/// levels of detail for the web.
+	///
+	/// Either keeps `--target` gaussians sampled by opacity, or the most
+	/// opaque gaussian of each `--voxel` sized cube.
+	Downsample {
+		/// Path to the input `.spz` or `.ply` file.
+		input: PathBuf,
+		/// Path to the output `.spz` or `.ply` file.
+		output: PathBuf,
+		/// Number of gaussians to keep.
+		#[arg(long, conflicts_with = "voxel", required_unless_present = "voxel")]
+		target: Option<usize>,
+		/// Edge length of the voxels to keep one gaussian of.
+		#[arg(long)]
+		voxel: Option<f32>,
+		/// Seed of the `--target` sampling.
+		#[arg(long, default_value_t = 0)]
+		seed: u64,
+	},
+	/// Compare two SPZ or PLY files, e.g. to regression test conversion
+	/// pipelines.
+	///
+	/// Reports header mismatches and, if the point counts match, the mean
+	/// and max absolute difference of each attribute. Rotations are compared
+	/// by `1 - |dot|` of the normalized quaternions. Fails if the counts,
+	/// SH degrees or flags differ, or if any difference exceeds
+	/// `--tolerance`, so it can gate CI.
+	Diff {
+		/// Path to the 1st `.spz` or `.ply` file.
+		a: PathBuf,
+		/// Path to the 2nd `.spz` or `.ply` file.
+		b: PathBuf,
+		/// Largest allowed absolute difference of any attribute value.
+		#[arg(long, default_value_t = 0.0)]
+		tolerance: f32,
+	},
+}
+
+/// Output of `info --json`.
+#[derive(Serialize, Debug)]
+struct Info {
+	num_points: i32,
+	sh_degree: u8,
+	antialiased: bool,
+	version: i32,
+	fractional_bits: u8,
+	bbox: BoundingBox,
+	median_volume: f32,
+}
+
+/// File formats `convert` reads and writes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+	Spz,
+	Ply,
+}
+
+impl Format {
+	fn from_path(path: &Path) -> Result<Self> {
+		let ext = path
+			.extension()
+			.and_then(|e| e.to_str())
+			.map(str::to_ascii_lowercase);
+
+		match ext.as_deref() {
+			Some("spz") => Ok(Format::Spz),
+			Some("ply") => Ok(Format::Ply),
+			_ => bail!(
+				"unsupported file extension, expected .spz or .ply: {:?}",
+				path
+			),
+		}
+	}
 }
 
 fn main() -> Result<ExitCode> {
@@ -52,19 +154,278 @@ fn run() -> Result<()> {
 
 	match cli.command {
 		Commands::Metainfo { spz_path: file } => cmd_metainfo(&file),
-		Commands::Info { spz_path: file } => cmd_info(&file),
+		Commands::Info {
+			spz_path: file,
+			json,
+		} => cmd_info(&file, json),
+		Commands::Stats { spz_path: file } => cmd_stats(&file),
+		Commands::Convert {
+			input,
+			output,
+			from_coord,
+			to_coord,
+		} => cmd_convert(&input, &output, from_coord, to_coord),
+		Commands::Downsample {
+			input,
+			output,
+			target,
+			voxel,
+			seed,
+		} => cmd_downsample(&input, &output, target, voxel, seed),
+		Commands::Diff { a, b, tolerance } => cmd_diff(&a, &b, tolerance),
 	}
 }
 
-fn cmd_info<P>(spz_path: P) -> Result<()>
+fn parse_coord(s: &str) -> Result<CoordinateSystem, String> {
+	let coord = CoordinateSystem::from(s);
+
+	if coord == CoordinateSystem::Unspecified && !s.eq_ignore_ascii_case("unspecified") {
+		return Err(format!(
+			"unknown coordinate system: {s}, expected e.g. RUB or RDF"
+		));
+	}
+	Ok(coord)
+}
+
+/// Loads an SPZ or PLY file, picked by its extension, in RUB.
+fn load_splat(path: &Path) -> Result<GaussianSplat> {
+	let opts = LoadOptions::builder()
+		.coord_sys(CoordinateSystem::RightUpBack)
+		.build();
+
+	match Format::from_path(path)? {
+		Format::Spz => GaussianSplat::load_with(path, &opts).map_err(Into::into),
+		Format::Ply => GaussianSplat::from_ply_file(path, &opts),
+	}
+	.with_context(|| format!("failed to load {:?}", path))
+}
+
+fn cmd_convert(
+	input: &Path,
+	output: &Path,
+	from_coord: CoordinateSystem,
+	to_coord: CoordinateSystem,
+) -> Result<()> {
+	let out_format = Format::from_path(output)?;
+	let save_opts = SaveOptions::builder()
+		.coord_sys(CoordinateSystem::RightUpBack)
+		.build();
+
+	let mut gs = load_splat(input)?;
+
+	gs.convert_coordinates(from_coord, to_coord);
+
+	match out_format {
+		Format::Spz => gs.save(output, &save_opts),
+		Format::Ply => gs.save_ply(output, &save_opts),
+	}
+	.with_context(|| format!("failed to save {:?}", output))?;
+
+	println!(
+		"converted {:?} -> {:?}: {} points, sh degree {}",
+		input, output, gs.header.num_points, gs.header.spherical_harmonics_degree
+	);
+	Ok(())
+}
+
+fn cmd_downsample(
+	input: &Path,
+	output: &Path,
+	target: Option<usize>,
+	voxel: Option<f32>,
+	seed: u64,
+) -> Result<()> {
+	let out_format = Format::from_path(output)?;
+	let save_opts = SaveOptions::builder()
+		.coord_sys(CoordinateSystem::RightUpBack)
+		.build();
+
+	let mut gs = load_splat(input)?;
+
+	let before = gs.len();
+
+	match (target, voxel) {
+		(Some(target), None) => gs.decimate(target, seed),
+		(None, Some(voxel)) => gs.voxel_downsample(voxel)?,
+		_ => bail!("expected exactly one of --target or --voxel"),
+	}
+	match out_format {
+		Format::Spz => gs.save(output, &save_opts),
+		Format::Ply => gs.save_ply(output, &save_opts),
+	}
+	.with_context(|| format!("failed to save {:?}", output))?;
+
+	let file_size = std::fs::metadata(output)
+		.with_context(|| format!("failed to stat {:?}", output))?
+		.len();
+	// raw float attribute bytes per byte of the written file
+	let ratio = gs.memory_footprint().total as f64 / file_size.max(1) as f64;
+
+	println!(
+		"downsampled {:?} -> {:?}: {} -> {} points, {} bytes, compression ratio {:.2}",
+		input,
+		output,
+		before,
+		gs.len(),
+		file_size,
+		ratio
+	);
+	Ok(())
+}
+
+fn cmd_diff(a: &Path, b: &Path, tolerance: f32) -> Result<()> {
+	let gs_a = load_splat(a)?;
+	let gs_b = load_splat(b)?;
+	let (ha, hb) = (&gs_a.header, &gs_b.header);
+	let mut failed = false;
+
+	println!("Diff:");
+	println!(
+		"\tNumber of points:\t{} vs {} (delta {})",
+		gs_a.len(),
+		gs_b.len(),
+		gs_b.len() as i64 - gs_a.len() as i64
+	);
+	println!(
+		"\tSH degree:\t\t{} vs {}",
+		ha.spherical_harmonics_degree, hb.spherical_harmonics_degree
+	);
+	println!("\tVersion:\t\t{} vs {}", ha.version, hb.version);
+	println!("\tFlags:\t\t\t{:#04x} vs {:#04x}", ha.flags.0, hb.flags.0);
+
+	failed |= gs_a.len() != gs_b.len()
+		|| ha.spherical_harmonics_degree != hb.spherical_harmonics_degree
+		|| ha.flags != hb.flags;
+
+	if gs_a.len() == gs_b.len() {
+		let mut attrs = vec![
+			("positions", abs_diff(&gs_a.positions, &gs_b.positions)),
+			("scales", abs_diff(&gs_a.scales, &gs_b.scales)),
+			("rotations", rotation_diff(&gs_a.rotations, &gs_b.rotations)),
+			("alphas", abs_diff(&gs_a.alphas, &gs_b.alphas)),
+			("colors", abs_diff(&gs_a.colors, &gs_b.colors)),
+		];
+		if ha.spherical_harmonics_degree == hb.spherical_harmonics_degree {
+			attrs.push((
+				"spherical harmonics",
+				abs_diff(&gs_a.spherical_harmonics, &gs_b.spherical_harmonics),
+			));
+		}
+		println!("\tAbsolute differences (mean, max):");
+
+		for (name, (mean, max)) in attrs {
+			println!("\t\t{:<21}{mean}, {max}", format!("{name}:"));
+
+			failed |= max > tolerance;
+		}
+	}
+	if failed {
+		bail!("files differ beyond the tolerance of {tolerance}");
+	}
+	Ok(())
+}
+
+/// Returns the mean and max absolute difference of `a` and `b`, counting
+/// a `NaN` against anything but the same `NaN` as infinite.
+fn abs_diff(a: &[f32], b: &[f32]) -> (f32, f32) {
+	diff_stats(a.iter().zip(b).map(|(x, y)| {
+		if x.to_bits() == y.to_bits() {
+			0.0
+		} else {
+			(x - y).abs()
+		}
+	}))
+}
+
+/// Returns the mean and max of `1 - |dot|` of the normalized quaternions in
+/// `a` and `b`, since `q` and `-q` are the same rotation.
+fn rotation_diff(a: &[f32], b: &[f32]) -> (f32, f32) {
+	diff_stats(a.chunks_exact(4).zip(b.chunks_exact(4)).map(|(x, y)| {
+		let dot = x.iter().zip(y).map(|(x, y)| x * y).sum::<f32>();
+		let norms = x.iter().map(|v| v * v).sum::<f32>().sqrt()
+			* y.iter().map(|v| v * v).sum::<f32>().sqrt();
+
+		if norms == 0.0 {
+			if x == y { 0.0 } else { 1.0 }
+		} else {
+			1.0 - (dot / norms).abs()
+		}
+	}))
+}
+
+fn diff_stats(diffs: impl Iterator<Item = f32>) -> (f32, f32) {
+	let (mut sum, mut max, mut n) = (0.0_f64, 0.0_f32, 0_usize);
+
+	for d in diffs {
+		let d = if d.is_nan() { f32::INFINITY } else { d };
+
+		sum += d as f64;
+		max = max.max(d);
+		n += 1;
+	}
+	if n == 0 {
+		return (0.0, 0.0);
+	}
+	((sum / n as f64) as f32, max)
+}
+
+fn cmd_info<P>(spz_path: P, json: bool) -> Result<()>
 where
 	P: AsRef<Path>,
 {
 	let gs = GaussianSplat::load(spz_path.as_ref())
 		.with_context(|| format!("failed to load SPZ file: {:?}", spz_path.as_ref()))?;
 
-	print!("{}", gs.pretty_fmt());
+	if json {
+		let info = Info {
+			num_points: gs.header.num_points,
+			sh_degree: gs.header.spherical_harmonics_degree,
+			antialiased: gs.header.flags.is_antialiased(),
+			version: gs.header.version as i32,
+			fractional_bits: gs.header.fractional_bits,
+			bbox: gs.bbox(),
+			median_volume: gs.median_volume(),
+		};
+		println!("{}", serde_json::to_string(&info)?);
+	} else {
+		print!("{}", gs.pretty_fmt());
+	}
+
+	Ok(())
+}
 
+fn cmd_stats<P>(spz_path: P) -> Result<()>
+where
+	P: AsRef<Path>,
+{
+	let gs = GaussianSplat::load(spz_path.as_ref())
+		.with_context(|| format!("failed to load SPZ file: {:?}", spz_path.as_ref()))?;
+	let stats = gs.stats();
+	let bbox = &stats.bbox;
+
+	println!("Stats:");
+	println!("\tNumber of points:\t{}", gs.header.num_points);
+	println!("\tNon-finite values:\t{}", stats.non_finite);
+	println!("\tPosition extents:");
+	println!("\t\tx: {} to {}", bbox.min_x, bbox.max_x);
+	println!("\t\ty: {} to {}", bbox.min_y, bbox.max_y);
+	println!("\t\tz: {} to {}", bbox.min_z, bbox.max_z);
+	println!("\tLog-scale sum percentiles:");
+	println!("\t\tp10: {}", stats.scale_sum_p10);
+	println!("\t\tp50: {}", stats.scale_sum_p50);
+	println!("\t\tp90: {}", stats.scale_sum_p90);
+	println!("\tOpacity histogram:");
+
+	let bins = stats.opacity_histogram.len();
+
+	for (i, count) in stats.opacity_histogram.iter().enumerate() {
+		println!(
+			"\t\t{:.1} to {:.1}:\t{}",
+			i as f32 / bins as f32,
+			(i + 1) as f32 / bins as f32,
+			count
+		);
+	}
 	Ok(())
 }
 
@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use spz::prelude::*;
+
+use crate::util::{grid_splat, mktmp};
+
+#[path = "../../spz/tests/util.rs"]
+mod util;
+
+#[test]
+fn test_info_json() {
+	let filename = mktmp().join("spz_cli_info_json.spz");
+	let gs = GaussianSplat {
+		header: Header {
+			num_points: 2,
+			spherical_harmonics_degree: 1,
+			..Default::default()
+		},
+		positions: vec![-1.0, -2.0, -3.0, 1.0, 2.0, 3.0],
+		scales: vec![0.0; 6],
+		rotations: vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+		alphas: vec![0.0; 2],
+		colors: vec![0.0; 6],
+		spherical_harmonics: vec![0.0; 18],
+	};
+	gs.save(&filename, &SaveOptions::default())
+		.expect("failed to save splat");
+
+	let output = Command::new(env!("CARGO_BIN_EXE_spz"))
+		.args(["info", "--json"])
+		.arg(&filename)
+		.output()
+		.expect("failed to run spz");
+
+	assert!(output.status.success(), "{output:?}");
+
+	let info: serde_json::Value =
+		serde_json::from_slice(&output.stdout).expect("output is not valid JSON");
+
+	assert_eq!(info["num_points"], 2);
+	assert_eq!(info["sh_degree"], 1);
+	assert_eq!(info["antialiased"], false);
+	assert_eq!(info["version"], 3);
+	assert_eq!(info["fractional_bits"], 12);
+	assert_eq!(info["bbox"]["min_x"], -1.0);
+	assert_eq!(info["bbox"]["max_z"], 3.0);
+	assert!(info["median_volume"].is_number());
+}
+
+#[test]
+fn test_downsample() {
+	let input = mktmp().join("spz_cli_downsample_in.spz");
+	let target = mktmp().join("spz_cli_downsample_target.spz");
+	let voxel = mktmp().join("spz_cli_downsample_voxel.spz");
+
+	grid_splat()
+		.save(&input, &SaveOptions::default())
+		.expect("failed to save splat");
+
+	let output = Command::new(env!("CARGO_BIN_EXE_spz"))
+		.args(["downsample", "--target", "3"])
+		.arg(&input)
+		.arg(&target)
+		.output()
+		.expect("failed to run spz");
+
+	assert!(output.status.success(), "{output:?}");
+	assert!(String::from_utf8_lossy(&output.stdout).contains("8 -> 3 points"));
+	assert_eq!(GaussianSplat::load(&target).unwrap().len(), 3);
+
+	let output = Command::new(env!("CARGO_BIN_EXE_spz"))
+		.args(["downsample", "--voxel", "2"])
+		.arg(&input)
+		.arg(&voxel)
+		.output()
+		.expect("failed to run spz");
+
+	assert!(output.status.success(), "{output:?}");
+	assert_eq!(GaussianSplat::load(&voxel).unwrap().len(), 1);
+
+	for args in [&["--target", "3", "--voxel", "2"][..], &[]] {
+		let output = Command::new(env!("CARGO_BIN_EXE_spz"))
+			.arg("downsample")
+			.args(args)
+			.arg(&input)
+			.arg(&target)
+			.output()
+			.expect("failed to run spz");
+
+		assert!(!output.status.success(), "{args:?}: {output:?}");
+	}
+}
+
+#[test]
+fn test_diff() {
+	let a = mktmp().join("spz_cli_diff_a.spz");
+	let b = mktmp().join("spz_cli_diff_b.spz");
+	let c = mktmp().join("spz_cli_diff_c.spz");
+	let diff = |other: &PathBuf, tolerance: &str| {
+		Command::new(env!("CARGO_BIN_EXE_spz"))
+			.args(["diff", "--tolerance", tolerance])
+			.arg(&a)
+			.arg(other)
+			.output()
+			.expect("failed to run spz")
+	};
+	let mut gs = grid_splat();
+
+	gs.save(&a, &SaveOptions::default())
+		.expect("failed to save splat");
+
+	gs.alphas[0] = 0.5;
+	gs.save(&b, &SaveOptions::default())
+		.expect("failed to save splat");
+
+	gs.decimate(4, 0);
+	gs.save(&c, &SaveOptions::default())
+		.expect("failed to save splat");
+
+	let output = diff(&a, "0");
+
+	assert!(output.status.success(), "{output:?}");
+
+	let output = diff(&b, "0.1");
+
+	assert_eq!(output.status.code(), Some(1), "{output:?}");
+	assert!(String::from_utf8_lossy(&output.stdout).contains("alphas:"));
+
+	let output = diff(&b, "1");
+
+	assert!(output.status.success(), "{output:?}");
+
+	let output = diff(&c, "1");
+
+	assert_eq!(output.status.code(), Some(1), "{output:?}");
+	assert!(String::from_utf8_lossy(&output.stdout).contains("8 vs 4 (delta -4)"));
+}
@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use codspeed_criterion_compat::Criterion;
+use spz::{
+	compression::Compression,
+	gaussian_splat::{GaussianSplat, SaveOptions},
+	packed::PackedGaussianSplat,
+};
+
+const RACOON_FAMILY: &str = "../../assets/racoonfamily.spz";
+
+/// Compares compressed size and decode time of the supported codecs.
+pub fn bench_compression_codecs(c: &mut Criterion) {
+	let gs = GaussianSplat::load(RACOON_FAMILY).expect("failed to load");
+
+	let codecs = [
+		Compression::Gzip,
+		#[cfg(feature = "zstd")]
+		Compression::Zstd,
+	];
+	for codec in codecs {
+		let bytes = gs
+			.serialize_to_packed_bytes(
+				&SaveOptions::builder().compression(codec).build(),
+			)
+			.expect("failed to serialize");
+
+		println!("racoonfamily.spz {codec}: {} bytes", bytes.len());
+
+		c.bench_function(&format!("decode_racoonfamily_{codec}"), |b| {
+			b.iter(|| {
+				PackedGaussianSplat::from_bytes(&bytes).expect("failed to decode")
+			});
+		});
+	}
+}
@@ -0,0 +1,21 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use codspeed_criterion_compat::{BatchSize, Criterion};
+
+use crate::benchmarks::util;
+
+pub fn bench_merge(c: &mut Criterion) {
+	let gs = util::create_splat(500_000);
+	let other = util::create_splat(500_000);
+
+	c.bench_function("splat_merge_500_000_pts", |b| {
+		b.iter_batched(
+			|| gs.clone(),
+			|mut gs| {
+				gs.merge(&other).unwrap();
+				gs
+			},
+			BatchSize::LargeInput,
+		);
+	});
+}
@@ -10,5 +10,7 @@ criterion_group! {
 	benchmarks::load::bench_cloud_load_n,
 	benchmarks::save::bench_cloud_save_n,
 	benchmarks::print_info::bench_print_info,
+	benchmarks::compression::bench_compression_codecs,
+	benchmarks::merge::bench_merge,
 }
 criterion_main!(benches);
@@ -1,7 +1,234 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use std::io::Write;
+
+use anyhow::{Result, bail};
+use arbitrary::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+/// Container compression used for the packed gaussian data.
+///
+/// SPZ files are gzip compressed. Zstd is an extension offering faster
+/// decoding and requires the `zstd` feature.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Arbitrary)]
+pub enum Compression {
+	#[default]
+	Gzip,
+	Zstd,
+}
+
+impl Compression {
+	/// Magic bytes every stream of this kind starts with.
+	#[inline]
+	pub const fn magic(&self) -> &'static [u8] {
+		match self {
+			Compression::Gzip => &[0x1f, 0x8b],
+			Compression::Zstd => &[0x28, 0xb5, 0x2f, 0xfd],
+		}
+	}
+
+	/// Detects the compression from the leading magic bytes.
+	#[inline]
+	pub fn detect(compressed: &[u8]) -> Option<Self> {
+		[Compression::Gzip, Compression::Zstd]
+			.into_iter()
+			.find(|c| compressed.starts_with(c.magic()))
+	}
+}
+
+impl std::fmt::Display for Compression {
+	#[inline]
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Compression::Gzip => write!(f, "gzip"),
+			Compression::Zstd => write!(f, "zstd"),
+		}
+	}
+}
+
+/// Compress data with the given compression.
+pub fn compress_bytes(
+	decompressed: &[u8],
+	compressed: &mut Vec<u8>,
+	compression: Compression,
+) -> Result<()> {
+	match compression {
+		Compression::Gzip => gzip::compress_bytes(decompressed, compressed),
+		#[cfg(feature = "zstd")]
+		Compression::Zstd => zstd::compress_bytes(decompressed, compressed),
+		#[cfg(not(feature = "zstd"))]
+		Compression::Zstd => bail!("zstd compression requires the `zstd` feature"),
+	}
+}
+
+/// Compress the data `write` produces straight into `writer`, without
+/// buffering it uncompressed first.
+///
+/// # Returns
+///
+/// `writer`, after the compressed stream is finished.
+pub fn compress_to<W, F>(writer: W, compression: Compression, write: F) -> Result<W>
+where
+	W: Write,
+	F: FnOnce(&mut dyn Write) -> Result<()>,
+{
+	match compression {
+		Compression::Gzip => gzip::compress_to(writer, write),
+		#[cfg(feature = "zstd")]
+		Compression::Zstd => zstd::compress_to(writer, write),
+		#[cfg(not(feature = "zstd"))]
+		Compression::Zstd => bail!("zstd compression requires the `zstd` feature"),
+	}
+}
+
+/// Decompress all data into the given buffer.
+///
+/// If `compression` is `None`, it is detected from the magic bytes.
+pub fn decompress_end<C, D>(
+	compressed: C,
+	decompressed: D,
+	compression: Option<Compression>,
+) -> Result<()>
+where
+	C: AsRef<[u8]>,
+	D: AsMut<Vec<u8>>,
+{
+	match resolve(compressed.as_ref(), compression)? {
+		Compression::Gzip => gzip::decompress_end(compressed, decompressed),
+		#[cfg(feature = "zstd")]
+		Compression::Zstd => zstd::decompress_end(compressed, decompressed),
+		#[cfg(not(feature = "zstd"))]
+		Compression::Zstd => bail!("zstd decompression requires the `zstd` feature"),
+	}
+}
+
+/// Decompress the beginning of the data into the given buffer, detecting
+/// the compression from the magic bytes.
+///
+/// # Returns
+///
+/// The number of bytes written to the start of the buffer, less than its
+/// length if the (possibly truncated) data ends early.
+pub fn decompress<C, D>(compressed: C, decompressed: D) -> Result<usize>
+where
+	C: AsRef<[u8]>,
+	D: AsMut<[u8]>,
+{
+	match resolve(compressed.as_ref(), None)? {
+		Compression::Gzip => gzip::decompress(compressed, decompressed),
+		#[cfg(feature = "zstd")]
+		Compression::Zstd => zstd::decompress(compressed, decompressed),
+		#[cfg(not(feature = "zstd"))]
+		Compression::Zstd => bail!("zstd decompression requires the `zstd` feature"),
+	}
+}
+
+#[inline]
+fn resolve(compressed: &[u8], compression: Option<Compression>) -> Result<Compression> {
+	if let Some(compression) = compression {
+		return Ok(compression);
+	}
+	match Compression::detect(compressed) {
+		Some(compression) => Ok(compression),
+		None => bail!("unknown compression, expected gzip or zstd magic bytes"),
+	}
+}
+
+#[cfg(feature = "zstd")]
+pub mod zstd {
+	use std::io::{Read, Write};
+
+	use anyhow::Context;
+	use anyhow::Result;
+
+	/// Compress data using zstd compression.
+	#[inline]
+	pub fn compress_bytes(decompressed: &[u8], compressed: &mut Vec<u8>) -> Result<()> {
+		compressed.clear();
+		compressed.reserve(decompressed.len() / 4);
+
+		::zstd::stream::copy_encode(
+			decompressed,
+			compressed,
+			::zstd::DEFAULT_COMPRESSION_LEVEL,
+		)
+		.with_context(|| "unable to compress")
+	}
+
+	/// Compress the data `write` produces into `writer` using zstd
+	/// compression.
+	#[inline]
+	pub fn compress_to<W, F>(writer: W, write: F) -> Result<W>
+	where
+		W: Write,
+		F: FnOnce(&mut dyn Write) -> Result<()>,
+	{
+		let mut encoder = ::zstd::stream::write::Encoder::new(
+			writer,
+			::zstd::DEFAULT_COMPRESSION_LEVEL,
+		)
+		.with_context(|| "unable to create encoder")?;
+
+		write(&mut encoder)?;
+
+		encoder.finish().with_context(|| "unable to compress")
+	}
+
+	/// Decompress zstd-compressed data into the given buffer.
+	#[inline]
+	pub fn decompress_end<C, D>(compressed: C, mut decompressed: D) -> Result<()>
+	where
+		C: AsRef<[u8]>,
+		D: AsMut<Vec<u8>>,
+	{
+		let mut decoder = ::zstd::stream::read::Decoder::new(compressed.as_ref())?;
+
+		decoder.read_to_end(decompressed.as_mut())
+			.with_context(|| "unable to decompress to end")?;
+
+		Ok(())
+	}
+
+	/// Decompress zstd-compressed data into the given buffer, stopping once
+	/// the buffer is full or the data ends.
+	///
+	/// `compressed` may be truncated, e.g. only the first block of a file,
+	/// the output then ends where the data does.
+	///
+	/// # Returns
+	///
+	/// The number of bytes written to the start of the buffer.
+	#[inline]
+	pub fn decompress<C, D>(compressed: C, mut decompressed: D) -> Result<usize>
+	where
+		C: AsRef<[u8]>,
+		D: AsMut<[u8]>,
+	{
+		let mut decoder = ::zstd::stream::read::Decoder::new(compressed.as_ref())?;
+		let buf = decompressed.as_mut();
+		let mut filled = 0_usize;
+
+		while filled < buf.len() {
+			let n = match decoder.read(&mut buf[filled..]) {
+				Ok(0) => break,
+				Ok(n) => n,
+				Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => {
+					break;
+				},
+				Err(err) => {
+					return Err(err).with_context(
+						|| "unable to decompress into the given buffer",
+					);
+				},
+			};
+			filled += n;
+		}
+		Ok(filled)
+	}
+}
+
 pub mod gzip {
-	use std::io::{BufRead, BufReader, Read};
+	use std::io::{BufRead, BufReader, Read, Write};
 
 	use anyhow::Context;
 	use anyhow::Result;
@@ -35,6 +262,21 @@ pub mod gzip {
 		Ok(())
 	}
 
+	/// Compress the data `write` produces into `writer` using gzip
+	/// compression.
+	#[inline]
+	pub fn compress_to<W, F>(writer: W, write: F) -> Result<W>
+	where
+		W: Write,
+		F: FnOnce(&mut dyn Write) -> Result<()>,
+	{
+		let mut encoder = flate2::write::GzEncoder::new(writer, Compression::default());
+
+		write(&mut encoder)?;
+
+		encoder.finish().with_context(|| "unable to compress")
+	}
+
 	/// Decompress gzip-compressed data into the given buffer.
 	#[inline]
 	pub fn decompress_end<C, D>(compressed: C, mut decompressed: D) -> Result<()>
@@ -51,26 +293,48 @@ pub mod gzip {
 		Ok(())
 	}
 
-	/// Decompress gzip-compressed data into the given buffer.
+	/// Decompress gzip-compressed data into the given buffer, stopping once
+	/// the buffer is full or the data ends.
+	///
+	/// `compressed` may be truncated, e.g. only the first block of a file,
+	/// the output then ends where the data does.
+	///
+	/// # Returns
+	///
+	/// The number of bytes written to the start of the buffer.
 	#[inline]
-	pub fn decompress<C, D>(compressed: C, mut decompressed: D) -> Result<()>
+	pub fn decompress<C, D>(compressed: C, mut decompressed: D) -> Result<usize>
 	where
 		C: AsRef<[u8]>,
 		D: AsMut<[u8]>,
 	{
 		let mut gz_decoder = GzDecoder::new(compressed.as_ref());
-
-		gz_decoder
-			.read(decompressed.as_mut())
-			.with_context(|| "unable to decompress into the given buffer")?;
-
-		Ok(())
+		let buf = decompressed.as_mut();
+		let mut filled = 0_usize;
+
+		while filled < buf.len() {
+			let n = match gz_decoder.read(&mut buf[filled..]) {
+				Ok(0) => break,
+				Ok(n) => n,
+				Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => {
+					break;
+				},
+				Err(err) => {
+					return Err(err).with_context(
+						|| "unable to decompress into the given buffer",
+					);
+				},
+			};
+			filled += n;
+		}
+		Ok(filled)
 	}
 }
 
 #[cfg(test)]
 mod tests {
-	use super::gzip;
+	use super::*;
+	use rstest::rstest;
 
 	#[test]
 	fn test_compress_decompress_roundtrip() {
@@ -126,6 +390,24 @@ mod tests {
 		assert!(decompressed.is_empty());
 	}
 
+	#[test]
+	fn test_compress_to_matches_compress_bytes() {
+		let original: Vec<u8> = (0..100_000).map(|i| (i % 251) as u8).collect();
+		let streamed = compress_to(Vec::new(), Compression::Gzip, |w| {
+			for chunk in original.chunks(1000) {
+				w.write_all(chunk)?;
+			}
+			Ok(())
+		})
+		.expect("compression failed");
+		let mut expected = Vec::new();
+
+		compress_bytes(&original, &mut expected, Compression::Gzip)
+			.expect("compression failed");
+
+		assert_eq!(streamed, expected);
+	}
+
 	#[test]
 	fn test_decompress_invalid_data() {
 		let bad_data = vec![0xDE, 0xAD, 0xBE, 0xEF];
@@ -163,4 +445,109 @@ mod tests {
 		assert_eq!(compressed[0], 0x1F);
 		assert_eq!(compressed[1], 0x8B);
 	}
+
+	#[rstest]
+	#[case(&[0x1f, 0x8b, 0x08], Some(Compression::Gzip))]
+	#[case(&[0x28, 0xb5, 0x2f, 0xfd, 0x00], Some(Compression::Zstd))]
+	#[case(&[0x1f], None)]
+	#[case(&[], None)]
+	fn test_compression_detect(#[case] bytes: &[u8], #[case] expected: Option<Compression>) {
+		assert_eq!(Compression::detect(bytes), expected);
+	}
+
+	#[test]
+	fn test_compress_decompress_auto_gzip() {
+		let original: Vec<u8> = (0..10_000).map(|i| (i % 251) as u8).collect();
+		let mut compressed = Vec::new();
+
+		compress_bytes(&original, &mut compressed, Compression::Gzip).unwrap();
+
+		assert_eq!(Compression::detect(&compressed), Some(Compression::Gzip));
+
+		let mut decompressed = Vec::new();
+
+		decompress_end(&compressed, &mut decompressed, None).unwrap();
+
+		assert_eq!(decompressed, original);
+	}
+
+	#[test]
+	fn test_decompress_unknown_compression_fails() {
+		let mut decompressed = Vec::new();
+
+		assert!(decompress_end([0xDE, 0xAD, 0xBE, 0xEF], &mut decompressed, None).is_err());
+	}
+
+	#[cfg(feature = "zstd")]
+	#[test]
+	fn test_compress_decompress_zstd_roundtrip() {
+		let original: Vec<u8> = (0..10_000).map(|i| (i % 251) as u8).collect();
+		let mut compressed = Vec::new();
+
+		compress_bytes(&original, &mut compressed, Compression::Zstd).unwrap();
+
+		assert_eq!(Compression::detect(&compressed), Some(Compression::Zstd));
+		assert!(compressed.len() < original.len());
+
+		let mut decompressed = Vec::new();
+
+		decompress_end(&compressed, &mut decompressed, None).unwrap();
+
+		assert_eq!(decompressed, original);
+
+		let mut prefix = [0_u8; 16];
+
+		decompress(&compressed, &mut prefix).unwrap();
+
+		assert_eq!(prefix.as_slice(), &original[..16]);
+	}
+
+	#[cfg(not(feature = "zstd"))]
+	#[test]
+	fn test_zstd_without_feature_fails() {
+		let mut compressed = Vec::new();
+
+		assert!(compress_bytes(b"data", &mut compressed, Compression::Zstd).is_err());
+	}
+
+	#[test]
+	fn test_gzip_decompress_fills_buffer() {
+		// incompressible, so the output spans many inflate blocks and reads
+		let mut state = 0x2545_f491_u32;
+		let original: Vec<u8> = (0..300_000)
+			.map(|_| {
+				state ^= state << 13;
+				state ^= state >> 17;
+				state ^= state << 5;
+				state as u8
+			})
+			.collect();
+		let mut compressed = Vec::new();
+
+		gzip::compress_bytes(&original, &mut compressed).expect("compression failed");
+
+		let mut prefix = vec![0_u8; 200_000];
+		let filled =
+			gzip::decompress(&compressed, &mut prefix).expect("decompression failed");
+
+		assert_eq!(filled, prefix.len());
+		assert_eq!(prefix.as_slice(), &original[..prefix.len()]);
+
+		// a buffer larger than the data is filled up to its end
+		let mut oversized = vec![0_u8; original.len() + 10];
+		let filled = gzip::decompress(&compressed, &mut oversized)
+			.expect("decompression failed");
+
+		assert_eq!(filled, original.len());
+		assert_eq!(&oversized[..original.len()], original.as_slice());
+		assert!(oversized[original.len()..].iter().all(|&b| b == 0));
+
+		// truncated data ends the output instead of failing
+		let mut partial = vec![0_u8; original.len()];
+		let filled = gzip::decompress(&compressed[..512], &mut partial)
+			.expect("decompression failed");
+
+		assert!(filled < 512, "{filled}");
+		assert_eq!(&partial[..filled], &original[..filled]);
+	}
 }
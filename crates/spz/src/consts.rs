@@ -13,6 +13,21 @@ use strum::EnumIter;
 /// smaller value.
 pub const COLOR_SCALE: f32 = 0.15;
 
+/// Zeroth order spherical harmonics basis constant, converts the DC color
+/// coefficient to RGB as `0.5 + SH_C0 * color`.
+pub const SH_C0: f32 = 0.282_094_8;
+
+/// Default number of fractional bits of the 24-bit fixed point positions.
+pub const DEFAULT_FRACTIONAL_BITS: u8 = 12;
+
+/// Maximum number of fractional bits of the 24-bit fixed point positions,
+/// leaving the sign bit as the only integer bit.
+pub const MAX_FRACTIONAL_BITS: u8 = 23;
+
+/// Default maximum number of points a loaded splat may have, see
+/// [`LoadOptions::max_points`](crate::gaussian_splat::LoadOptions::max_points).
+pub const MAX_POINTS_TO_READ: usize = 10_000_000;
+
 /// Standard file extensions for SPZ files.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
 pub enum Extensions {
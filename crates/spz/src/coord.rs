@@ -3,7 +3,7 @@
 use std::str::FromStr;
 
 use arbitrary::Arbitrary;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
 use strum::EnumIter;
 
 /// Supported 3D coordinate systems for Gaussian splat data.
@@ -15,12 +15,18 @@ use strum::EnumIter;
 /// These are specified in the [`LoadOptions`](crate::gaussian_splat::LoadOptions)
 /// and [`SaveOptions`](crate::gaussian_splat::SaveOptions) respectively.
 /// If the coordinate system is `Unspecified`, data will be saved and loaded
-/// without conversion, which may harm interoperability.
+/// without conversion, which may harm interoperability: the data is treated
+/// as if it already were RUB, SPZ's internal system. Set `strict_coord_sys`
+/// in the options to make this an error instead.
 ///
 /// Enum item values follow the original Niantic C++ SPZ values.
-#[derive(
-	EnumIter, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Arbitrary,
-)]
+///
+/// Serializes as the short form, e.g. `"RDF"`, see
+/// [`CoordinateSystem::as_short_str`]. Deserializes from every form
+/// [`FromStr`] accepts, including the long names like `"RightDownFront"`
+/// of older versions, failing on unknown names instead of falling back to
+/// `Unspecified`.
+#[derive(EnumIter, Clone, Copy, Debug, Default, PartialEq, Eq, Arbitrary)]
 pub enum CoordinateSystem {
 	#[default]
 	Unspecified = 0,
@@ -102,6 +108,44 @@ impl From<&str> for CoordinateSystem {
 	}
 }
 
+impl Serialize for CoordinateSystem {
+	#[inline]
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(self.as_short_str())
+	}
+}
+
+impl<'de> Deserialize<'de> for CoordinateSystem {
+	#[inline]
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		const VARIANTS: &[&str] = &[
+			"LDB",
+			"RDB",
+			"LUB",
+			"RUB",
+			"LDF",
+			"RDF",
+			"LUF",
+			"RUF",
+			"UNSPECIFIED",
+		];
+		let s = std::borrow::Cow::<'de, str>::deserialize(deserializer)?;
+		let coord = CoordinateSystem::from(s.as_ref());
+
+		if coord == CoordinateSystem::Unspecified && !s.eq_ignore_ascii_case("unspecified")
+		{
+			return Err(de::Error::unknown_variant(&s, VARIANTS));
+		}
+		Ok(coord)
+	}
+}
+
 impl CoordinateSystem {
 	/// Returns a short 3-letter abbreviation for the coordinate system.
 	///
@@ -115,7 +159,7 @@ impl CoordinateSystem {
 	/// # Example
 	///
 	/// ```
-	/// use spz::CoordinateSystem;
+	/// use spz::coord::CoordinateSystem;
 	///
 	/// assert_eq!(CoordinateSystem::RightDownFront.as_short_str(), "RDF");
 	/// assert_eq!(CoordinateSystem::LeftUpFront.as_short_str(), "LUF");
@@ -134,6 +178,30 @@ impl CoordinateSystem {
 		}
 	}
 
+	/// Returns `true` if the system is right-handed, i.e. `x × y = z`.
+	///
+	/// Flipping a single axis changes the handedness, so RUB, RDF, LUF and
+	/// LDB are right-handed, the other four left-handed. Returns `false` for
+	/// [`CoordinateSystem::Unspecified`], whose handedness is unknown.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use spz::coord::CoordinateSystem;
+	///
+	/// assert!(CoordinateSystem::RightDownFront.is_right_handed());
+	/// assert!(!CoordinateSystem::RightUpFront.is_right_handed());
+	/// ```
+	pub const fn is_right_handed(&self) -> bool {
+		matches!(
+			self,
+			CoordinateSystem::RightUpBack
+				| CoordinateSystem::RightDownFront
+				| CoordinateSystem::LeftUpFront
+				| CoordinateSystem::LeftDownBack
+		)
+	}
+
 	/// Computes the axis flip multipliers needed to convert from `self` to `target`.
 	///
 	/// Returns an [`AxisFlips`], containing sign multipliers (`1.0` or `-1.0`) for
@@ -144,7 +212,7 @@ impl CoordinateSystem {
 	/// # Example
 	///
 	/// ```
-	/// use spz::CoordinateSystem;
+	/// use spz::coord::CoordinateSystem;
 	///
 	/// // PLY uses RightDownFront, GLB uses LeftUpFront
 	/// let axis_flips = CoordinateSystem::RightDownFront.axis_flips_to(CoordinateSystem::LeftUpFront);
@@ -187,6 +255,17 @@ impl CoordinateSystem {
 		}
 	}
 
+	/// Computes the axis flip multipliers needed to convert from `source` to
+	/// `self`, i.e. `source.axis_flips_to(self)`.
+	///
+	/// Useful to undo a conversion done with [`Self::axis_flips_to`]. Sign
+	/// flips are their own inverse, so both directions yield the same
+	/// multipliers, see [`AxisFlips::inverse`].
+	#[inline]
+	pub const fn axis_flips_from(self, source: CoordinateSystem) -> AxisFlips {
+		source.axis_flips_to(self)
+	}
+
 	/// Compares axis orientations between two coordinate systems.
 	///
 	/// Returns a tuple of booleans `(x, y, z)` indicating whether each axis
@@ -201,7 +280,7 @@ impl CoordinateSystem {
 	/// # Example
 	///
 	/// ```
-	/// use spz::CoordinateSystem;
+	/// use spz::coord::CoordinateSystem;
 	///
 	/// // RightUpBack vs LeftUpFront: X differs, Y matches, Z differs
 	/// let (x, y, z) = CoordinateSystem::RightUpBack.axes_align(CoordinateSystem::LeftUpFront);
@@ -234,10 +313,11 @@ impl CoordinateSystem {
 /// # Example
 ///
 /// ```
-/// use spz::{CoordinateSystem, AxisFlips};
+/// use spz::coord::{AxisFlips, CoordinateSystem};
 ///
 /// // Convert from PLY (RightDownFront) to GLB (LeftUpFront)
-/// let flips = CoordinateSystem::RightDownFront.converter(CoordinateSystem::LeftUpFront);
+/// let flips: AxisFlips =
+///     CoordinateSystem::RightDownFront.axis_flips_to(CoordinateSystem::LeftUpFront);
 ///
 /// // X and Y axes differ, Z matches
 /// assert_eq!(flips.position, [-1.0, -1.0, 1.0]);
@@ -252,6 +332,20 @@ pub struct AxisFlips {
 	pub spherical_harmonics: [f32; 15],
 }
 
+impl AxisFlips {
+	/// Returns the multipliers undoing these flips.
+	///
+	/// Multiplying by `-1.0` twice is the identity, so this is the
+	/// element-wise reciprocal, which for valid flips equals `self`.
+	pub fn inverse(&self) -> AxisFlips {
+		AxisFlips {
+			position: self.position.map(f32::recip),
+			rotation: self.rotation.map(f32::recip),
+			spherical_harmonics: self.spherical_harmonics.map(f32::recip),
+		}
+	}
+}
+
 impl Default for AxisFlips {
 	#[inline]
 	fn default() -> Self {
@@ -459,4 +553,95 @@ mod tests {
 		assert_eq!(flips.rotation, [1.0, 1.0, 1.0]);
 		assert_eq!(flips.spherical_harmonics, [1.0; 15]);
 	}
+
+	#[test]
+	fn test_axis_flips_inverse_is_identity() {
+		let apply = |v: &[f32], flips: &[f32]| {
+			v.iter().zip(flips).map(|(v, f)| v * f).collect::<Vec<_>>()
+		};
+		let sh = (1..=15).map(|i| i as f32).collect::<Vec<_>>();
+
+		for from in CoordinateSystem::iter() {
+			for to in CoordinateSystem::iter() {
+				let flips = from.axis_flips_to(to);
+				let inverse = flips.inverse();
+
+				assert_eq!(to.axis_flips_from(from), flips);
+				assert_eq!(from.axis_flips_from(to), inverse);
+
+				let p = apply(
+					&apply(&[1.0, 2.0, 3.0], &flips.position),
+					&inverse.position,
+				);
+				let r = apply(
+					&apply(&[4.0, 5.0, 6.0], &flips.rotation),
+					&inverse.rotation,
+				);
+				let s = apply(
+					&apply(&sh, &flips.spherical_harmonics),
+					&inverse.spherical_harmonics,
+				);
+
+				assert_eq!(p, [1.0, 2.0, 3.0], "{from:?} -> {to:?}");
+				assert_eq!(r, [4.0, 5.0, 6.0], "{from:?} -> {to:?}");
+				assert_eq!(s, sh, "{from:?} -> {to:?}");
+			}
+		}
+	}
+
+	#[rstest]
+	#[case("\"RDF\"")]
+	#[case("\"rdf\"")]
+	#[case("\"Right-Down-Front\"")]
+	#[case("\"RightDownFront\"")]
+	#[case("\"RIGHT_DOWN_FRONT\"")]
+	fn test_deserialize(#[case] json: &str) {
+		let cs: CoordinateSystem = serde_json::from_str(json).unwrap();
+
+		assert_eq!(cs, CoordinateSystem::RightDownFront);
+	}
+
+	#[rstest]
+	#[case("\"RDX\"")]
+	#[case("\"\"")]
+	#[case("\"up\"")]
+	fn test_deserialize_unknown(#[case] json: &str) {
+		let err = serde_json::from_str::<CoordinateSystem>(json).unwrap_err();
+
+		assert!(err.to_string().contains("unknown variant"), "{err}");
+	}
+
+	#[test]
+	fn test_serde_roundtrip() {
+		for cs in CoordinateSystem::iter() {
+			let json = serde_json::to_string(&cs).unwrap();
+
+			assert_eq!(json, format!("\"{}\"", cs.as_short_str()));
+			assert_eq!(serde_json::from_str::<CoordinateSystem>(&json).unwrap(), cs);
+		}
+	}
+
+	#[rstest]
+	#[case(CoordinateSystem::LeftDownBack, true)]
+	#[case(CoordinateSystem::RightDownBack, false)]
+	#[case(CoordinateSystem::LeftUpBack, false)]
+	#[case(CoordinateSystem::RightUpBack, true)]
+	#[case(CoordinateSystem::LeftDownFront, false)]
+	#[case(CoordinateSystem::RightDownFront, true)]
+	#[case(CoordinateSystem::LeftUpFront, true)]
+	#[case(CoordinateSystem::RightUpFront, false)]
+	fn test_is_right_handed(#[case] cs: CoordinateSystem, #[case] expected: bool) {
+		assert_eq!(cs.is_right_handed(), expected);
+
+		// handedness flips with every flipped axis
+		let (x, y, z) = cs.axes_align(CoordinateSystem::RightUpBack);
+		let flips = [x, y, z].iter().filter(|m| !**m).count();
+
+		assert_eq!(flips % 2 == 0, expected);
+	}
+
+	#[test]
+	fn test_is_right_handed_unspecified() {
+		assert!(!CoordinateSystem::Unspecified.is_right_handed());
+	}
 }
@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! File-type sniffing for Gaussian Splat containers.
+
+use std::io::Read;
+
+use flate2::bufread::GzDecoder;
+
+use crate::compression::Compression;
+use crate::header::Header;
+
+/// Binary glTF (`.glb`) magic bytes.
+pub const GLTF_MAGIC: [u8; 4] = *b"glTF";
+
+/// Kind of file detected by [`detect`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FileKind {
+	/// Gzip (or, with the `zstd` feature, zstd) compressed stream starting
+	/// with the SPZ header magic.
+	Spz,
+	/// PLY, ASCII or binary.
+	Ply,
+	/// Binary glTF (`.glb`).
+	Gltf,
+	/// Anything else, including compressed streams that don't start with
+	/// the SPZ header magic.
+	Unknown,
+}
+
+impl std::fmt::Display for FileKind {
+	#[inline]
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			FileKind::Spz => write!(f, "SPZ"),
+			FileKind::Ply => write!(f, "PLY"),
+			FileKind::Gltf => write!(f, "glTF"),
+			FileKind::Unknown => write!(f, "Unknown"),
+		}
+	}
+}
+
+/// Detects the kind of file from its leading bytes.
+///
+/// For gzip streams only the first few bytes are decompressed to look for
+/// the SPZ header magic, so passing the whole file is cheap.
+pub fn detect(bytes: &[u8]) -> FileKind {
+	match Compression::detect(bytes) {
+		Some(Compression::Gzip) => {
+			let mut magic = [0_u8; 4];
+
+			return match GzDecoder::new(bytes).read_exact(&mut magic) {
+				Ok(()) if magic == Header::magic_bytes() => FileKind::Spz,
+				_ => FileKind::Unknown,
+			};
+		},
+		#[cfg(feature = "zstd")]
+		Some(Compression::Zstd) => {
+			let mut magic = [0_u8; 4];
+
+			return match crate::compression::zstd::decompress(bytes, &mut magic) {
+				Ok(4) if magic == Header::magic_bytes() => FileKind::Spz,
+				_ => FileKind::Unknown,
+			};
+		},
+		#[cfg(not(feature = "zstd"))]
+		Some(Compression::Zstd) => return FileKind::Unknown,
+		None => {},
+	}
+	if bytes.starts_with(b"ply\n") || bytes.starts_with(b"ply\r\n") {
+		return FileKind::Ply;
+	}
+	if bytes.starts_with(&GLTF_MAGIC) {
+		return FileKind::Gltf;
+	}
+	FileKind::Unknown
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+
+	use crate::compression::gzip;
+
+	fn gzipped(data: &[u8]) -> Vec<u8> {
+		let mut compressed = Vec::new();
+
+		gzip::compress_bytes(data, &mut compressed).unwrap();
+		compressed
+	}
+
+	#[rstest]
+	#[case(b"ply\nformat binary_little_endian 1.0\n".to_vec(), FileKind::Ply)]
+	#[case(b"ply\r\nformat ascii 1.0\r\n".to_vec(), FileKind::Ply)]
+	#[case(b"glTF\x02\x00\x00\x00".to_vec(), FileKind::Gltf)]
+	#[case(b"NGSP".to_vec(), FileKind::Unknown)]
+	#[case(b"plyx".to_vec(), FileKind::Unknown)]
+	#[case(vec![], FileKind::Unknown)]
+	#[case(vec![0x1f, 0x8b], FileKind::Unknown)]
+	fn test_detect_raw(#[case] bytes: Vec<u8>, #[case] expected: FileKind) {
+		assert_eq!(detect(&bytes), expected);
+	}
+
+	#[test]
+	fn test_detect_spz() {
+		let header: [u8; 16] = Header::default().into();
+
+		assert_eq!(detect(&gzipped(&header)), FileKind::Spz);
+	}
+
+	#[cfg(feature = "zstd")]
+	#[test]
+	fn test_detect_zstd_spz() {
+		let header: [u8; 16] = Header::default().into();
+		let mut compressed = Vec::new();
+
+		crate::compression::zstd::compress_bytes(&header, &mut compressed).unwrap();
+
+		assert_eq!(detect(&compressed), FileKind::Spz);
+	}
+
+	#[test]
+	fn test_detect_gzip_without_spz_magic() {
+		assert_eq!(detect(&gzipped(b"not a splat")), FileKind::Unknown);
+	}
+}
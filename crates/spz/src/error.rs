@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Error type of the loading API.
+
+use thiserror::Error;
+
+/// Errors returned when loading and decoding SPZ data.
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum SpzError {
+	/// Reading the file or stream failed.
+	#[error("io error: {0}")]
+	Io(#[from] std::io::Error),
+
+	/// There is no data to decode, not even a header.
+	#[error("data is empty")]
+	Empty,
+
+	/// The container could not be decompressed.
+	#[error("unable to decompress data: {0:#}")]
+	Decompress(anyhow::Error),
+
+	/// The decompressed data doesn't start with
+	/// [`MAGIC_VALUE`](crate::header::MAGIC_VALUE).
+	#[error("bad magic: {0:#010x}")]
+	BadMagic(i32),
+
+	/// The header's version is not supported by this crate.
+	#[error("unsupported version: {0}")]
+	UnsupportedVersion(i32),
+
+	/// The header has a valid magic and version but fails validation
+	/// otherwise, e.g. unknown flags or an out of range SH degree.
+	#[error("header fails validation")]
+	HeaderValidation,
+
+	/// The data ends before the section is fully read.
+	#[error("read error ({section}): {source}")]
+	Truncated {
+		section: &'static str,
+		source: std::io::Error,
+	},
+
+	/// The header claims more points than the rest of the data can hold.
+	#[error("header claims {num_points} points, but the data only holds {max}")]
+	NumPointsExceedsData { num_points: i32, max: usize },
+
+	/// The header claims more points than
+	/// [`LoadOptions::max_points`](crate::gaussian_splat::LoadOptions::max_points)
+	/// allows.
+	#[error("{num_points} points exceed the limit of {max}")]
+	TooManyPoints { num_points: i32, max: usize },
+
+	/// The attribute arrays don't match the number of points and the
+	/// spherical harmonics degree.
+	#[error("inconsistent sizes")]
+	InconsistentSizes,
+
+	/// A rotation's stored components are too large to be part of a unit
+	/// quaternion, only reported with `strict_rotations` set in the load
+	/// options.
+	#[error("corrupt rotation of gaussian {index}")]
+	CorruptRotation { index: usize },
+
+	/// The coordinate system is
+	/// [`Unspecified`](crate::coord::CoordinateSystem::Unspecified) while
+	/// `strict_coord_sys` is set in the load or save options.
+	#[error("coordinate system is unspecified")]
+	UnspecifiedCoordinateSystem,
+
+	/// Any other error.
+	#[error(transparent)]
+	Other(#[from] anyhow::Error),
+}
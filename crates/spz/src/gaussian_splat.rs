@@ -1,5 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use std::collections::HashMap;
 use std::path::Path;
 use std::{fmt::Write, io::Read};
 
@@ -10,16 +11,24 @@ use serde::{Deserialize, Serialize};
 use tokio::io::AsyncReadExt;
 
 use crate::{
-	compression, consts,
+	compression::{self, Compression},
+	consts,
 	coord::{AxisFlips, CoordinateSystem},
-	header::{Flags, Header},
+	error::SpzError,
+	header::{Flags, Header, Version},
 	math::{self, dim_for_degree},
 	mmap,
-	packed::PackedGaussianSplat,
+	packed::{PackOptions, PackedGaussianSplat},
+	ply,
+	unpacked::UnpackedGaussian,
 };
 
 /// A set of Gaussian Splats representing a 3D scene.
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Arbitrary)]
+///
+/// The [`Debug`](std::fmt::Debug) output only shows the array lengths, so it
+/// stays short for real files. The alternate `{:#?}` form also shows the
+/// first few elements of each array.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize, Arbitrary)]
 pub struct GaussianSplat {
 	/// Header data for the splat.
 	pub header: Header,
@@ -78,6 +87,70 @@ impl GaussianSplat {
 		GaussianSplatBuilder::default()
 	}
 
+	/// Creates a splat from spherical harmonics kept per color channel, as
+	/// many training frameworks do, interleaving them into the
+	/// coefficient-major layout of [`GaussianSplat::spherical_harmonics`].
+	///
+	/// # Args
+	///
+	/// * `positions`, `scales`, `rotations`, `alphas`, `dc_colors` - see the
+	///   fields of [`GaussianSplat`], the number of points is taken from
+	///   `alphas`.
+	/// * `sh_r`, `sh_g`, `sh_b` - `num_points * sh_dim` coefficients each,
+	///   coefficient-major per gaussian.
+	/// * `degree` - spherical harmonics degree, `0..=3`.
+	#[allow(clippy::too_many_arguments)]
+	pub fn from_channel_sh(
+		positions: Vec<f32>,
+		scales: Vec<f32>,
+		rotations: Vec<f32>,
+		alphas: Vec<f32>,
+		dc_colors: Vec<f32>,
+		sh_r: &[f32],
+		sh_g: &[f32],
+		sh_b: &[f32],
+		degree: u8,
+	) -> Result<Self> {
+		if unlikely(degree > 3) {
+			bail!("unsupported spherical harmonics degree: {degree}");
+		}
+		let num_points = alphas.len();
+		let sh_dim = dim_for_degree(degree) as usize;
+		let expected = num_points.saturating_mul(sh_dim);
+
+		for (name, channel) in [("sh_r", sh_r), ("sh_g", sh_g), ("sh_b", sh_b)] {
+			if unlikely(channel.len() != expected) {
+				bail!(
+					"{name} has length {}, expected {expected} for {num_points} points",
+					channel.len()
+				);
+			}
+		}
+		let mut spherical_harmonics = Vec::with_capacity(expected * 3);
+
+		for ((r, g), b) in sh_r.iter().zip(sh_g).zip(sh_b) {
+			spherical_harmonics.extend([*r, *g, *b]);
+		}
+		let gs = GaussianSplat {
+			header: Header {
+				num_points: i32::try_from(num_points).with_context(|| {
+					format!("too many points: {num_points}")
+				})?,
+				spherical_harmonics_degree: degree,
+				..Default::default()
+			},
+			positions,
+			scales,
+			rotations,
+			alphas,
+			colors: dc_colors,
+			spherical_harmonics,
+		};
+		gs.validate()?;
+
+		Ok(gs)
+	}
+
 	/// Loads a [`GaussianSplat`] from a file with the given options, async.
 	///
 	/// # Args
@@ -97,7 +170,10 @@ impl GaussianSplat {
 
 		infile.read_to_end(contents).await?;
 
-		Self::new_from_packed_gaussians(&PackedGaussianSplat::from_bytes(&contents)?, opts)
+		Ok(Self::new_from_packed_gaussians(
+			&packed_from_bytes(contents, opts, &mut Vec::new())?,
+			opts,
+		)?)
 	}
 
 	/// Loads a [`GaussianSplat`] from a file with the given options from
@@ -108,19 +184,18 @@ impl GaussianSplat {
 	/// `from` - gzip compressed, packed gaussian data.
 	/// `opts` - options for loading the splat.
 	#[inline]
-	pub async fn read_from_async<F, R>(mut from: R, opts: &LoadOptions) -> Result<Self>
+	pub async fn read_from_async<R>(mut from: R, opts: &LoadOptions) -> Result<Self>
 	where
-		F: AsRef<Path>,
 		R: AsyncReadExt + Unpin,
 	{
 		let mut contents = Vec::new();
 
 		from.read_to_end(&mut contents).await?;
 
-		let packed = PackedGaussianSplat::from_bytes(&contents)
+		let packed = packed_from_bytes(&contents, opts, &mut Vec::new())
 			.with_context(|| "unable to parse splat")?;
 
-		Self::new_from_packed_gaussians(&packed, opts)
+		Ok(Self::new_from_packed_gaussians(&packed, opts)?)
 	}
 
 	/// Loads a [`GaussianSplat`] from a file with the given options from
@@ -130,20 +205,71 @@ impl GaussianSplat {
 	///
 	/// `from` - gzip compressed, packed gaussian data.
 	/// `opts` - options for loading the splat.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use std::io::Cursor;
+	///
+	/// use spz::prelude::*;
+	///
+	/// let splat = GaussianSplat {
+	///     header: Header {
+	///         num_points: 1,
+	///         ..Default::default()
+	///     },
+	///     positions: vec![1.0, 2.0, 3.0],
+	///     scales: vec![-1.0, -1.0, -1.0],
+	///     rotations: vec![0.0, 0.0, 0.0, 1.0],
+	///     alphas: vec![0.5],
+	///     colors: vec![0.1, 0.2, 0.3],
+	///     spherical_harmonics: vec![],
+	/// };
+	/// let bytes = splat.serialize_to_packed_bytes(&SaveOptions::default())?;
+	///
+	/// let loaded = GaussianSplat::read_from(Cursor::new(bytes), &LoadOptions::default())?;
+	///
+	/// assert_eq!(loaded.header.num_points, 1);
+	/// assert!((loaded.positions[2] - 3.0).abs() < 1e-3);
+	/// # Ok::<(), anyhow::Error>(())
+	/// ```
 	#[inline]
-	pub fn read_from<F, R>(mut from: R, opts: &LoadOptions) -> Result<Self>
+	pub fn read_from<R>(mut from: R, opts: &LoadOptions) -> Result<Self>
 	where
-		F: AsRef<Path>,
 		R: Read,
 	{
 		let mut contents = Vec::new();
 
 		from.read_to_end(&mut contents)?;
 
-		let packed = PackedGaussianSplat::from_bytes(&contents)
+		let packed = packed_from_bytes(&contents, opts, &mut Vec::new())
 			.with_context(|| "unable to parse splat")?;
 
-		Self::new_from_packed_gaussians(&packed, opts)
+		Ok(Self::new_from_packed_gaussians(&packed, opts)?)
+	}
+
+	/// Loads a [`GaussianSplat`] from a gzip compressed reader, decoding it
+	/// as the data arrives.
+	///
+	/// Unlike [`Self::read_from`], neither the compressed nor the whole
+	/// decompressed data is buffered, which suits slow sources like network
+	/// sockets. `opts.compression` is ignored, the data has to be gzip.
+	///
+	/// # Args
+	///
+	/// `from` - gzip compressed, packed gaussian data.
+	/// `opts` - options for loading the splat.
+	pub fn from_gzip_reader<R>(from: R, opts: &LoadOptions) -> Result<Self>
+	where
+		R: Read,
+	{
+		let packed = PackedGaussianSplat::read_from(
+			flate2::read::GzDecoder::new(from),
+			opts.max_points,
+		)
+		.with_context(|| "unable to parse splat")?;
+
+		Ok(Self::new_from_packed_gaussians(&packed, opts)?)
 	}
 
 	/// Loads a [`GaussianSplat`] from a file with the given options, async.
@@ -169,29 +295,88 @@ impl GaussianSplat {
 	/// `filepath` - gzip compressed, packed gaussian data file.
 	/// `opts` - options for loading the splat.
 	#[inline]
-	pub fn load_with<F>(filepath: F, opts: &LoadOptions) -> Result<Self>
+	pub fn load_with<F>(filepath: F, opts: &LoadOptions) -> Result<Self, SpzError>
 	where
 		F: AsRef<Path>,
 	{
-		// mmap on macos isn't great according to ripgrep code
-		if cfg!(target_os = "macos") {
-			let infile = std::fs::read(filepath)?;
+		Self::load_with_buf(filepath, opts, &mut Vec::new())
+	}
 
-			return Self::new_from_packed_gaussians(
-				&PackedGaussianSplat::from_bytes(&infile)?,
-				opts,
-			);
-		}
-		let mmap = mmap::mmap(filepath)?;
-		let packed = PackedGaussianSplat::from_bytes(mmap.as_ref())
-			.with_context(|| "unable to load packed file")?;
+	/// Loads many [`GaussianSplat`]s with the same options, reusing one
+	/// decompression buffer, e.g. for batch conversions.
+	///
+	/// # Args
+	///
+	/// `paths` - gzip compressed, packed gaussian data files.
+	/// `opts` - options for loading the splats.
+	///
+	/// # Returns
+	///
+	/// One result per path, in the same order. A failing file doesn't stop
+	/// the others from loading.
+	pub fn load_many<P>(paths: &[P], opts: &LoadOptions) -> Vec<Result<Self, SpzError>>
+	where
+		P: AsRef<Path>,
+	{
+		let mut scratch = Vec::new();
+
+		paths.iter()
+			.map(|path| Self::load_with_buf(path, opts, &mut scratch))
+			.collect()
+	}
+
+	/// Loads a [`GaussianSplat`] like [`Self::load_with`], decompressing into
+	/// `scratch`.
+	fn load_with_buf<F>(
+		filepath: F,
+		opts: &LoadOptions,
+		scratch: &mut Vec<u8>,
+	) -> Result<Self, SpzError>
+	where
+		F: AsRef<Path>,
+	{
+		let packed = read_packed(filepath, opts, scratch)?;
 
 		Self::new_from_packed_gaussians(&packed, opts)
 	}
 
+	/// Loads a [`GaussianSplat`] like [`Self::load_with`], reporting the
+	/// progress, e.g. for a progress bar during long loads.
+	///
+	/// `on_progress` is called with the loaded fraction, from `0.0` to
+	/// `1.0`, at coarse milestones only: once the file is decompressed,
+	/// which counts as the first half, and after each decoded attribute
+	/// array. A successful load always ends with `1.0`.
+	///
+	/// # Args
+	///
+	/// `filepath` - gzip compressed, packed gaussian data file.
+	/// `opts` - options for loading the splat.
+	/// `on_progress` - called with the loaded fraction.
+	pub fn load_with_progress<P, F>(
+		filepath: P,
+		opts: &LoadOptions,
+		mut on_progress: F,
+	) -> Result<Self, SpzError>
+	where
+		P: AsRef<Path>,
+		F: FnMut(f32),
+	{
+		on_progress(0.0);
+
+		let packed = read_packed(filepath, opts, &mut Vec::new())?;
+
+		on_progress(0.5);
+
+		Self::decode_packed(&packed, opts, &mut |p| on_progress(0.5 + 0.5 * p))
+	}
+
 	/// Loads a [`GaussianSplat`] from a file.
 	///
-	/// Convenience method that uses the default load options.
+	/// Convenience method that uses the default load options and returns the
+	/// data in [`CoordinateSystem::RightUpBack`] (RUB), the coordinate system
+	/// SPZ stores, i.e. without any axis flips. Use
+	/// [`GaussianSplat::load_with`] to convert to another one.
 	///
 	/// # Args
 	///
@@ -201,17 +386,19 @@ impl GaussianSplat {
 	where
 		F: AsRef<Path>,
 	{
-		Self::load_with(
+		Ok(Self::load_with(
 			filepath,
 			&LoadOptions::builder()
-				.coord_sys(CoordinateSystem::default())
+				.coord_sys(CoordinateSystem::RightUpBack)
 				.build(),
-		)
+		)?)
 	}
 
 	/// Loads a [`GaussianSplat`] from a file, async.
 	///
-	/// Convenience async method that uses default load options.
+	/// Convenience async method that uses default load options and returns
+	/// the data in [`CoordinateSystem::RightUpBack`] (RUB), like
+	/// [`GaussianSplat::load`].
 	///
 	/// # Args
 	///
@@ -224,13 +411,14 @@ impl GaussianSplat {
 		Self::load_with_async(
 			filepath,
 			&LoadOptions::builder()
-				.coord_sys(CoordinateSystem::default()) // default to LUF (glTF)
+				.coord_sys(CoordinateSystem::RightUpBack)
 				.build(),
 		)
 		.await
 	}
 
-	/// Saves a [`GaussianSplat`] to a file.
+	/// Saves a [`GaussianSplat`] to a file, creating missing parent
+	/// directories.
 	///
 	/// # Args
 	///
@@ -243,19 +431,19 @@ impl GaussianSplat {
 	{
 		let compressed = self.serialize_to_packed_bytes(opts)?;
 
-		tokio::fs::create_dir_all(
-			filepath.as_ref()
-				.parent()
-				.ok_or_else(|| anyhow::anyhow!("recursive mkdir failed"))?,
-		)
-		.await?;
+		let parent = parent_dir(filepath.as_ref())?;
+
+		tokio::fs::create_dir_all(parent).await.with_context(|| {
+			format!("unable to create directory {}", parent.display())
+		})?;
 
 		tokio::fs::write(filepath, compressed)
 			.await
 			.with_context(|| "unable to write to file")
 	}
 
-	/// Saves a [`GaussianSplat`] to a file.
+	/// Saves a [`GaussianSplat`] to a file, creating missing parent
+	/// directories.
 	///
 	/// # Args
 	///
@@ -268,37 +456,155 @@ impl GaussianSplat {
 	{
 		let compressed = self.serialize_to_packed_bytes(opts)?;
 
-		std::fs::create_dir_all(
-			filepath.as_ref()
-				.parent()
-				.ok_or_else(|| anyhow::anyhow!("recursive mkdir failed"))?,
-		)?;
+		create_parent_dirs(filepath.as_ref())?;
 		std::fs::write(filepath, compressed).with_context(|| "unable to write to file")
 	}
 
+	/// Packs, compresses and writes the splat to `writer`, e.g. to stream a
+	/// merged splat to a socket or stdout instead of a file.
+	///
+	/// # Args
+	///
+	/// `writer` - destination of the compressed, packed gaussian data.
+	/// `opts` - options for saving the splat.
+	pub fn write_packed_to<W>(&self, writer: &mut W, opts: &SaveOptions) -> Result<()>
+	where
+		W: std::io::Write,
+	{
+		let packed = self.to_packed_gaussians(opts)?;
+
+		compression::compress_to(&mut *writer, opts.compression, |w| {
+			packed.write_self_to(w)
+		})
+		.with_context(|| "unable to write packed data")?;
+
+		writer.flush().with_context(|| "unable to flush writer")
+	}
+
+	/// Returns the length of the packed data `opts` would produce before
+	/// compression, without packing anything, e.g. to budget downloads.
+	///
+	/// Matches the length of
+	/// [`to_packed_gaussians`](Self::to_packed_gaussians)`(opts)?.to_bytes_vec()?`
+	/// exactly. Together with the length of the
+	/// [`serialize_to_packed_bytes`](Self::serialize_to_packed_bytes) output
+	/// it gives the compression ratio.
+	pub fn estimated_packed_size(&self, opts: &SaveOptions) -> usize {
+		Header {
+			version: opts.version,
+			..self.header
+		}
+		.expected_decompressed_len()
+	}
+
 	pub fn serialize_to_packed_bytes(&self, opts: &SaveOptions) -> Result<Vec<u8>> {
+		if unlikely(!self.header_matches_data()) {
+			return Err(SpzError::InconsistentSizes).with_context(|| {
+				format!(
+					"header does not match data: header has {} points (sh degree {}), data has {} positions and {} sh coefficients",
+					self.header.num_points,
+					self.header.spherical_harmonics_degree,
+					self.positions.len() / 3,
+					self.spherical_harmonics.len()
+				)
+			});
+		}
 		let packed = self.to_packed_gaussians(opts)?;
 
-		let uncompressed = packed.to_bytes_vec()?;
-		let mut compressed = Vec::new();
+		// stream into the encoder, the uncompressed data is never buffered
+		compression::compress_to(Vec::new(), opts.compression, |w| packed.write_self_to(w))
+	}
+
+	/// Loads a [`GaussianSplat`] from a binary PLY file.
+	///
+	/// See [`ply`](crate::ply) for the supported layout.
+	///
+	/// # Args
+	///
+	/// `filepath` - PLY file path.
+	/// `opts` - options for loading the splat, `compression` is ignored.
+	pub fn from_ply_file<F>(filepath: F, opts: &LoadOptions) -> Result<Self>
+	where
+		F: AsRef<Path>,
+	{
+		let file = std::fs::File::open(filepath).with_context(|| "unable to open file")?;
+
+		ply::read_ply(&mut std::io::BufReader::new(file), opts)
+	}
+
+	/// Loads a [`GaussianSplat`] from binary PLY data.
+	///
+	/// # Args
+	///
+	/// `bytes` - PLY file contents.
+	/// `opts` - options for loading the splat, `compression` is ignored.
+	#[inline]
+	pub fn from_ply_bytes(bytes: &[u8], opts: &LoadOptions) -> Result<Self> {
+		ply::read_ply(&mut &bytes[..], opts)
+	}
+
+	/// Saves a [`GaussianSplat`] as a binary PLY file, creating missing parent
+	/// directories.
+	///
+	/// # Args
+	///
+	/// `filepath` - file path to save the PLY data to.
+	/// `opts` - options for saving the splat, only `coord_sys` applies.
+	pub fn save_ply<F>(&self, filepath: F, opts: &SaveOptions) -> Result<()>
+	where
+		F: AsRef<Path>,
+	{
+		create_parent_dirs(filepath.as_ref())?;
+		let file =
+			std::fs::File::create(filepath).with_context(|| "unable to create file")?;
+
+		ply::write_ply(self, &mut std::io::BufWriter::new(file), opts)
+	}
+
+	/// Serializes a [`GaussianSplat`] to binary PLY data.
+	///
+	/// # Args
+	///
+	/// `opts` - options for saving the splat, only `coord_sys` applies.
+	pub fn to_ply_bytes(&self, opts: &SaveOptions) -> Result<Vec<u8>> {
+		let mut out = Vec::new();
 
-		compression::gzip::compress_bytes(uncompressed.as_ref(), &mut compressed)?;
+		ply::write_ply(self, &mut out, opts)?;
 
-		Ok(compressed)
+		Ok(out)
 	}
 
 	pub fn new_from_packed_gaussians(
 		packed: &PackedGaussianSplat,
 		opts: &LoadOptions,
-	) -> Result<Self> {
+	) -> Result<Self, SpzError> {
+		Self::decode_packed(packed, opts, &mut |_| {})
+	}
+
+	/// Decodes `packed`, calling `on_progress` with the decoded fraction
+	/// after each attribute array.
+	fn decode_packed(
+		packed: &PackedGaussianSplat,
+		opts: &LoadOptions,
+		on_progress: &mut dyn FnMut(f32),
+	) -> Result<Self, SpzError> {
 		let num_points = packed.num_points as usize;
 		let sh_dim = dim_for_degree(packed.sh_degree as u8);
 
+		if unlikely(num_points > opts.max_points) {
+			return Err(SpzError::TooManyPoints {
+				num_points: packed.num_points,
+				max: opts.max_points,
+			});
+		}
+		check_coord_sys(opts.coord_sys, opts.strict_coord_sys)?;
+
 		if unlikely(!packed.check_sizes(num_points, sh_dim)) {
-			bail!("inconsistent sizes");
+			return Err(SpzError::InconsistentSizes);
 		}
 		let mut result = Self {
 			header: Header {
+				version: packed.to_header().version,
 				num_points: packed.num_points,
 				spherical_harmonics_degree: packed.sh_degree as u8,
 				fractional_bits: packed.fractional_bits as u8,
@@ -309,45 +615,52 @@ impl GaussianSplat {
 				},
 				..Default::default()
 			},
-			positions: vec![0_f32; num_points * 3],
+			// positions: decode 24-bit fixed point coordinates
+			positions: packed.decode_positions_only(&AxisFlips::default()),
 			scales: vec![0_f32; num_points * 3],
 			rotations: vec![0_f32; num_points * 4],
-			alphas: vec![0_f32; num_points],
-			colors: vec![0_f32; num_points * 3],
+			alphas: packed.decode_alphas_only(),
+			colors: packed.decode_colors_only(),
 			spherical_harmonics: vec![0_f32; num_points * sh_dim as usize * 3],
 		};
-		// positions: decode 24-bit fixed point coordinates
-		let scale = 1.0_f32 / (1_u32 << (packed.fractional_bits as u32)) as f32;
+		// progress by the share of the packed bytes decoded so far
+		let total = [
+			packed.positions.len(),
+			packed.alphas.len(),
+			packed.colors.len(),
+			packed.scales.len(),
+			packed.rotations.len(),
+			packed.spherical_harmonics.len(),
+		]
+		.iter()
+		.sum::<usize>()
+		.max(1) as f32;
+		let mut decoded =
+			packed.positions.len() + packed.alphas.len() + packed.colors.len();
+
+		on_progress(decoded as f32 / total);
 
-		for (dst, src) in result
-			.positions
-			.chunks_exact_mut(3)
-			.zip(packed.positions.chunks_exact(9))
-		{
-			for (i, value) in dst.iter_mut().enumerate().take(3) {
-				let base = i * 3;
-				let mut fixed32 = src[base] as i32
-					| ((src[base + 1] as i32) << 8) | ((src[base + 2]
-					as i32) << 16);
-
-				if (fixed32 & 0x800000) != 0 {
-					fixed32 |= 0xff000000_u32 as i32;
-				}
-				*value = fixed32 as f32 * scale;
-			}
-		}
 		// scales
 		for (dst, src) in result.scales.iter_mut().zip(packed.scales.iter()) {
 			*dst = *src as f32 / 16.0 - 10.0;
 		}
+		decoded += packed.scales.len();
+		on_progress(decoded as f32 / total);
+
 		// rotations
 		if packed.uses_quaternion_smallest_three {
-			for (dst, src) in result
+			for (index, (dst, src)) in result
 				.rotations
 				.chunks_exact_mut(4)
 				.zip(packed.rotations.chunks_exact(4))
+				.enumerate()
 			{
-				math::unpack_quaternion_smallest_three(dst, src);
+				let valid =
+					math::unpack_quaternion_smallest_three_checked(dst, src);
+
+				if unlikely(!valid && opts.strict_rotations) {
+					return Err(SpzError::CorruptRotation { index });
+				}
 			}
 		} else {
 			for (dst, src) in result
@@ -358,14 +671,9 @@ impl GaussianSplat {
 				math::unpack_quaternion_first_three(dst, src);
 			}
 		}
-		// alphas
-		for (dst, src) in result.alphas.iter_mut().zip(packed.alphas.iter()) {
-			*dst = math::inv_sigmoid(*src as f32 / 255.0);
-		}
-		// colors
-		for (dst, src) in result.colors.iter_mut().zip(packed.colors.iter()) {
-			*dst = ((*src as f32 / 255.0) - 0.5) / consts::COLOR_SCALE;
-		}
+		decoded += packed.rotations.len();
+		on_progress(decoded as f32 / total);
+
 		// spherical harmonics
 		for (dst, src) in result
 			.spherical_harmonics
@@ -376,28 +684,45 @@ impl GaussianSplat {
 		}
 		result.convert_coordinates(opts.coord_sys, CoordinateSystem::RightUpBack);
 
+		on_progress(1.0);
+
 		Ok(result)
 	}
 
 	pub fn to_packed_gaussians(&self, opts: &SaveOptions) -> Result<PackedGaussianSplat> {
 		if unlikely(!self.check_sizes()) {
-			bail!("inconsistent sizes");
+			return Err(SpzError::InconsistentSizes.into());
 		}
+		if unlikely(opts.version == Version::V1) {
+			return Err(SpzError::UnsupportedVersion(Version::V1 as i32).into());
+		}
+		if unlikely(opts.fractional_bits > consts::MAX_FRACTIONAL_BITS) {
+			bail!(
+				"invalid fractional bits: {} (max {})",
+				opts.fractional_bits,
+				consts::MAX_FRACTIONAL_BITS
+			);
+		}
+		check_coord_sys(opts.coord_sys, opts.strict_coord_sys)?;
+
 		let num_points = self.header.num_points as usize;
 		let sh_dim = math::dim_for_degree(self.header.spherical_harmonics_degree) as usize;
 		let axis_flips = opts.coord_sys.axis_flips_to(CoordinateSystem::RightUpBack);
-		let fractional_bits: i32 = 12;
+		let fractional_bits = opts.fractional_bits as i32;
 		let scale = (1_i32 << fractional_bits) as f32;
+		let uses_quaternion_smallest_three =
+			crate::packed::is_encoding_quaternion_smallest_three_used(opts.version);
+		let rotation_bytes = if uses_quaternion_smallest_three { 4 } else { 3 };
 
 		let mut packed = PackedGaussianSplat {
 			num_points: self.header.num_points,
 			sh_degree: self.header.spherical_harmonics_degree as i32,
 			fractional_bits,
 			antialiased: self.header.flags.is_antialiased(),
-			uses_quaternion_smallest_three: true,
+			uses_quaternion_smallest_three,
 			positions: vec![0_u8; num_points * 9],
 			scales: vec![0_u8; num_points * 3],
-			rotations: vec![0_u8; num_points * 4],
+			rotations: vec![0_u8; num_points * rotation_bytes],
 			alphas: vec![0_u8; num_points],
 			colors: vec![0_u8; num_points * 3],
 			spherical_harmonics: vec![0_u8; num_points * sh_dim * 3],
@@ -415,23 +740,28 @@ impl GaussianSplat {
 		for i in 0..(num_points * 3) {
 			packed.scales[i] = math::to_u8((self.scales[i] + 10.0) * 16.0);
 		}
-		// Pack rotations using smallest-three encoding
-		for i in 0..num_points {
-			let rot_src: [f32; 4] = [
-				self.rotations[4 * i],
-				self.rotations[4 * i + 1],
-				self.rotations[4 * i + 2],
-				self.rotations[4 * i + 3],
-			];
-			let rot_dst = math::pack_quaternion_smallest_three(
-				&rot_src,
-				[
-					axis_flips.rotation[0],
-					axis_flips.rotation[1],
-					axis_flips.rotation[2],
-				],
-			);
-			packed.rotations[4 * i..4 * i + 4].copy_from_slice(&rot_dst);
+		// Pack rotations using smallest-three (v3) or first-three (v2) encoding
+		let flip_q = [
+			axis_flips.rotation[0],
+			axis_flips.rotation[1],
+			axis_flips.rotation[2],
+		];
+		for (dst, src) in packed
+			.rotations
+			.chunks_exact_mut(rotation_bytes)
+			.zip(self.rotations.chunks_exact(4))
+		{
+			let rot_src = [src[0], src[1], src[2], src[3]];
+
+			if uses_quaternion_smallest_three {
+				dst.copy_from_slice(&math::pack_quaternion_smallest_three(
+					&rot_src, flip_q,
+				));
+			} else {
+				dst.copy_from_slice(&math::pack_quaternion_first_three(
+					&rot_src, flip_q,
+				));
+			}
 		}
 		// Pack alphas with sigmoid activation
 		for i in 0..num_points {
@@ -445,72 +775,68 @@ impl GaussianSplat {
 		}
 		// Pack spherical harmonics
 		if self.header.spherical_harmonics_degree > 0 {
-			const SH1_BITS: i32 = 5;
-			const SH_REST_BITS: i32 = 4;
-
 			let sh_per_point = sh_dim * 3;
 
-			for point_idx in 0..num_points {
-				let base = point_idx * sh_per_point;
-
-				let mut j = 0_usize;
-				let mut k = 0_usize;
-
-				while j < 9 && j < sh_per_point {
-					let step = 1_i32 << (8 - SH1_BITS);
-
-					packed.spherical_harmonics[base + j] = math::quantize_sh(
-						axis_flips.spherical_harmonics[k]
-							* self.spherical_harmonics[base + j],
-						step,
-					);
-					packed.spherical_harmonics[base + j + 1] =
-						math::quantize_sh(
-							axis_flips.spherical_harmonics[k]
-								* self.spherical_harmonics
-									[base + j + 1],
-							step,
-						);
-					packed.spherical_harmonics[base + j + 2] =
-						math::quantize_sh(
-							axis_flips.spherical_harmonics[k]
-								* self.spherical_harmonics
-									[base + j + 2],
-							step,
-						);
-					j += 3;
-					k += 1;
-				}
-				while j < sh_per_point {
-					let step = 1_i32 << (8 - SH_REST_BITS);
-
-					packed.spherical_harmonics[base + j] = math::quantize_sh(
-						axis_flips.spherical_harmonics[k]
-							* self.spherical_harmonics[base + j],
-						step,
-					);
-					packed.spherical_harmonics[base + j + 1] =
-						math::quantize_sh(
-							axis_flips.spherical_harmonics[k]
-								* self.spherical_harmonics
-									[base + j + 1],
-							step,
-						);
-					packed.spherical_harmonics[base + j + 2] =
-						math::quantize_sh(
-							axis_flips.spherical_harmonics[k]
-								* self.spherical_harmonics
-									[base + j + 2],
-							step,
-						);
-					j += 3;
-					k += 1;
-				}
-			}
+			#[cfg(feature = "rayon")]
+			pack_spherical_harmonics_par(
+				&mut packed.spherical_harmonics,
+				&self.spherical_harmonics,
+				sh_per_point,
+				&axis_flips,
+			);
+			#[cfg(not(feature = "rayon"))]
+			pack_spherical_harmonics(
+				&mut packed.spherical_harmonics,
+				&self.spherical_harmonics,
+				sh_per_point,
+				&axis_flips,
+			);
 		}
 		Ok(packed)
 	}
 
+	/// Packs the splat like [`GaussianSplat::to_packed_gaussians`] with
+	/// default [`SaveOptions`], converting from the coordinate system of
+	/// `opts`. The counterpart of [`PackedGaussianSplat::unpack_with`].
+	#[inline]
+	pub fn pack_with(&self, opts: &PackOptions) -> Result<PackedGaussianSplat> {
+		self.to_packed_gaussians(&SaveOptions::builder().coord_sys(opts.from).build())
+	}
+
+	/// Like [`GaussianSplat::to_packed_gaussians`], additionally reporting
+	/// the values that don't fit their quantized range and get clamped, so
+	/// lossy saves can be surfaced to users.
+	pub fn to_packed_gaussians_with_report(
+		&self,
+		opts: &SaveOptions,
+	) -> Result<(PackedGaussianSplat, PackReport)> {
+		let packed = self.to_packed_gaussians(opts)?;
+
+		let scale = (1_i32 << opts.fractional_bits) as f32;
+		let max_fixed = ((1_i32 << 23) - 1) as f32;
+		let min_fixed = -(1_i32 << 23) as f32;
+
+		let position_overflows = self
+			.positions
+			.iter()
+			.filter(|p| !(min_fixed..=max_fixed).contains(&(*p * scale).round()))
+			.count();
+		let sh_clips = self
+			.spherical_harmonics
+			.iter()
+			.filter(|sh| !(0.0..=255.0).contains(&(*sh * 128.0 + 128.0).round()))
+			.count();
+
+		Ok((
+			packed,
+			PackReport {
+				position_overflows,
+				color_clips: self.colors_clip_report(),
+				sh_clips,
+			},
+		))
+	}
+
 	pub fn convert_coordinates(
 		&mut self,
 		source_cs: crate::coord::CoordinateSystem,
@@ -583,590 +909,4492 @@ impl GaussianSplat {
 		}
 	}
 
-	/// Compute median ellipsoid volume.
-	pub fn median_volume(&self) -> f32 {
-		if unlikely(self.scales.is_empty()) {
-			return 0.01;
+	/// Converts the splat from `source_cs` to `target_cs`, like
+	/// [`Self::convert_coordinates`], but fails instead of silently doing
+	/// nothing if either system is
+	/// [`Unspecified`](crate::coord::CoordinateSystem::Unspecified).
+	///
+	/// Fails with [`SpzError::UnspecifiedCoordinateSystem`], the splat is
+	/// left untouched then.
+	pub fn convert_coordinates_checked(
+		&mut self,
+		source_cs: crate::coord::CoordinateSystem,
+		target_cs: crate::coord::CoordinateSystem,
+	) -> Result<(), SpzError> {
+		check_coord_sys(source_cs, true)?;
+		check_coord_sys(target_cs, true)?;
+
+		self.convert_coordinates(source_cs, target_cs);
+
+		Ok(())
+	}
+
+	/// Applies a similarity transform to the splat in place.
+	///
+	/// Each position is rotated, uniformly scaled, and then translated, i.e.
+	/// `p' = rotation * (scale * p) + translation`. Rotations are
+	/// pre-multiplied by `rotation_quat` and `scale.ln()` is added to the
+	/// log-encoded scales. Spherical harmonics are rotated along, see
+	/// [`math::rotate_sh`], so the view-dependent color follows the rotation.
+	///
+	/// # Args
+	///
+	/// `translation` - (x, y, z) offset applied last.
+	/// `rotation_quat` - rotation as a (x, y, z, w) quaternion, normalized
+	/// before use.
+	/// `scale` - uniform scale factor, must be finite and positive.
+	pub fn apply_transform(
+		&mut self,
+		translation: [f32; 3],
+		rotation_quat: [f32; 4],
+		scale: f32,
+	) -> Result<()> {
+		if unlikely(!scale.is_finite() || scale <= 0.0) {
+			bail!("scale must be finite and positive, got {scale}");
 		}
-		// The volume of an ellipsoid is 4/3 * pi * x * y * z,
-		// where x, y, and z are the radii on each axis.
-		// Scales are stored on a log scale, and
-		// 	exp(x) * exp(y) * exp(z) = exp(x + y + z).
-		// So we can sort by value = (x + y + z) and compute
-		// 	volume = 4/3 * pi * exp(value) later.
-		let mut sums = self
-			.scales
-			.chunks_exact(3)
-			.filter_map(|c| {
-				let s = c[0] + c[1] + c[2];
+		let rotation = math::normalize_quaternion(&rotation_quat);
+		let log_scale = scale.ln();
 
-				if unlikely(!s.is_finite()) {
-					None
-				} else {
-					Some(s)
-				}
-			})
-			.collect::<Vec<_>>();
+		for p in self.positions.chunks_exact_mut(3) {
+			let rotated = math::rotate_vector(
+				&rotation,
+				&[p[0] * scale, p[1] * scale, p[2] * scale],
+			);
 
-		if unlikely(sums.is_empty()) {
-			return 0.01;
+			p[0] = rotated[0] + translation[0];
+			p[1] = rotated[1] + translation[1];
+			p[2] = rotated[2] + translation[2];
 		}
-		let n = sums.len() / 2;
+		for r in self.rotations.chunks_exact_mut(4) {
+			let q = math::multiply_quaternions(&rotation, &[r[0], r[1], r[2], r[3]]);
 
-		sums.select_nth_unstable_by(n, |a, b| {
-			a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal)
-		});
-		let median = sums[sums.len() / 2];
+			r.copy_from_slice(&q);
+		}
+		for s in self.scales.iter_mut() {
+			*s += log_scale;
+		}
+		let degree = self.header.spherical_harmonics_degree;
+		let dim = dim_for_degree(degree) as usize;
 
-		if unlikely(!median.is_finite() || median <= f32::MIN_POSITIVE.ln()) {
-			return 0.01;
+		if dim > 0 {
+			let sh_rotation = math::ShRotation::new(degree, rotation);
+
+			for sh in self.spherical_harmonics.chunks_exact_mut(dim * 3) {
+				sh_rotation.apply(sh);
+			}
 		}
-		(std::f32::consts::PI * 4.0 / 3.0) * median.exp()
+		Ok(())
 	}
 
-	/// Validates that all internal arrays have consistent sizes.
+	/// Appends all gaussians of `other` to `self`.
 	///
-	/// Checks that:
-	/// - `num_points` is non-negative
-	/// - `spherical_harmonics_degree` is in range 0..=3
-	/// - `positions` has length `num_points * 3`
-	/// - `scales` has length `num_points * 3`
-	/// - `rotations` has length `num_points * 4`
-	/// - `alphas` has length `num_points`
-	/// - `colors` has length `num_points * 3`
-	/// - `spherical_harmonics` has length `num_points * sh_dim * 3`
+	/// Both splats must be valid, see [`GaussianSplat::validate`], and have
+	/// the same spherical harmonics degree. Header
+	/// fields other than `num_points` and `flags` are kept from `self`.
 	///
-	/// # Returns
+	/// # Antialiasing
 	///
-	/// Returns `true` if all sizes are valid, `false` otherwise.
-	pub fn check_sizes(&self) -> bool {
-		if unlikely(self.header.num_points < 0) {
-			return false;
+	/// The antialiased flag applies to the whole splat, so a renderer can't
+	/// treat only part of the gaussians as trained with antialiasing. The
+	/// result is therefore antialiased only if **both** inputs are; merging
+	/// an antialiased splat with a non-antialiased one clears the flag.
+	pub fn merge(&mut self, other: &GaussianSplat) -> Result<()> {
+		self.validate()
+			.context("cannot merge, invalid self splat")?;
+		other.validate()
+			.context("cannot merge, invalid other splat")?;
+
+		if unlikely(
+			self.header.spherical_harmonics_degree
+				!= other.header.spherical_harmonics_degree,
+		) {
+			bail!(
+				"spherical harmonics degree mismatch: {} vs {}",
+				self.header.spherical_harmonics_degree,
+				other.header.spherical_harmonics_degree
+			);
 		}
-		if unlikely(self.header.spherical_harmonics_degree > 3) {
-			return false;
-		}
-		let np = self.header.num_points as usize;
-		let sh_dim = dim_for_degree(self.header.spherical_harmonics_degree) as usize;
+		let Some(num_points) = self.header.num_points.checked_add(other.header.num_points)
+		else {
+			bail!("too many points to merge");
+		};
+		// Grow every array once, instead of possibly several times while
+		// extending with a large `other`.
+		self.positions.reserve_exact(other.positions.len());
+		self.scales.reserve_exact(other.scales.len());
+		self.rotations.reserve_exact(other.rotations.len());
+		self.alphas.reserve_exact(other.alphas.len());
+		self.colors.reserve_exact(other.colors.len());
+		self.spherical_harmonics
+			.reserve_exact(other.spherical_harmonics.len());
 
-		let expected_xyz = np.saturating_mul(3);
-		let expected_rot = np.saturating_mul(4);
-		let expected_colors = np.saturating_mul(3);
-		let expected_sh = np.saturating_mul(sh_dim).saturating_mul(3);
+		self.positions.extend_from_slice(&other.positions);
+		self.scales.extend_from_slice(&other.scales);
+		self.rotations.extend_from_slice(&other.rotations);
+		self.alphas.extend_from_slice(&other.alphas);
+		self.colors.extend_from_slice(&other.colors);
+		self.spherical_harmonics
+			.extend_from_slice(&other.spherical_harmonics);
 
-		if self.positions.len() != expected_xyz
-			|| self.scales.len() != expected_xyz
-			|| self.rotations.len() != expected_rot
-			|| self.alphas.len() != np
-			|| self.colors.len() != expected_colors
-			|| self.spherical_harmonics.len() != expected_sh
-		{
-			return false;
+		self.header.num_points = num_points;
+
+		if !other.header.flags.is_antialiased() {
+			self.header.flags.remove(Flags::ANTIALIASED);
 		}
-		true
+		Ok(())
 	}
 
-	pub fn bbox(&self) -> BoundingBox {
-		let mut min_x = self.positions[0];
-		let mut max_x = self.positions[0];
-		let mut min_y = self.positions[1];
-		let mut max_y = self.positions[1];
-		let mut min_z = self.positions[2];
-		let mut max_z = self.positions[2];
-
-		for i in (0..self.positions.len()).step_by(3) {
-			min_x = min_x.min(self.positions[i]);
-			max_x = max_x.max(self.positions[i]);
-			min_y = min_y.min(self.positions[i + 1]);
-			max_y = max_y.max(self.positions[i + 1]);
-			min_z = min_z.min(self.positions[i + 2]);
-			max_z = max_z.max(self.positions[i + 2]);
-		}
-		BoundingBox {
-			min_x,
-			max_x,
-			min_y,
-			max_y,
-			min_z,
-			max_z,
-		}
+	/// Appends a single gaussian, see [`GaussianSplat::append_gaussians`].
+	#[inline]
+	pub fn append_gaussian(&mut self, g: &UnpackedGaussian) -> Result<()> {
+		self.append_gaussians(std::slice::from_ref(g))
 	}
 
-	pub fn pretty_fmt(&self) -> String {
-		let bbox = self.bbox();
-		let (size_x, size_y, size_z) = bbox.size();
-		let (center_x, center_y, center_z) = bbox.center();
+	/// Appends all gaussians of `gs`, growing the arrays only once.
+	///
+	/// Only the first `dim_for_degree(degree)` spherical harmonics
+	/// coefficients of each gaussian are used, for the splat's current
+	/// degree. Gaussians with non-zero coefficients beyond it would lose
+	/// them, so they are rejected and nothing is appended.
+	pub fn append_gaussians(&mut self, gs: &[UnpackedGaussian]) -> Result<()> {
+		self.validate()?;
 
-		let mut ret = String::new();
+		let sh_dim = dim_for_degree(self.header.spherical_harmonics_degree) as usize;
 
-		let _ = writeln!(ret, "GaussianSplat:");
-		let _ = writeln!(ret, "\tNumber of points:\t\t{}", self.header.num_points);
-		let _ = writeln!(
-			ret,
-			"\tSpherical harmonics degree:\t{}",
-			self.header.spherical_harmonics_degree
-		);
-		let _ = writeln!(
-			ret,
-			"\tAntialiased:\t\t\t{}",
-			self.header.flags.is_antialiased()
-		);
-		let _ = writeln!(ret, "\tMedian ellipsoid volume:\t{:}", self.median_volume());
-		let _ = writeln!(
-			ret,
-			"\tBounding box:\n\t\tx: {:} to {:} (size {:}, center {:})\n",
-			bbox.min_x, bbox.max_x, size_x, center_x
-		);
-		let _ = writeln!(
-			ret,
-			"\t\ty: {:} to {:} (size {:}, center {:})",
-			bbox.min_y, bbox.max_y, size_y, center_y
-		);
-		let _ = writeln!(
-			ret,
-			"\t\tz: {:} to {:} (size {:}, center {:})",
-			bbox.min_z, bbox.max_z, size_z, center_z
-		);
-		ret
-	}
-}
+		if let Some(i) = gs.iter().position(|g| {
+			[&g.sh_r, &g.sh_g, &g.sh_b]
+				.iter()
+				.any(|sh| sh[sh_dim..].iter().any(|c| *c != 0.0))
+		}) {
+			bail!(
+				"gaussian {i} has spherical harmonics above degree {}",
+				self.header.spherical_harmonics_degree
+			);
+		}
+		let Some(num_points) = i32::try_from(gs.len())
+			.ok()
+			.and_then(|n| self.header.num_points.checked_add(n))
+		else {
+			bail!("too many points to append");
+		};
+		self.positions.reserve_exact(gs.len() * 3);
+		self.scales.reserve_exact(gs.len() * 3);
+		self.rotations.reserve_exact(gs.len() * 4);
+		self.alphas.reserve_exact(gs.len());
+		self.colors.reserve_exact(gs.len() * 3);
+		self.spherical_harmonics
+			.reserve_exact(gs.len() * sh_dim * 3);
 
-impl std::fmt::Display for GaussianSplat {
-	#[inline]
-	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		let _ = write!(
-			f,
-			"GaussianSplat={{{}, {}, ",
-			self.header,
-			self.median_volume()
-		);
-		let BoundingBox {
-			min_x,
-			max_x,
-			min_y,
-			max_y,
-			min_z,
-			max_z,
-		} = self.bbox();
+		for g in gs {
+			self.positions.extend_from_slice(&g.position);
+			self.scales.extend_from_slice(&g.scale);
+			self.rotations.extend_from_slice(&g.rotation);
+			self.alphas.push(g.alpha);
+			self.colors.extend_from_slice(&g.color);
+
+			for k in 0..sh_dim {
+				self.spherical_harmonics
+					.extend([g.sh_r[k], g.sh_g[k], g.sh_b[k]]);
+			}
+		}
+		self.header.num_points = num_points;
 
-		write!(
-			f,
-			"bbox=[x={:.6} to {:.6}, y={:.6} to {:.6}, z={:.6} to {:.6}]}}",
-			min_x, max_x, min_y, max_y, min_z, max_z
-		)?;
 		Ok(())
 	}
-}
 
-#[derive(Clone, Debug, Arbitrary)]
-pub struct GaussianSplatBuilder {
-	coord_sys: CoordinateSystem,
-	packed: bool,
-}
+	/// Like [`GaussianSplat::merge`], with the extra processing of `opts`.
+	///
+	/// With [`MergeOptions::dedup_radius`] set, the merged splat is run
+	/// through [`GaussianSplat::merge_nearby`], so points captured by both
+	/// inputs don't pile up.
+	pub fn merge_with(&mut self, other: &GaussianSplat, opts: &MergeOptions) -> Result<()> {
+		self.merge(other)?;
 
-impl GaussianSplatBuilder {
-	#[inline]
-	pub fn packed(mut self, packed: bool) -> Result<Self> {
-		if unlikely(!packed) {
-			bail!("only packed format loading is supported currently");
+		if let Some(radius) = opts.dedup_radius {
+			self.merge_nearby(radius);
 		}
-		self.packed = packed;
-
-		Ok(self)
+		Ok(())
 	}
 
-	#[inline]
-	pub fn coord_sys(mut self, coord_sys: CoordinateSystem) -> Self {
-		self.coord_sys = coord_sys;
-		self
-	}
+	/// Removes every gaussian whose center lies within `radius` of an
+	/// earlier, kept gaussian, so of each cluster of near-coincident points
+	/// only the first one remains.
+	///
+	/// Gaussians with a non-finite position are always kept. Does nothing if
+	/// `radius` is negative or `NaN`.
+	pub fn merge_nearby(&mut self, radius: f32) {
+		if unlikely(radius.is_nan() || radius < 0.0) {
+			return;
+		}
+		let cell_size = radius.max(f32::MIN_POSITIVE);
+		let radius_sq = radius * radius;
+		let cell_of = |p: &[f32]| {
+			[
+				(p[0] / cell_size).floor() as i64,
+				(p[1] / cell_size).floor() as i64,
+				(p[2] / cell_size).floor() as i64,
+			]
+		};
+		let mut grid = HashMap::<[i64; 3], Vec<usize>>::new();
+		let mut keep = vec![true; self.positions.len() / 3];
 
-	#[inline]
-	pub fn load<P>(self, filepath: P) -> Result<GaussianSplat>
-	where
-		P: AsRef<Path>,
-	{
-		GaussianSplat::load_with(
-			filepath,
-			&LoadOptions::builder().coord_sys(self.coord_sys).build(),
-		)
-	}
+		for (i, p) in self.positions.chunks_exact(3).enumerate() {
+			if unlikely(!p.iter().all(|v| v.is_finite())) {
+				continue;
+			}
+			let cell = cell_of(p);
+			let mut is_duplicate = false;
 
-	#[inline]
-	pub async fn load_async<P>(self, filepath: P) -> Result<GaussianSplat>
-	where
-		P: AsRef<Path>,
-	{
-		GaussianSplat::load_with_async(
-			filepath,
-			&LoadOptions::builder().coord_sys(self.coord_sys).build(),
-		)
-		.await
-	}
-}
+			'search: for dx in -1..=1 {
+				for dy in -1..=1 {
+					for dz in -1..=1 {
+						let neighbor = [
+							cell[0].saturating_add(dx),
+							cell[1].saturating_add(dy),
+							cell[2].saturating_add(dz),
+						];
+						let Some(kept) = grid.get(&neighbor) else {
+							continue;
+						};
+						for &j in kept {
+							let q = &self.positions[j * 3..j * 3 + 3];
+							let d_sq = (p[0] - q[0]).powi(2)
+								+ (p[1] - q[1]).powi(2) + (p[2]
+								- q[2])
+							.powi(2);
 
-impl Default for GaussianSplatBuilder {
-	#[inline]
-	fn default() -> Self {
-		GaussianSplatBuilder {
-			coord_sys: CoordinateSystem::Unspecified,
-			packed: true,
+							if d_sq <= radius_sq {
+								is_duplicate = true;
+								break 'search;
+							}
+						}
+					}
+				}
+			}
+			if is_duplicate {
+				keep[i] = false;
+			} else {
+				grid.entry(cell).or_default().push(i);
+			}
 		}
+		self.retain_mask(&keep);
 	}
-}
 
-#[derive(Clone, Debug, Arbitrary)]
-pub struct LoadOptionsBuilder {
-	coord_sys: CoordinateSystem,
-}
+	/// Concatenates `splats` into a new splat, see [`GaussianSplat::merge`].
+	///
+	/// Returns an empty splat if `splats` is empty.
+	pub fn concat(splats: &[GaussianSplat]) -> Result<GaussianSplat> {
+		let Some((first, rest)) = splats.split_first() else {
+			return Ok(GaussianSplat::default());
+		};
+		let mut ret = first.clone();
+		let total = |len: fn(&GaussianSplat) -> usize| rest.iter().map(len).sum::<usize>();
 
-impl LoadOptionsBuilder {
-	#[inline]
-	pub fn coord_sys(mut self, coord_sys: CoordinateSystem) -> Self {
-		self.coord_sys = coord_sys;
-		self
-	}
+		// Reserve for all of `rest` up front, so `merge` doesn't reallocate
+		// on every splat.
+		ret.positions.reserve_exact(total(|gs| gs.positions.len()));
+		ret.scales.reserve_exact(total(|gs| gs.scales.len()));
+		ret.rotations.reserve_exact(total(|gs| gs.rotations.len()));
+		ret.alphas.reserve_exact(total(|gs| gs.alphas.len()));
+		ret.colors.reserve_exact(total(|gs| gs.colors.len()));
+		ret.spherical_harmonics
+			.reserve_exact(total(|gs| gs.spherical_harmonics.len()));
 
-	#[inline]
-	pub fn build(self) -> LoadOptions {
-		LoadOptions {
-			coord_sys: self.coord_sys,
+		for splat in rest {
+			ret.merge(splat)?;
 		}
+		Ok(ret)
 	}
-}
 
-impl Default for LoadOptionsBuilder {
-	#[inline]
-	fn default() -> Self {
-		Self {
-			coord_sys: CoordinateSystem::Unspecified,
+	/// Drops the spherical harmonics bands above `new_degree`.
+	///
+	/// Keeps the first `dim_for_degree(new_degree)` coefficients of every
+	/// gaussian and updates the header accordingly.
+	///
+	/// # Args
+	///
+	/// `new_degree` - must not be greater than the current degree.
+	pub fn truncate_sh_degree(&mut self, new_degree: u8) -> Result<()> {
+		let degree = self.header.spherical_harmonics_degree;
+
+		if unlikely(new_degree > degree) {
+			bail!(
+				"cannot raise spherical harmonics degree from {degree} to {new_degree}"
+			);
+		}
+		if unlikely(!self.check_sizes()) {
+			bail!("inconsistent sizes");
+		}
+		let old_stride = dim_for_degree(degree) as usize * 3;
+		let new_stride = dim_for_degree(new_degree) as usize * 3;
+
+		if new_stride != old_stride {
+			let num_points = self.header.num_points as usize;
+
+			for i in 0..num_points {
+				self.spherical_harmonics.copy_within(
+					i * old_stride..i * old_stride + new_stride,
+					i * new_stride,
+				);
+			}
+			self.spherical_harmonics.truncate(num_points * new_stride);
 		}
+		self.header.spherical_harmonics_degree = new_degree;
+
+		Ok(())
 	}
-}
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Arbitrary)]
-pub struct LoadOptions {
-	/// Specifies the coordinate system to convert to when loading from
-	/// the one the data is stored in the SPZ file.
+	/// Estimates the color error of reducing the spherical harmonics to
+	/// `target_degree`, see [`GaussianSplat::truncate_sh_degree`].
 	///
-	/// For more information see [`CoordinateSystem`](crate::coord::CoordinateSystem).
-	pub coord_sys: CoordinateSystem,
-}
+	/// The view dependent color of every gaussian is evaluated with all and
+	/// with only the kept bands, in `sample_dirs` directions spread evenly
+	/// over the sphere (deterministic, so results are reproducible).
+	///
+	/// # Returns
+	///
+	/// The mean absolute difference per RGB channel, before clamping to the
+	/// displayable range. `0.0` if `target_degree` is not lower than the
+	/// current degree, or if there is nothing to sample.
+	pub fn sh_downgrade_error(&self, target_degree: u8, sample_dirs: usize) -> f32 {
+		let degree = self.header.spherical_harmonics_degree;
 
-impl LoadOptions {
-	#[inline]
-	pub fn builder() -> LoadOptionsBuilder {
-		LoadOptionsBuilder::default()
-	}
-}
+		if target_degree >= degree || sample_dirs == 0 || !self.check_sizes() {
+			return 0.0;
+		}
+		let num_points = self.header.num_points as usize;
 
-/// Options for saving the [`GaussianSplat`](crate::gaussian_splat::GaussianSplat) data.
-///
-/// Specifies the source coordinate system so axis flips can be applied during
-/// compression to convert to the SPZ internal format (RightUpBack|RUB).
-///
-/// For more information see [`CoordinateSystem`](crate::coord::CoordinateSystem).
-#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Arbitrary)]
-pub struct SaveOptions {
-	/// Specifies the coordinate system to convert to when saving the
-	/// Gaussian Splat data into the SPZ file.
-	pub coord_sys: CoordinateSystem,
-}
+		if unlikely(num_points == 0) {
+			return 0.0;
+		}
+		let dim = dim_for_degree(degree) as usize;
+		let kept = dim_for_degree(target_degree) as usize;
+		let dirs = math::fibonacci_sphere(sample_dirs);
 
-impl SaveOptions {
-	/// Creates a new [`SaveOptionsBuilder`].
-	#[inline]
-	pub fn builder() -> SaveOptionsBuilder {
-		SaveOptionsBuilder::default()
-	}
-}
+		let mut sum = 0.0_f64;
 
-/// Builder for [`SaveOptions`].
-#[derive(Clone, Debug, Arbitrary)]
-pub struct SaveOptionsBuilder {
-	coord_sys: CoordinateSystem,
-}
+		for dir in &dirs {
+			let basis = math::sh_basis(dir);
 
-impl SaveOptionsBuilder {
-	/// Sets the source coordinate system.
-	#[inline]
-	pub fn coord_sys(mut self, coord_sys: CoordinateSystem) -> Self {
-		self.coord_sys = coord_sys;
-		self
-	}
+			for sh in self.spherical_harmonics.chunks_exact(dim * 3) {
+				// Only the dropped bands differ between the two colors.
+				let mut diff = [0.0_f32; 3];
 
-	/// Builds the [`SaveOptions`].
-	#[inline]
-	pub fn build(self) -> SaveOptions {
-		SaveOptions {
-			coord_sys: self.coord_sys,
+				for k in kept..dim {
+					for (c, d) in diff.iter_mut().enumerate() {
+						*d += basis[k] * sh[k * 3 + c];
+					}
+				}
+				sum += diff.iter().map(|d| d.abs() as f64).sum::<f64>();
+			}
 		}
+		(sum / (dirs.len() * num_points * 3) as f64) as f32
 	}
-}
 
-impl Default for SaveOptionsBuilder {
-	#[inline]
-	fn default() -> Self {
+	/// Bakes the view dependent color seen along `view_dir` into `colors` and
+	/// drops the spherical harmonics, leaving a degree 0 splat, e.g. for flat
+	/// viewers that can't evaluate spherical harmonics.
+	///
+	/// The bands above 0 are evaluated like the reference renderer does, for
+	/// a gaussian viewed along `view_dir`, and their contribution is added
+	/// to the DC color. Seen from that direction the colors stay the same,
+	/// from others they are an approximation.
+	///
+	/// # Args
+	///
+	/// `view_dir` - direction the viewer looks in, needn't be normalized.
+	///
+	/// Fails if `view_dir` is zero or not finite, or if the sizes are
+	/// inconsistent.
+	pub fn bake_sh_to_color(&mut self, view_dir: [f32; 3]) -> Result<()> {
+		let Some(dir) = normalized(view_dir) else {
+			bail!("invalid view direction: {view_dir:?}");
+		};
+		if unlikely(!self.check_sizes()) {
+			bail!("inconsistent sizes");
+		}
+		let dim = dim_for_degree(self.header.spherical_harmonics_degree) as usize;
+
+		if dim > 0 {
+			let basis = math::sh_basis(&dir);
+
+			for (color, sh) in self
+				.colors
+				.chunks_exact_mut(3)
+				.zip(self.spherical_harmonics.chunks_exact(dim * 3))
+			{
+				for (c, dc) in color.iter_mut().enumerate() {
+					let rest = (0..dim)
+						.map(|k| basis[k] * sh[k * 3 + c])
+						.sum::<f32>();
+
+					*dc += rest / consts::SH_C0;
+				}
+			}
+		}
+		self.spherical_harmonics = Vec::new();
+		self.header.spherical_harmonics_degree = 0;
+
+		Ok(())
+	}
+
+	/// Clamps every alpha logit to `[-max_abs, max_abs]`.
+	///
+	/// Some trainers emit extreme logits that saturate to an opacity of
+	/// exactly 0 or 1, which then get clamped by
+	/// [`inv_sigmoid`](crate::math::inv_sigmoid) on load and don't
+	/// round-trip stably.
+	///
+	/// Fails if `max_abs` is negative or not finite, leaving the alphas
+	/// unchanged.
+	pub fn clamp_opacity_logits(&mut self, max_abs: f32) -> Result<()> {
+		if unlikely(!max_abs.is_finite() || max_abs < 0.0) {
+			bail!("invalid max abs opacity logit: {max_abs}");
+		}
+		for alpha in self.alphas.iter_mut() {
+			*alpha = alpha.clamp(-max_abs, max_abs);
+		}
+		Ok(())
+	}
+
+	/// Removes every gaussian whose opacity is below `min_alpha`.
+	///
+	/// Alphas are stored as logits, so the comparison is done in opacity
+	/// space, i.e. on `sigmoid(alpha)`.
+	///
+	/// # Args
+	///
+	/// `min_alpha` - minimum opacity in `[0, 1]` for a gaussian to be kept.
+	pub fn filter_by_opacity(&mut self, min_alpha: f32) {
+		let keep = self
+			.alphas
+			.iter()
+			.map(|a| math::sigmoid(*a) >= min_alpha)
+			.collect::<Vec<_>>();
+
+		self.retain_mask(&keep);
+	}
+
+	/// Retains only the gaussians whose center position lies inside `bbox`
+	/// (bounds inclusive), compacting all attribute arrays in place.
+	///
+	/// If no gaussian falls inside the box, the splat is left with zero
+	/// points and empty arrays.
+	pub fn crop(&mut self, bbox: &BoundingBox) {
+		let keep = self
+			.positions
+			.chunks_exact(3)
+			.map(|p| bbox.contains(p[0], p[1], p[2]))
+			.collect::<Vec<_>>();
+
+		self.retain_mask(&keep);
+	}
+
+	/// Reduces the splat to `target` gaussians, e.g. to produce levels of
+	/// detail for web delivery.
+	///
+	/// Gaussians are sampled without replacement with a probability
+	/// proportional to their opacity, so visually important ones are more
+	/// likely to survive. The sampling is deterministic for a given `seed`,
+	/// and the kept gaussians stay in their original order.
+	///
+	/// Does nothing if the splat has at most `target` gaussians.
+	pub fn decimate(&mut self, target: usize, seed: u64) {
+		let num_points = self.alphas.len();
+
+		if target >= num_points {
+			return;
+		}
+		let mut rng = math::SplitMix64::new(seed);
+
+		// Weighted sampling without replacement (Efraimidis-Spirakis): keep
+		// the `target` largest keys `u^(1/w)`, compared as `ln(u) / w`.
+		let mut keys = self
+			.alphas
+			.iter()
+			.enumerate()
+			.map(|(i, a)| {
+				let w = math::sigmoid(*a) as f64;
+				let w = if w.is_finite() { w.max(1e-6) } else { 1e-6 };
+
+				(rng.next_f64().ln() / w, i)
+			})
+			.collect::<Vec<_>>();
+
+		let mut keep = vec![false; num_points];
+
+		if target > 0 {
+			keys.select_nth_unstable_by(target - 1, |a, b| b.0.total_cmp(&a.0));
+
+			for (_, i) in &keys[..target] {
+				keep[*i] = true;
+			}
+		}
+		self.retain_mask(&keep);
+	}
+
+	/// Buckets the gaussians into a uniform grid of `voxel_size` cubes and
+	/// keeps only the most opaque one of each occupied voxel, for spatially
+	/// uniform levels of detail, unlike [`GaussianSplat::decimate`].
+	///
+	/// Ties keep the first gaussian, and the kept gaussians stay in their
+	/// original order. Gaussians with a non-finite position are dropped.
+	///
+	/// # Args
+	///
+	/// `voxel_size` - edge length of the voxels, must be positive and finite.
+	pub fn voxel_downsample(&mut self, voxel_size: f32) -> Result<()> {
+		if unlikely(!voxel_size.is_finite() || voxel_size <= 0.0) {
+			bail!("invalid voxel size: {voxel_size}");
+		}
+		let mut best = HashMap::<[i64; 3], usize>::new();
+
+		for (i, p) in self.positions.chunks_exact(3).enumerate() {
+			if unlikely(!p.iter().all(|v| v.is_finite())) {
+				continue;
+			}
+			let voxel = [
+				(p[0] / voxel_size).floor() as i64,
+				(p[1] / voxel_size).floor() as i64,
+				(p[2] / voxel_size).floor() as i64,
+			];
+			best.entry(voxel)
+				.and_modify(|j| {
+					if self.alphas[i] > self.alphas[*j] {
+						*j = i;
+					}
+				})
+				.or_insert(i);
+		}
+		let mut keep = vec![false; self.alphas.len()];
+
+		for i in best.into_values() {
+			keep[i] = true;
+		}
+		self.retain_mask(&keep);
+
+		Ok(())
+	}
+
+	/// Releases the excess capacity of all attribute arrays, e.g. after
+	/// [`GaussianSplat::crop`] or [`GaussianSplat::filter_by_opacity`], which
+	/// compact in place and keep the original allocations.
+	pub fn shrink_to_fit(&mut self) {
+		self.positions.shrink_to_fit();
+		self.scales.shrink_to_fit();
+		self.rotations.shrink_to_fit();
+		self.alphas.shrink_to_fit();
+		self.colors.shrink_to_fit();
+		self.spherical_harmonics.shrink_to_fit();
+	}
+
+	/// Removes gaussian `i` in `O(1)` by moving the last gaussian into its
+	/// slot, like [`Vec::swap_remove`]. Doesn't preserve the order of the
+	/// gaussians.
+	///
+	/// Fails if `i` is out of bounds or the attribute arrays are inconsistent
+	/// with `header.num_points`.
+	pub fn swap_remove(&mut self, i: usize) -> Result<()> {
+		self.validate()
+			.with_context(|| "cannot swap remove, invalid splat")?;
+
+		let num_points = self.header.num_points as usize;
+
+		if unlikely(i >= num_points) {
+			bail!("index {i} out of bounds for {num_points} points");
+		}
+		fn swap_remove_chunk(v: &mut Vec<f32>, i: usize, last: usize, stride: usize) {
+			v.copy_within(last * stride..(last + 1) * stride, i * stride);
+			v.truncate(last * stride);
+		}
+		let last = num_points - 1;
+		let sh_stride = dim_for_degree(self.header.spherical_harmonics_degree) as usize * 3;
+
+		swap_remove_chunk(&mut self.positions, i, last, 3);
+		swap_remove_chunk(&mut self.scales, i, last, 3);
+		swap_remove_chunk(&mut self.rotations, i, last, 4);
+		swap_remove_chunk(&mut self.alphas, i, last, 1);
+		swap_remove_chunk(&mut self.colors, i, last, 3);
+		swap_remove_chunk(&mut self.spherical_harmonics, i, last, sh_stride);
+
+		self.header.num_points = last as i32;
+
+		Ok(())
+	}
+
+	/// Reserves capacity in every attribute array for exactly
+	/// `header.num_points` gaussians of `header.spherical_harmonics_degree`,
+	/// e.g. before filling the arrays from a deserializer.
+	///
+	/// The header is trusted as is, so validate it against the source
+	/// first, a bogus `num_points` can reserve gigabytes.
+	pub fn reserve_exact_for_header(&mut self) {
+		fn reserve_exact_to(v: &mut Vec<f32>, len: usize) {
+			v.reserve_exact(len.saturating_sub(v.len()));
+		}
+		let n = self.header.num_points.max(0) as usize;
+		let sh_dim = dim_for_degree(self.header.spherical_harmonics_degree) as usize;
+
+		reserve_exact_to(&mut self.positions, n * 3);
+		reserve_exact_to(&mut self.scales, n * 3);
+		reserve_exact_to(&mut self.rotations, n * 4);
+		reserve_exact_to(&mut self.alphas, n);
+		reserve_exact_to(&mut self.colors, n * 3);
+		reserve_exact_to(&mut self.spherical_harmonics, n * sh_dim * 3);
+	}
+
+	/// Returns a copy of the splat cropped to `bbox`, see [`GaussianSplat::crop`].
+	#[inline]
+	pub fn cropped(&self, bbox: &BoundingBox) -> Self {
+		let mut ret = self.clone();
+
+		ret.crop(bbox);
+		ret
+	}
+
+	/// Returns the per-gaussian depth along `view_dir`, i.e.
+	/// `dot(position, view_dir)`, without reordering the splat.
+	///
+	/// These are the keys [`GaussianSplat::sort_by_depth`] sorts by, for
+	/// renderers that sort on the GPU.
+	///
+	/// # Args
+	///
+	/// `view_dir` - viewing direction, pointing from the camera into the
+	/// scene. Doesn't need to be normalized.
+	pub fn depth_keys(&self, view_dir: [f32; 3]) -> Vec<f32> {
+		self.positions
+			.chunks_exact(3)
+			.map(|p| p[0] * view_dir[0] + p[1] * view_dir[1] + p[2] * view_dir[2])
+			.collect()
+	}
+
+	/// Reorders the gaussians back to front along `view_dir`, i.e. by
+	/// descending `dot(position, view_dir)`, as needed by alpha-blended
+	/// renderers.
+	///
+	/// # Args
+	///
+	/// `view_dir` - viewing direction, pointing from the camera into the
+	/// scene. Doesn't need to be normalized.
+	pub fn sort_by_depth(&mut self, view_dir: [f32; 3]) {
+		let depths = self.depth_keys(view_dir);
+
+		let mut order = (0..depths.len()).collect::<Vec<_>>();
+
+		order.sort_by(|a, b| depths[*b].total_cmp(&depths[*a]));
+
+		self.permute(&order);
+	}
+
+	/// Moves every gaussian by `offset`.
+	pub fn translate(&mut self, offset: [f32; 3]) {
+		for p in self.positions.chunks_exact_mut(3) {
+			p[0] += offset[0];
+			p[1] += offset[1];
+			p[2] += offset[2];
+		}
+	}
+
+	/// Translates the splat so that its bounding box is centered at the
+	/// origin.
+	///
+	/// # Returns
+	///
+	/// The applied offset, i.e. the negated center. Translating by its
+	/// negation undoes the recentering. Empty splats are left untouched and
+	/// yield a zero offset.
+	pub fn center_at_origin(&mut self) -> [f32; 3] {
+		if unlikely(self.positions.len() < 3) {
+			return [0.0; 3];
+		}
+		let (cx, cy, cz) = self.bbox().center();
+		let offset = [-cx, -cy, -cz];
+
+		self.translate(offset);
+
+		offset
+	}
+
+	/// Returns the mean position of all gaussians, `[0, 0, 0]` for an empty
+	/// splat.
+	///
+	/// Unlike the bounding box center, this isn't skewed by a few far away
+	/// outliers. Accumulates in `f64` to stay precise for large splats.
+	pub fn centroid(&self) -> [f32; 3] {
+		let n = self.positions.len() / 3;
+
+		if unlikely(n == 0) {
+			return [0.0; 3];
+		}
+		let mut sum = [0.0_f64; 3];
+
+		for p in self.positions.chunks_exact(3) {
+			sum[0] += p[0] as f64;
+			sum[1] += p[1] as f64;
+			sum[2] += p[2] as f64;
+		}
+		sum.map(|s| (s / n as f64) as f32)
+	}
+
+	/// Translates the splat so that its [`GaussianSplat::centroid`] is at the
+	/// origin, e.g. for scans far from it, which lose fixed point precision
+	/// and complicate camera setup. See [`GaussianSplat::center_at_origin`]
+	/// to center the bounding box instead.
+	///
+	/// # Returns
+	///
+	/// The applied offset, i.e. the negated centroid. Translating by its
+	/// negation undoes the recentering.
+	pub fn recenter(&mut self) -> [f32; 3] {
+		let offset = self.centroid().map(|c| -c);
+
+		self.translate(offset);
+
+		offset
+	}
+
+	/// Rearranges the gaussians so that gaussian `i` of the result is
+	/// gaussian `order[i]` of the input.
+	fn permute(&mut self, order: &[usize]) {
+		debug_assert!(self.check_sizes());
+		debug_assert_eq!(order.len(), self.header.num_points.max(0) as usize);
+
+		fn gather(src: &[f32], order: &[usize], stride: usize) -> Vec<f32> {
+			let mut dst = Vec::with_capacity(order.len() * stride);
+
+			for i in order {
+				dst.extend_from_slice(&src[i * stride..(i + 1) * stride]);
+			}
+			dst
+		}
+		let sh_stride = dim_for_degree(self.header.spherical_harmonics_degree) as usize * 3;
+
+		self.positions = gather(&self.positions, order, 3);
+		self.scales = gather(&self.scales, order, 3);
+		self.rotations = gather(&self.rotations, order, 4);
+		self.alphas = gather(&self.alphas, order, 1);
+		self.colors = gather(&self.colors, order, 3);
+		self.spherical_harmonics = gather(&self.spherical_harmonics, order, sh_stride);
+	}
+
+	/// Keeps gaussian `i` iff `keep[i]` is `true`, preserving order and
+	/// keeping every attribute array and `header.num_points` consistent.
+	fn retain_mask(&mut self, keep: &[bool]) {
+		debug_assert!(self.check_sizes());
+		debug_assert_eq!(keep.len(), self.header.num_points.max(0) as usize);
+
+		let sh_stride = dim_for_degree(self.header.spherical_harmonics_degree) as usize * 3;
+		let mut dst = 0_usize;
+
+		for (src, _) in keep.iter().enumerate().filter(|(_, k)| **k) {
+			if src != dst {
+				self.positions.copy_within(src * 3..src * 3 + 3, dst * 3);
+				self.scales.copy_within(src * 3..src * 3 + 3, dst * 3);
+				self.rotations.copy_within(src * 4..src * 4 + 4, dst * 4);
+				self.alphas[dst] = self.alphas[src];
+				self.colors.copy_within(src * 3..src * 3 + 3, dst * 3);
+				self.spherical_harmonics.copy_within(
+					src * sh_stride..(src + 1) * sh_stride,
+					dst * sh_stride,
+				);
+			}
+			dst += 1;
+		}
+		self.positions.truncate(dst * 3);
+		self.scales.truncate(dst * 3);
+		self.rotations.truncate(dst * 4);
+		self.alphas.truncate(dst);
+		self.colors.truncate(dst * 3);
+		self.spherical_harmonics.truncate(dst * sh_stride);
+
+		self.header.num_points = dst as i32;
+	}
+
+	/// Returns the opacity of every gaussian in `[0, 1]`, i.e. `alphas` with
+	/// the sigmoid activation applied.
+	pub fn opacities(&self) -> Vec<f32> {
+		self.alphas.iter().map(|a| math::sigmoid(*a)).collect()
+	}
+
+	/// Returns the world space scales, i.e. `exp` of the log-scales in
+	/// `scales`, in the same `(x, y, z)` per gaussian layout.
+	pub fn linear_scales(&self) -> Vec<f32> {
+		self.scales.iter().map(|s| s.exp()).collect()
+	}
+
+	/// Returns a view of the `i`-th gaussian, borrowing from the attribute
+	/// arrays without copying.
+	///
+	/// # Returns
+	///
+	/// `None` if `i` is out of bounds, or the arrays are too short for it.
+	pub fn gaussian(&self, i: usize) -> Option<GaussianView<'_>> {
+		let sh_stride = dim_for_degree(self.header.spherical_harmonics_degree) as usize * 3;
+		let range = |stride: usize| {
+			let start = i.checked_mul(stride)?;
+
+			Some(start..start.checked_add(stride)?)
+		};
+
+		Some(GaussianView {
+			position: self.positions.get(range(3)?)?.try_into().ok()?,
+			scale: self.scales.get(range(3)?)?.try_into().ok()?,
+			rotation: self.rotations.get(range(4)?)?.try_into().ok()?,
+			alpha: *self.alphas.get(i)?,
+			color: self.colors.get(range(3)?)?.try_into().ok()?,
+			spherical_harmonics: self.spherical_harmonics.get(range(sh_stride)?)?,
+		})
+	}
+
+	/// Iterates over views of all gaussians, see [`GaussianSplat::gaussian`].
+	///
+	/// Stops early if the arrays are shorter than `num_points` implies.
+	pub fn gaussians(&self) -> impl Iterator<Item = GaussianView<'_>> {
+		(0..self.header.num_points.max(0) as usize).map_while(|i| self.gaussian(i))
+	}
+
+	/// Returns a single spherical harmonics coefficient.
+	///
+	/// # Args
+	///
+	/// `point` - index of the gaussian.
+	/// `coeff` - index of the coefficient, in `0..dim_for_degree(degree)`.
+	/// `channel` - color channel, `0..3` for r, g, b.
+	///
+	/// # Returns
+	///
+	/// `None` if any index is out of range.
+	pub fn sh_at(&self, point: usize, coeff: usize, channel: usize) -> Option<f32> {
+		let sh_dim = dim_for_degree(self.header.spherical_harmonics_degree) as usize;
+
+		if unlikely(coeff >= sh_dim || channel >= 3) {
+			return None;
+		}
+		let idx = point
+			.checked_mul(sh_dim * 3)?
+			.checked_add(coeff * 3 + channel)?;
+
+		self.spherical_harmonics.get(idx).copied()
+	}
+
+	/// Compute median ellipsoid volume.
+	pub fn median_volume(&self) -> f32 {
+		if unlikely(self.scales.is_empty()) {
+			return 0.01;
+		}
+		// The volume of an ellipsoid is 4/3 * pi * x * y * z,
+		// where x, y, and z are the radii on each axis.
+		// Scales are stored on a log scale, and
+		// 	exp(x) * exp(y) * exp(z) = exp(x + y + z).
+		// So we can sort by value = (x + y + z) and compute
+		// 	volume = 4/3 * pi * exp(value) later.
+		let mut sums = self
+			.scales
+			.chunks_exact(3)
+			.filter_map(|c| {
+				let s = c[0] + c[1] + c[2];
+
+				if unlikely(!s.is_finite()) {
+					None
+				} else {
+					Some(s)
+				}
+			})
+			.collect::<Vec<_>>();
+
+		if unlikely(sums.is_empty()) {
+			return 0.01;
+		}
+		let n = sums.len() / 2;
+
+		sums.select_nth_unstable_by(n, |a, b| {
+			a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal)
+		});
+		let median = sums[sums.len() / 2];
+
+		if unlikely(!median.is_finite() || median <= f32::MIN_POSITIVE.ln()) {
+			return 0.01;
+		}
+		(std::f32::consts::PI * 4.0 / 3.0) * median.exp()
+	}
+
+	/// Computes percentiles of the per-gaussian log-scale sum
+	/// (`x + y + z`), i.e. of the log of the ellipsoid volume up to a
+	/// constant. Useful to find outliers, like huge "sky" gaussians.
+	///
+	/// Non-finite sums are ignored. Percentiles are linearly interpolated
+	/// between the closest ranks.
+	///
+	/// # Args
+	///
+	/// `ps` - percentiles in `[0, 100]`, clamped to that range.
+	///
+	/// # Returns
+	///
+	/// One value per entry of `ps`, all `NaN` if there are no gaussians.
+	pub fn scale_sum_percentiles(&self, ps: &[f32]) -> Vec<f32> {
+		let mut sums = self
+			.scales
+			.chunks_exact(3)
+			.map(|c| c[0] + c[1] + c[2])
+			.filter(|s| s.is_finite())
+			.collect::<Vec<_>>();
+
+		if unlikely(sums.is_empty()) {
+			return vec![f32::NAN; ps.len()];
+		}
+		sums.sort_unstable_by(f32::total_cmp);
+
+		let last = (sums.len() - 1) as f32;
+
+		ps.iter()
+			.map(|p| {
+				let rank = p.clamp(0.0, 100.0) / 100.0 * last;
+				let lo = rank.floor() as usize;
+				let hi = rank.ceil() as usize;
+
+				sums[lo] + (sums[hi] - sums[lo]) * (rank - lo as f32)
+			})
+			.collect()
+	}
+
+	/// Computes distribution statistics to diagnose bad captures, beyond the
+	/// single number of [`Self::median_volume`].
+	///
+	/// # Returns
+	///
+	/// A [`SplatStats`], see its fields.
+	pub fn stats(&self) -> SplatStats {
+		let scale_percentiles = self.scale_sum_percentiles(&[10.0, 50.0, 90.0]);
+
+		let mut opacity_histogram = [0_usize; SplatStats::OPACITY_BINS];
+
+		for a in self.alphas.iter().filter(|a| a.is_finite()) {
+			let bin = (math::sigmoid(*a) * SplatStats::OPACITY_BINS as f32) as usize;
+
+			opacity_histogram[bin.min(SplatStats::OPACITY_BINS - 1)] += 1;
+		}
+
+		SplatStats {
+			bbox: self.bbox(),
+			scale_sum_p10: scale_percentiles[0],
+			scale_sum_p50: scale_percentiles[1],
+			scale_sum_p90: scale_percentiles[2],
+			opacity_histogram,
+			non_finite: self.find_non_finite().total(),
+		}
+	}
+
+	/// Counts the gaussians per cell of a uniform grid spanning the bounding
+	/// box, to spot clumps and empty regions.
+	///
+	/// Gaussians with a non-finite position aren't counted. Positions on the
+	/// upper bounds fall into the last cell of each axis.
+	///
+	/// # Args
+	///
+	/// `divisions` - number of cells along the x, y and z axes.
+	///
+	/// # Returns
+	///
+	/// The counts, indexed by `x + divisions[0] * (y + divisions[1] * z)`.
+	/// All zero for an empty splat, empty if any of the `divisions` is `0`.
+	/// Fails if the number of cells overflows `usize`.
+	pub fn density_grid(&self, divisions: [usize; 3]) -> Result<Vec<u32>> {
+		let Some(num_cells) = divisions
+			.iter()
+			.try_fold(1_usize, |acc, d| acc.checked_mul(*d))
+		else {
+			bail!("number of density grid cells for {divisions:?} overflows usize");
+		};
+		let mut counts = vec![0_u32; num_cells];
+
+		if unlikely(counts.is_empty()) {
+			return Ok(counts);
+		}
+		let finite = || {
+			self.positions
+				.chunks_exact(3)
+				.filter(|p| p.iter().all(|v| v.is_finite()))
+		};
+		let mut min = [f32::INFINITY; 3];
+		let mut max = [f32::NEG_INFINITY; 3];
+
+		for p in finite() {
+			for axis in 0..3 {
+				min[axis] = min[axis].min(p[axis]);
+				max[axis] = max[axis].max(p[axis]);
+			}
+		}
+		for p in finite() {
+			let mut cell = [0_usize; 3];
+
+			for axis in 0..3 {
+				let extent = max[axis] - min[axis];
+
+				if extent > 0.0 {
+					let t = (p[axis] - min[axis]) / extent;
+
+					cell[axis] = ((t * divisions[axis] as f32) as usize)
+						.min(divisions[axis] - 1);
+				}
+			}
+			counts[cell[0] + divisions[0] * (cell[1] + divisions[1] * cell[2])] += 1;
+		}
+		Ok(counts)
+	}
+
+	/// Renders a small RGBA8 preview, e.g. for thumbnails in a file browser.
+	///
+	/// The gaussians are projected orthographically along `view_dir` as flat
+	/// disks with a radius of twice their largest linear scale, at least one
+	/// pixel, and alpha blended back to front. Only the DC color is used,
+	/// spherical harmonics are ignored. The view is framed to fit the
+	/// bounding box of the finite positions. This is no substitute for a real
+	/// splat renderer.
+	///
+	/// # Args
+	///
+	/// * `width` - Image width in pixels.
+	/// * `height` - Image height in pixels.
+	/// * `view_dir` - Direction the camera looks in. `-Z`, i.e. forward in
+	///   RUB, is used if it is zero or not finite.
+	///
+	/// # Returns
+	///
+	/// `width * height * 4` bytes of row-major, non-premultiplied RGBA with
+	/// the top row first. Uncovered pixels are transparent black.
+	pub fn render_thumbnail(&self, width: usize, height: usize, view_dir: [f32; 3]) -> Vec<u8> {
+		let mut image = vec![[0.0_f32; 4]; width * height];
+
+		if unlikely(image.is_empty()) {
+			return Vec::new();
+		}
+		let forward = normalized(view_dir).unwrap_or([0.0, 0.0, -1.0]);
+		let up_hint = if forward[1].abs() > 0.99 {
+			[0.0, 0.0, -1.0]
+		} else {
+			[0.0, 1.0, 0.0]
+		};
+		let right = normalized(cross(forward, up_hint)).unwrap_or([1.0, 0.0, 0.0]);
+		let up = cross(right, forward);
+
+		// (u, v, depth, radius, rgba) in world units
+		let mut disks = self
+			.gaussians()
+			.filter(|g| g.position.iter().all(|v| v.is_finite()))
+			.map(|g| {
+				let p = *g.position;
+				let radius = 2.0 * g
+					.scale
+					.iter()
+					.copied()
+					.fold(f32::NEG_INFINITY, f32::max)
+					.exp();
+				let [r, gr, b] =
+					g.color.map(|c| (0.5 + consts::SH_C0 * c).clamp(0.0, 1.0));
+
+				(
+					dot(p, right),
+					dot(p, up),
+					dot(p, forward),
+					if radius.is_finite() { radius } else { 0.0 },
+					[r, gr, b, math::sigmoid(g.alpha)],
+				)
+			})
+			.collect::<Vec<_>>();
+
+		if unlikely(disks.is_empty()) {
+			return vec![0_u8; width * height * 4];
+		}
+		let mut min = [f32::INFINITY; 2];
+		let mut max = [f32::NEG_INFINITY; 2];
+		let mut max_radius = 0.0_f32;
+
+		for &(u, v, _, radius, _) in &disks {
+			min = [min[0].min(u), min[1].min(v)];
+			max = [max[0].max(u), max[1].max(v)];
+			max_radius = max_radius.max(radius);
+		}
+		let (w, h) = (width as f32, height as f32);
+		// world units per pixel, with a 10% margin
+		let mut unit = ((max[0] - min[0]) / w).max((max[1] - min[1]) / h) * 1.1;
+
+		if unit <= 0.0 || !unit.is_finite() {
+			unit = 2.0 * max_radius / w.min(h);
+		}
+		if unit <= 0.0 || !unit.is_finite() {
+			unit = 1.0;
+		}
+		let center = [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0];
+
+		disks.sort_unstable_by(|a, b| b.2.total_cmp(&a.2));
+
+		for (u, v, _, radius, rgba) in disks {
+			let cx = (u - center[0]) / unit + w / 2.0;
+			let cy = h / 2.0 - (v - center[1]) / unit;
+			let r = (radius / unit).max(1.0);
+			let x0 = (cx - r).floor().clamp(0.0, w) as usize;
+			let x1 = (cx + r).ceil().clamp(0.0, w) as usize;
+			let y0 = (cy - r).floor().clamp(0.0, h) as usize;
+			let y1 = (cy + r).ceil().clamp(0.0, h) as usize;
+			let a = rgba[3];
+
+			for y in y0..y1 {
+				for x in x0..x1 {
+					let dx = x as f32 + 0.5 - cx;
+					let dy = y as f32 + 0.5 - cy;
+
+					if dx * dx + dy * dy > r * r {
+						continue;
+					}
+					let dst = &mut image[y * width + x];
+
+					for c in 0..3 {
+						dst[c] = rgba[c] * a + dst[c] * (1.0 - a);
+					}
+					dst[3] = a + dst[3] * (1.0 - a);
+				}
+			}
+		}
+		image.iter()
+			.flat_map(|px| {
+				let a = px[3];
+				let rgb = |c: f32| if a > 0.0 { c / a } else { 0.0 };
+
+				[rgb(px[0]), rgb(px[1]), rgb(px[2]), a]
+					.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+			})
+			.collect()
+	}
+
+	/// Counts the `NaN` and infinite values of each attribute array.
+	///
+	/// Such values usually come from a diverged training run and can't be
+	/// packed meaningfully, see [`GaussianSplat::sanitize`].
+	pub fn find_non_finite(&self) -> NonFiniteReport {
+		let count = |v: &[f32]| v.iter().filter(|x| !x.is_finite()).count();
+
+		NonFiniteReport {
+			positions: count(&self.positions),
+			scales: count(&self.scales),
+			rotations: count(&self.rotations),
+			alphas: count(&self.alphas),
+			colors: count(&self.colors),
+			spherical_harmonics: count(&self.spherical_harmonics),
+		}
+	}
+
+	/// Replaces non-finite positions and scales with zeros, and rotations
+	/// that are non-finite or have a zero norm with the identity.
+	///
+	/// Other attributes are left as is.
+	pub fn sanitize(&mut self) {
+		for v in self.positions.iter_mut().chain(self.scales.iter_mut()) {
+			if unlikely(!v.is_finite()) {
+				*v = 0.0;
+			}
+		}
+		for r in self.rotations.chunks_exact_mut(4) {
+			let norm_sq = r[0] * r[0] + r[1] * r[1] + r[2] * r[2] + r[3] * r[3];
+
+			if unlikely(!norm_sq.is_finite() || norm_sq == 0.0) {
+				r.copy_from_slice(&[0.0, 0.0, 0.0, 1.0]);
+			}
+		}
+	}
+
+	/// Counts the color components that would be clipped when packed.
+	///
+	/// Colors are stored as `color * COLOR_SCALE * 255 + 127.5` in a `u8`, so
+	/// only DC values in about `[-3.33, 3.33]` are representable; anything
+	/// outside is clamped on save. Non-finite values count as clipped too.
+	/// The encoding is the same for every [`SaveOptions`].
+	///
+	/// # Returns
+	///
+	/// The number of clipped components, `0` means colors survive packing
+	/// up to quantization.
+	pub fn colors_clip_report(&self) -> usize {
+		self.colors
+			.iter()
+			.filter(|&&c| {
+				let v = c * (consts::COLOR_SCALE * 255.0) + (0.5 * 255.0);
+
+				!(0.0..=255.0).contains(&v)
+			})
+			.count()
+	}
+
+	/// Validates that all internal arrays have consistent sizes.
+	///
+	/// Checks that:
+	/// - `num_points` is non-negative
+	/// - `spherical_harmonics_degree` is in range 0..=3
+	/// - `positions` has length `num_points * 3`
+	/// - `scales` has length `num_points * 3`
+	/// - `rotations` has length `num_points * 4`
+	/// - `alphas` has length `num_points`
+	/// - `colors` has length `num_points * 3`
+	/// - `spherical_harmonics` has length `num_points * sh_dim * 3`
+	///
+	/// # Returns
+	///
+	/// Returns `true` if all sizes are valid, `false` otherwise.
+	pub fn check_sizes(&self) -> bool {
+		self.validate().is_ok()
+	}
+
+	/// Like [`GaussianSplat::check_sizes`], but the error names the first
+	/// invalid header field or array, with its actual and expected length.
+	pub fn validate(&self) -> Result<()> {
+		if unlikely(self.header.num_points < 0) {
+			bail!("negative number of points: {}", self.header.num_points);
+		}
+		if unlikely(self.header.spherical_harmonics_degree > 3) {
+			bail!(
+				"unsupported spherical harmonics degree: {}",
+				self.header.spherical_harmonics_degree
+			);
+		}
+		let np = self.header.num_points as usize;
+		let sh_dim = dim_for_degree(self.header.spherical_harmonics_degree) as usize;
+
+		let expected_xyz = np.saturating_mul(3);
+		let expected_rot = np.saturating_mul(4);
+		let expected_colors = np.saturating_mul(3);
+		let expected_sh = np.saturating_mul(sh_dim).saturating_mul(3);
+
+		for (name, len, expected) in [
+			("positions", self.positions.len(), expected_xyz),
+			("scales", self.scales.len(), expected_xyz),
+			("rotations", self.rotations.len(), expected_rot),
+			("alphas", self.alphas.len(), np),
+			("colors", self.colors.len(), expected_colors),
+			(
+				"spherical_harmonics",
+				self.spherical_harmonics.len(),
+				expected_sh,
+			),
+		] {
+			if unlikely(len != expected) {
+				bail!(
+					"{name} has length {len}, expected {expected} for {np} points"
+				);
+			}
+		}
+		Ok(())
+	}
+
+	/// Cross-checks the header against the data it describes.
+	///
+	/// Unlike [`GaussianSplat::check_sizes`], which validates all arrays
+	/// against each other, this only checks that `header.num_points` matches
+	/// the number of positions and that the spherical harmonics length
+	/// matches `header.spherical_harmonics_degree`, which is what goes wrong
+	/// when the header is edited by hand.
+	pub fn header_matches_data(&self) -> bool {
+		if unlikely(!self.positions.len().is_multiple_of(3)) {
+			return false;
+		}
+		let np = self.positions.len() / 3;
+
+		if unlikely(usize::try_from(self.header.num_points) != Ok(np)) {
+			return false;
+		}
+		let sh_dim = dim_for_degree(self.header.spherical_harmonics_degree) as usize;
+
+		self.spherical_harmonics.len() == np * sh_dim * 3
+	}
+
+	/// Returns `true` if `other` has the same header fields and every
+	/// attribute is within `tolerance` of the one in `self`, e.g. to compare
+	/// a splat with its packed and reloaded copy.
+	///
+	/// Rotations are compared by `1 - |dot|` of the normalized quaternions,
+	/// since `q` and `-q` are the same rotation. All other attributes are
+	/// compared per component.
+	///
+	/// The version and fractional bits aren't compared, as they only affect
+	/// the packed representation.
+	pub fn approx_eq(&self, other: &GaussianSplat, tolerance: f32) -> bool {
+		fn close(a: &[f32], b: &[f32], tolerance: f32) -> bool {
+			a.len() == b.len()
+				&& a.iter().zip(b).all(|(a, b)| (a - b).abs() <= tolerance)
+		}
+		fn same_rotation(a: &[f32], b: &[f32], tolerance: f32) -> bool {
+			let dot = a.iter().zip(b).map(|(a, b)| a * b).sum::<f32>();
+			let norms = a.iter().map(|v| v * v).sum::<f32>().sqrt()
+				* b.iter().map(|v| v * v).sum::<f32>().sqrt();
+
+			if unlikely(norms == 0.0) {
+				return close(a, b, tolerance);
+			}
+			1.0 - (dot / norms).abs() <= tolerance
+		}
+		self.header.num_points == other.header.num_points
+			&& self.header.spherical_harmonics_degree
+				== other.header.spherical_harmonics_degree
+			&& self.header.flags.is_antialiased() == other.header.flags.is_antialiased()
+			&& close(&self.positions, &other.positions, tolerance)
+			&& close(&self.scales, &other.scales, tolerance)
+			&& self.rotations.len() == other.rotations.len()
+			&& self.rotations
+				.chunks_exact(4)
+				.zip(other.rotations.chunks_exact(4))
+				.all(|(a, b)| same_rotation(a, b, tolerance))
+			&& close(&self.alphas, &other.alphas, tolerance)
+			&& close(&self.colors, &other.colors, tolerance)
+			&& close(
+				&self.spherical_harmonics,
+				&other.spherical_harmonics,
+				tolerance,
+			)
+	}
+
+	/// Returns the axis aligned bounding box of the gaussian centers.
+	///
+	/// Empty splats yield a zeroed box.
+	pub fn bbox(&self) -> BoundingBox {
+		if unlikely(self.positions.len() < 3) {
+			return BoundingBox::default();
+		}
+		let mut min_x = self.positions[0];
+		let mut max_x = self.positions[0];
+		let mut min_y = self.positions[1];
+		let mut max_y = self.positions[1];
+		let mut min_z = self.positions[2];
+		let mut max_z = self.positions[2];
+
+		for i in (0..self.positions.len()).step_by(3) {
+			min_x = min_x.min(self.positions[i]);
+			max_x = max_x.max(self.positions[i]);
+			min_y = min_y.min(self.positions[i + 1]);
+			max_y = max_y.max(self.positions[i + 1]);
+			min_z = min_z.min(self.positions[i + 2]);
+			max_z = max_z.max(self.positions[i + 2]);
+		}
+		BoundingBox {
+			min_x,
+			max_x,
+			min_y,
+			max_y,
+			min_z,
+			max_z,
+		}
+	}
+
+	/// Returns the center of the splat's [bounding box](Self::bbox).
+	///
+	/// # Returns
+	///
+	/// The `[x, y, z]` center coordinates.
+	#[inline]
+	pub fn center(&self) -> [f32; 3] {
+		let (x, y, z) = self.bbox().center();
+
+		[x, y, z]
+	}
+
+	/// Returns the extent of the splat's [bounding box](Self::bbox).
+	///
+	/// # Returns
+	///
+	/// The `[x, y, z]` size of the box along each axis.
+	#[inline]
+	pub fn extent(&self) -> [f32; 3] {
+		let (x, y, z) = self.bbox().size();
+
+		[x, y, z]
+	}
+
+	/// Returns the number of gaussians in the splat.
+	///
+	/// A negative `header.num_points` counts as `0`.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.header.num_points.max(0) as usize
+	}
+
+	/// Returns `true` if the splat has no gaussians, see [`Self::len`].
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Reports the bytes each attribute array of the splat takes, e.g. to
+	/// check whether it fits a GPU memory budget.
+	///
+	/// Only the stored values are counted, spare capacity of the vectors
+	/// is not.
+	pub fn memory_footprint(&self) -> MemoryReport {
+		let bytes = |v: &[f32]| std::mem::size_of_val(v);
+		let mut report = MemoryReport {
+			positions: bytes(&self.positions),
+			scales: bytes(&self.scales),
+			rotations: bytes(&self.rotations),
+			alphas: bytes(&self.alphas),
+			colors: bytes(&self.colors),
+			spherical_harmonics: bytes(&self.spherical_harmonics),
+			total: 0,
+		};
+		report.total = report.positions
+			+ report.scales + report.rotations
+			+ report.alphas + report.colors
+			+ report.spherical_harmonics;
+
+		report
+	}
+
+	pub fn pretty_fmt(&self) -> String {
+		let bbox = self.bbox();
+		let (size_x, size_y, size_z) = bbox.size();
+		let (center_x, center_y, center_z) = bbox.center();
+
+		let mut ret = String::new();
+
+		let _ = writeln!(ret, "GaussianSplat:");
+		let _ = writeln!(ret, "\tNumber of points:\t\t{}", self.header.num_points);
+		let _ = writeln!(
+			ret,
+			"\tSpherical harmonics degree:\t{}",
+			self.header.spherical_harmonics_degree
+		);
+		let _ = writeln!(
+			ret,
+			"\tAntialiased:\t\t\t{}",
+			self.header.flags.is_antialiased()
+		);
+		let _ = writeln!(ret, "\tMedian ellipsoid volume:\t{:}", self.median_volume());
+		let _ = writeln!(
+			ret,
+			"\tBounding box:\n\t\tx: {:} to {:} (size {:}, center {:})\n",
+			bbox.min_x, bbox.max_x, size_x, center_x
+		);
+		let _ = writeln!(
+			ret,
+			"\t\ty: {:} to {:} (size {:}, center {:})",
+			bbox.min_y, bbox.max_y, size_y, center_y
+		);
+		let _ = writeln!(
+			ret,
+			"\t\tz: {:} to {:} (size {:}, center {:})",
+			bbox.min_z, bbox.max_z, size_z, center_z
+		);
+		ret
+	}
+
+	/// Formats the same summary as [`Self::pretty_fmt`] as one `key=value`
+	/// pair per line, for shell scripts to grep and cut.
+	///
+	/// # Returns
+	///
+	/// Lines for `num_points`, `sh_degree`, `antialiased`, `median_volume`
+	/// and `bbox_{min,max}_{x,y,z}`, in that order.
+	pub fn fmt_kv(&self) -> String {
+		let bbox = self.bbox();
+
+		let mut ret = String::new();
+
+		let _ = writeln!(ret, "num_points={}", self.header.num_points);
+		let _ = writeln!(ret, "sh_degree={}", self.header.spherical_harmonics_degree);
+		let _ = writeln!(ret, "antialiased={}", self.header.flags.is_antialiased());
+		let _ = writeln!(ret, "median_volume={}", self.median_volume());
+		let _ = writeln!(ret, "bbox_min_x={}", bbox.min_x);
+		let _ = writeln!(ret, "bbox_max_x={}", bbox.max_x);
+		let _ = writeln!(ret, "bbox_min_y={}", bbox.min_y);
+		let _ = writeln!(ret, "bbox_max_y={}", bbox.max_y);
+		let _ = writeln!(ret, "bbox_min_z={}", bbox.min_z);
+		let _ = writeln!(ret, "bbox_max_z={}", bbox.max_z);
+		ret
+	}
+}
+
+/// Returns the directory `filepath` is in, `""` for bare file names.
+fn parent_dir(filepath: &Path) -> Result<&Path> {
+	filepath.parent()
+		.ok_or_else(|| anyhow::anyhow!("invalid file path: {}", filepath.display()))
+}
+
+/// Creates the directory `filepath` is in, including missing ancestors.
+fn create_parent_dirs(filepath: &Path) -> Result<()> {
+	let parent = parent_dir(filepath)?;
+
+	std::fs::create_dir_all(parent)
+		.with_context(|| format!("unable to create directory {}", parent.display()))
+}
+
+/// Quantizes the spherical harmonics of a single gaussian.
+///
+/// Degree 1 coefficients keep 5 bits of precision, higher degrees keep 4.
+#[inline]
+fn pack_spherical_harmonics_point(dst: &mut [u8], src: &[f32], axis_flips: &AxisFlips) {
+	const SH1_BITS: i32 = 5;
+	const SH_REST_BITS: i32 = 4;
+
+	for (k, (dst, src)) in dst.chunks_exact_mut(3).zip(src.chunks_exact(3)).enumerate() {
+		let step = if k < 3 {
+			1_i32 << (8 - SH1_BITS)
+		} else {
+			1_i32 << (8 - SH_REST_BITS)
+		};
+		let flip = axis_flips.spherical_harmonics[k];
+
+		for (d, s) in dst.iter_mut().zip(src) {
+			*d = math::quantize_sh(flip * s, step);
+		}
+	}
+}
+
+#[cfg(any(not(feature = "rayon"), test))]
+fn pack_spherical_harmonics(
+	dst: &mut [u8],
+	src: &[f32],
+	sh_per_point: usize,
+	axis_flips: &AxisFlips,
+) {
+	for (dst, src) in dst
+		.chunks_exact_mut(sh_per_point)
+		.zip(src.chunks_exact(sh_per_point))
+	{
+		pack_spherical_harmonics_point(dst, src, axis_flips);
+	}
+}
+
+#[cfg(feature = "rayon")]
+fn pack_spherical_harmonics_par(
+	dst: &mut [u8],
+	src: &[f32],
+	sh_per_point: usize,
+	axis_flips: &AxisFlips,
+) {
+	use rayon::prelude::*;
+
+	dst.par_chunks_mut(sh_per_point)
+		.zip(src.par_chunks(sh_per_point))
+		.for_each(|(dst, src)| pack_spherical_harmonics_point(dst, src, axis_flips));
+}
+
+impl std::fmt::Debug for GaussianSplat {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		/// Number of leading elements shown per array by `{:#?}`.
+		const PREVIEW_LEN: usize = 6;
+
+		struct Truncated<'a>(&'a [f32]);
+
+		impl std::fmt::Debug for Truncated<'_> {
+			fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+				if !f.alternate() {
+					return write!(f, "[f32; {}]", self.0.len());
+				}
+				write!(f, "[")?;
+
+				for (i, v) in self.0.iter().take(PREVIEW_LEN).enumerate() {
+					if i > 0 {
+						write!(f, ", ")?;
+					}
+					write!(f, "{v:?}")?;
+				}
+				if self.0.len() > PREVIEW_LEN {
+					write!(f, ", ... ({} total)", self.0.len())?;
+				}
+				write!(f, "]")
+			}
+		}
+		let mut d = f.debug_struct("GaussianSplat");
+
+		d.field("header", &self.header);
+
+		if self.positions.len() >= 3 {
+			d.field("bbox", &self.bbox());
+		}
+		d.field("positions", &Truncated(&self.positions))
+			.field("scales", &Truncated(&self.scales))
+			.field("rotations", &Truncated(&self.rotations))
+			.field("alphas", &Truncated(&self.alphas))
+			.field("colors", &Truncated(&self.colors))
+			.field("spherical_harmonics", &Truncated(&self.spherical_harmonics))
+			.finish()
+	}
+}
+
+impl std::fmt::Display for GaussianSplat {
+	#[inline]
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let _ = write!(
+			f,
+			"GaussianSplat={{{}, {}, ",
+			self.header,
+			self.median_volume()
+		);
+		let BoundingBox {
+			min_x,
+			max_x,
+			min_y,
+			max_y,
+			min_z,
+			max_z,
+		} = self.bbox();
+
+		write!(
+			f,
+			"bbox=[x={:.6} to {:.6}, y={:.6} to {:.6}, z={:.6} to {:.6}]}}",
+			min_x, max_x, min_y, max_y, min_z, max_z
+		)?;
+		Ok(())
+	}
+}
+
+#[derive(Clone, Debug, Arbitrary)]
+pub struct GaussianSplatBuilder {
+	coord_sys: CoordinateSystem,
+	packed: bool,
+	antialiased: Option<bool>,
+	version: Option<Version>,
+}
+
+impl GaussianSplatBuilder {
+	#[inline]
+	pub fn packed(mut self, packed: bool) -> Result<Self> {
+		if unlikely(!packed) {
+			bail!("only packed format loading is supported currently");
+		}
+		self.packed = packed;
+
+		Ok(self)
+	}
+
+	#[inline]
+	pub fn coord_sys(mut self, coord_sys: CoordinateSystem) -> Self {
+		self.coord_sys = coord_sys;
+		self
+	}
+
+	/// Sets the antialiasing flag of the loaded splat's header, which is
+	/// written back when it's saved. Keeps the flag of the file by default.
+	#[inline]
+	pub fn antialiased(mut self, antialiased: bool) -> Self {
+		self.antialiased = Some(antialiased);
+		self
+	}
+
+	/// Sets the version of the loaded splat's header, which is written back
+	/// when it's saved with [`SaveOptions::from_header`]. Keeps the version
+	/// of the file by default.
+	#[inline]
+	pub fn version(mut self, version: Version) -> Self {
+		self.version = Some(version);
+		self
+	}
+
+	#[inline]
+	pub fn load<P>(self, filepath: P) -> Result<GaussianSplat>
+	where
+		P: AsRef<Path>,
+	{
+		let gs = GaussianSplat::load_with(
+			filepath,
+			&LoadOptions::builder().coord_sys(self.coord_sys).build(),
+		)?;
+
+		Ok(self.apply(gs))
+	}
+
+	#[inline]
+	pub async fn load_async<P>(self, filepath: P) -> Result<GaussianSplat>
+	where
+		P: AsRef<Path>,
+	{
+		let gs = GaussianSplat::load_with_async(
+			filepath,
+			&LoadOptions::builder().coord_sys(self.coord_sys).build(),
+		)
+		.await?;
+
+		Ok(self.apply(gs))
+	}
+
+	/// Loads from compressed, packed gaussian data in memory, see
+	/// [`PackedGaussianSplat::from_compressed_bytes`].
+	#[inline]
+	pub fn load_from_bytes(self, bytes: &[u8]) -> Result<GaussianSplat> {
+		let opts = LoadOptions::builder().coord_sys(self.coord_sys).build();
+		let packed = PackedGaussianSplat::from_compressed_bytes(bytes, opts.compression)
+			.with_context(|| "unable to parse splat")?;
+
+		let gs = GaussianSplat::new_from_packed_gaussians(&packed, &opts)?;
+
+		Ok(self.apply(gs))
+	}
+
+	/// Loads from a reader of compressed, packed gaussian data, see
+	/// [`GaussianSplat::read_from`].
+	#[inline]
+	pub fn load_from_reader<R>(self, reader: R) -> Result<GaussianSplat>
+	where
+		R: Read,
+	{
+		let gs = GaussianSplat::read_from(
+			reader,
+			&LoadOptions::builder().coord_sys(self.coord_sys).build(),
+		)?;
+
+		Ok(self.apply(gs))
+	}
+
+	/// Applies the header overrides to a loaded splat.
+	fn apply(&self, mut gs: GaussianSplat) -> GaussianSplat {
+		if let Some(antialiased) = self.antialiased {
+			gs.header.flags.set(Flags::ANTIALIASED, antialiased);
+		}
+		if let Some(version) = self.version {
+			gs.header.version = version;
+		}
+		gs
+	}
+}
+
+impl Default for GaussianSplatBuilder {
+	#[inline]
+	fn default() -> Self {
+		GaussianSplatBuilder {
+			coord_sys: CoordinateSystem::Unspecified,
+			packed: true,
+			antialiased: None,
+			version: None,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Arbitrary)]
+pub struct LoadOptionsBuilder {
+	coord_sys: CoordinateSystem,
+	compression: Option<Compression>,
+	madvise: bool,
+	max_points: usize,
+	strict_coord_sys: bool,
+	strict_rotations: bool,
+	allow_uncompressed: bool,
+}
+
+impl LoadOptionsBuilder {
+	#[inline]
+	pub fn coord_sys(mut self, coord_sys: CoordinateSystem) -> Self {
+		self.coord_sys = coord_sys;
+		self
+	}
+
+	/// Forces the container compression instead of detecting it.
+	#[inline]
+	pub fn compression(mut self, compression: Compression) -> Self {
+		self.compression = Some(compression);
+		self
+	}
+
+	/// Sets whether memory-mapped files get a sequential read hint.
+	#[inline]
+	pub fn madvise(mut self, madvise: bool) -> Self {
+		self.madvise = madvise;
+		self
+	}
+
+	/// Sets the maximum number of points a loaded splat may have.
+	#[inline]
+	pub fn max_points(mut self, max_points: usize) -> Self {
+		self.max_points = max_points;
+		self
+	}
+
+	/// Sets whether loading with an unspecified coordinate system fails.
+	#[inline]
+	pub fn strict_coord_sys(mut self, strict_coord_sys: bool) -> Self {
+		self.strict_coord_sys = strict_coord_sys;
+		self
+	}
+
+	/// Sets whether corrupt rotations fail loading.
+	#[inline]
+	pub fn strict_rotations(mut self, strict_rotations: bool) -> Self {
+		self.strict_rotations = strict_rotations;
+		self
+	}
+
+	/// Sets whether packed data without compression is accepted.
+	#[inline]
+	pub fn allow_uncompressed(mut self, allow_uncompressed: bool) -> Self {
+		self.allow_uncompressed = allow_uncompressed;
+		self
+	}
+
+	#[inline]
+	pub fn build(self) -> LoadOptions {
+		LoadOptions {
+			coord_sys: self.coord_sys,
+			compression: self.compression,
+			madvise: self.madvise,
+			max_points: self.max_points,
+			strict_coord_sys: self.strict_coord_sys,
+			strict_rotations: self.strict_rotations,
+			allow_uncompressed: self.allow_uncompressed,
+		}
+	}
+}
+
+impl Default for LoadOptionsBuilder {
+	#[inline]
+	fn default() -> Self {
+		Self {
+			coord_sys: CoordinateSystem::Unspecified,
+			compression: None,
+			madvise: true,
+			max_points: consts::MAX_POINTS_TO_READ,
+			strict_coord_sys: false,
+			strict_rotations: false,
+			allow_uncompressed: false,
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Arbitrary)]
+pub struct LoadOptions {
+	/// Specifies the coordinate system to convert to when loading from
+	/// the one the data is stored in the SPZ file.
+	///
+	/// `Unspecified` (the default) skips the conversion, so the data comes
+	/// out in SPZ's internal RUB system. A renderer using another system,
+	/// e.g. RDF, silently gets the wrong orientation, see
+	/// [`strict_coord_sys`](Self::strict_coord_sys).
+	///
+	/// For more information see [`CoordinateSystem`](crate::coord::CoordinateSystem).
+	pub coord_sys: CoordinateSystem,
+
+	/// Container compression of the data. If `None` (default), it is
+	/// detected from the magic bytes.
+	pub compression: Option<Compression>,
+
+	/// Hint the kernel that memory-mapped files are read sequentially, see
+	/// [`mmap`](crate::mmap::mmap). `true` by default. Only affects loading
+	/// from a path.
+	#[serde(default = "default_madvise")]
+	pub madvise: bool,
+
+	/// Maximum number of points a loaded splat may have,
+	/// [`MAX_POINTS_TO_READ`](consts::MAX_POINTS_TO_READ) by default. Guards
+	/// against huge allocations for untrusted files.
+	#[serde(default = "default_max_points")]
+	pub max_points: usize,
+
+	/// Fail with [`SpzError::UnspecifiedCoordinateSystem`] instead of
+	/// skipping the conversion when `coord_sys` is `Unspecified`. `false` by
+	/// default.
+	#[serde(default)]
+	pub strict_coord_sys: bool,
+
+	/// Fail with [`SpzError::CorruptRotation`] if the stored components of a
+	/// _smallest three_ encoded rotation can't be part of a unit quaternion,
+	/// instead of silently loading a non-unit quaternion. Such rotations only
+	/// come from corrupt files. `false` by default.
+	#[serde(default)]
+	pub strict_rotations: bool,
+
+	/// Accept the packed data without any compression, as some tools write
+	/// it, if it starts with neither gzip nor zstd magic bytes. Only applies
+	/// if `compression` is `None`. `false` by default, SPZ files are gzip
+	/// compressed.
+	#[serde(default)]
+	pub allow_uncompressed: bool,
+}
+
+impl Default for LoadOptions {
+	#[inline]
+	fn default() -> Self {
+		LoadOptionsBuilder::default().build()
+	}
+}
+
+#[inline]
+fn default_madvise() -> bool {
+	true
+}
+
+#[inline]
+fn default_max_points() -> usize {
+	consts::MAX_POINTS_TO_READ
+}
+
+#[inline]
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+	a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+#[inline]
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+	[
+		a[1] * b[2] - a[2] * b[1],
+		a[2] * b[0] - a[0] * b[2],
+		a[0] * b[1] - a[1] * b[0],
+	]
+}
+
+/// Returns `v` scaled to unit length, `None` if it is zero or not finite.
+#[inline]
+fn normalized(v: [f32; 3]) -> Option<[f32; 3]> {
+	let len = dot(v, v).sqrt();
+
+	if len > 0.0 && len.is_finite() {
+		Some(v.map(|c| c / len))
+	} else {
+		None
+	}
+}
+
+/// Reads and parses the packed gaussian data file at `filepath`, see
+/// [`packed_from_bytes`].
+fn read_packed<F>(
+	filepath: F,
+	opts: &LoadOptions,
+	scratch: &mut Vec<u8>,
+) -> Result<PackedGaussianSplat, SpzError>
+where
+	F: AsRef<Path>,
+{
+	// mmap on macos isn't great according to ripgrep code
+	if cfg!(target_os = "macos") {
+		let infile = std::fs::read(filepath)?;
+
+		return packed_from_bytes(&infile, opts, scratch);
+	}
+	let mmap = mmap::mmap(filepath, opts.madvise)?;
+
+	packed_from_bytes(mmap.as_ref(), opts, scratch)
+}
+
+/// Parses packed gaussian data, decompressing it into `scratch` unless
+/// [`LoadOptions::allow_uncompressed`] is set and `bytes` are not compressed.
+fn packed_from_bytes(
+	bytes: &[u8],
+	opts: &LoadOptions,
+	scratch: &mut Vec<u8>,
+) -> Result<PackedGaussianSplat, SpzError> {
+	if opts.allow_uncompressed
+		&& opts.compression.is_none()
+		&& Compression::detect(bytes).is_none()
+	{
+		return PackedGaussianSplat::try_from(bytes);
+	}
+	PackedGaussianSplat::from_compressed_bytes_with_buf(bytes, opts.compression, scratch)
+}
+
+/// Fails if `coord_sys` is `Unspecified` and `strict` is set.
+#[inline]
+pub(crate) fn check_coord_sys(coord_sys: CoordinateSystem, strict: bool) -> Result<(), SpzError> {
+	if unlikely(strict && coord_sys == CoordinateSystem::Unspecified) {
+		return Err(SpzError::UnspecifiedCoordinateSystem);
+	}
+	Ok(())
+}
+
+impl LoadOptions {
+	#[inline]
+	pub fn builder() -> LoadOptionsBuilder {
+		LoadOptionsBuilder::default()
+	}
+}
+
+/// Options for saving the [`GaussianSplat`](crate::gaussian_splat::GaussianSplat) data.
+///
+/// Specifies the source coordinate system so axis flips can be applied during
+/// compression to convert to the SPZ internal format (RightUpBack|RUB).
+///
+/// For more information see [`CoordinateSystem`](crate::coord::CoordinateSystem).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Arbitrary)]
+pub struct SaveOptions {
+	/// Specifies the coordinate system to convert to when saving the
+	/// Gaussian Splat data into the SPZ file.
+	///
+	/// `Unspecified` (the default) skips the conversion, the data is stored
+	/// as if it were already RUB. Data authored in another system, e.g. RDF,
+	/// silently ends up with the wrong orientation in other readers, see
+	/// [`strict_coord_sys`](Self::strict_coord_sys).
+	pub coord_sys: CoordinateSystem,
+
+	/// Container compression to use, gzip by default as mandated by the
+	/// SPZ format.
+	pub compression: Compression,
+
+	/// SPZ version to write, `v3` by default. `v2` stores rotations with the
+	/// less precise _first three_ quaternion encoding. `v1` is unsupported.
+	pub version: Version,
+
+	/// Number of fractional bits of the 24-bit fixed point positions, at most
+	/// [`MAX_FRACTIONAL_BITS`](consts::MAX_FRACTIONAL_BITS). More bits give
+	/// more precision but a smaller range.
+	pub fractional_bits: u8,
+
+	/// Write PLY as ASCII instead of binary little-endian, which is larger
+	/// but readable and diffable, e.g. for golden files. Only affects PLY.
+	#[serde(default)]
+	pub ply_ascii: bool,
+
+	/// Fail with [`SpzError::UnspecifiedCoordinateSystem`] instead of
+	/// skipping the conversion when `coord_sys` is `Unspecified`. `false` by
+	/// default.
+	#[serde(default)]
+	pub strict_coord_sys: bool,
+}
+
+impl Default for SaveOptions {
+	#[inline]
+	fn default() -> Self {
+		Self {
+			coord_sys: CoordinateSystem::default(),
+			compression: Compression::default(),
+			version: Version::default(),
+			fractional_bits: consts::DEFAULT_FRACTIONAL_BITS,
+			ply_ascii: false,
+			strict_coord_sys: false,
+		}
+	}
+}
+
+impl SaveOptions {
+	/// Creates a new [`SaveOptionsBuilder`].
+	#[inline]
+	pub fn builder() -> SaveOptionsBuilder {
+		SaveOptionsBuilder::default()
+	}
+
+	/// Returns the default options, but writing the version and fractional
+	/// bits of `header`, e.g. to save a splat the way it was loaded or
+	/// overridden with [`GaussianSplatBuilder::version`].
+	#[inline]
+	pub fn from_header(header: &Header) -> Self {
+		Self {
+			version: header.version,
+			fractional_bits: header.fractional_bits,
+			..Default::default()
+		}
+	}
+}
+
+/// Builder for [`SaveOptions`].
+#[derive(Clone, Debug, Arbitrary)]
+pub struct SaveOptionsBuilder {
+	coord_sys: CoordinateSystem,
+	compression: Compression,
+	version: Version,
+	fractional_bits: u8,
+	ply_ascii: bool,
+	strict_coord_sys: bool,
+}
+
+impl SaveOptionsBuilder {
+	/// Sets the source coordinate system.
+	#[inline]
+	pub fn coord_sys(mut self, coord_sys: CoordinateSystem) -> Self {
+		self.coord_sys = coord_sys;
+		self
+	}
+
+	/// Sets the container compression.
+	#[inline]
+	pub fn compression(mut self, compression: Compression) -> Self {
+		self.compression = compression;
+		self
+	}
+
+	/// Sets the SPZ version to write.
+	#[inline]
+	pub fn version(mut self, version: Version) -> Self {
+		self.version = version;
+		self
+	}
+
+	/// Sets the number of fractional bits of the fixed point positions.
+	#[inline]
+	pub fn fractional_bits(mut self, fractional_bits: u8) -> Self {
+		self.fractional_bits = fractional_bits;
+		self
+	}
+
+	/// Sets whether PLY is written as ASCII.
+	#[inline]
+	pub fn ply_ascii(mut self, ply_ascii: bool) -> Self {
+		self.ply_ascii = ply_ascii;
+		self
+	}
+
+	/// Sets whether saving with an unspecified coordinate system fails.
+	#[inline]
+	pub fn strict_coord_sys(mut self, strict_coord_sys: bool) -> Self {
+		self.strict_coord_sys = strict_coord_sys;
+		self
+	}
+
+	/// Builds the [`SaveOptions`].
+	#[inline]
+	pub fn build(self) -> SaveOptions {
+		SaveOptions {
+			coord_sys: self.coord_sys,
+			compression: self.compression,
+			version: self.version,
+			fractional_bits: self.fractional_bits,
+			ply_ascii: self.ply_ascii,
+			strict_coord_sys: self.strict_coord_sys,
+		}
+	}
+}
+
+impl Default for SaveOptionsBuilder {
+	#[inline]
+	fn default() -> Self {
 		Self {
 			coord_sys: CoordinateSystem::Unspecified,
+			compression: Compression::Gzip,
+			version: Version::V3,
+			fractional_bits: consts::DEFAULT_FRACTIONAL_BITS,
+			ply_ascii: false,
+			strict_coord_sys: false,
+		}
+	}
+}
+
+/// Byte sizes of the attribute arrays of a [`GaussianSplat`], see
+/// [`GaussianSplat::memory_footprint`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryReport {
+	pub positions: usize,
+	pub scales: usize,
+	pub rotations: usize,
+	pub alphas: usize,
+	pub colors: usize,
+	pub spherical_harmonics: usize,
+	/// Sum of all of the above.
+	pub total: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct BoundingBox {
+	pub min_x: f32,
+	pub max_x: f32,
+	pub min_y: f32,
+	pub max_y: f32,
+	pub min_z: f32,
+	pub max_z: f32,
+}
+
+impl BoundingBox {
+	#[inline]
+	pub fn size(&self) -> (f32, f32, f32) {
+		(
+			self.max_x - self.min_x,
+			self.max_y - self.min_y,
+			self.max_z - self.min_z,
+		)
+	}
+
+	/// Returns `true` if the point lies inside the box, bounds inclusive.
+	#[inline]
+	pub fn contains(&self, x: f32, y: f32, z: f32) -> bool {
+		(self.min_x..=self.max_x).contains(&x)
+			&& (self.min_y..=self.max_y).contains(&y)
+			&& (self.min_z..=self.max_z).contains(&z)
+	}
+
+	/// Get the center of the bounding box.
+	///
+	/// # Returns
+	///
+	/// A tuple of (x, y, z) center coordinates.
+	#[inline]
+	pub fn center(&self) -> (f32, f32, f32) {
+		(
+			(self.min_x + self.max_x) / 2.0,
+			(self.min_y + self.max_y) / 2.0,
+			(self.min_z + self.max_z) / 2.0,
+		)
+	}
+}
+
+/// A single gaussian of a [`GaussianSplat`], borrowed from its attribute
+/// arrays, see [`GaussianSplat::gaussian`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GaussianView<'a> {
+	/// `(x, y, z)` position.
+	pub position: &'a [f32; 3],
+	/// `(x, y, z)` log-scale.
+	pub scale: &'a [f32; 3],
+	/// `(x, y, z, w)` rotation quaternion.
+	pub rotation: &'a [f32; 4],
+	/// Opacity logit.
+	pub alpha: f32,
+	/// `(r, g, b)` DC color.
+	pub color: &'a [f32; 3],
+	/// Spherical harmonics coefficients, `sh_dim * 3` values with
+	/// interleaved channels.
+	pub spherical_harmonics: &'a [f32],
+}
+
+/// Values clamped while quantizing a splat, see
+/// [`GaussianSplat::to_packed_gaussians_with_report`]. Non-finite values
+/// are counted too.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackReport {
+	/// Position coordinates outside the range of the 24-bit fixed point
+	/// encoding for the used fractional bits.
+	pub position_overflows: usize,
+	/// Color components outside the `u8` range, see
+	/// [`GaussianSplat::colors_clip_report`].
+	pub color_clips: usize,
+	/// Spherical harmonics coefficients outside about `[-1, 1]`.
+	pub sh_clips: usize,
+}
+
+impl PackReport {
+	/// Returns `true` if nothing was clamped.
+	#[inline]
+	pub fn is_clean(&self) -> bool {
+		self.position_overflows == 0 && self.color_clips == 0 && self.sh_clips == 0
+	}
+}
+
+/// Options for [`GaussianSplat::merge_with`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct MergeOptions {
+	/// If set, gaussians closer than this distance to an earlier one are
+	/// dropped after merging, see [`GaussianSplat::merge_nearby`].
+	pub dedup_radius: Option<f32>,
+}
+
+/// Number of non-finite values per attribute array, see
+/// [`GaussianSplat::find_non_finite`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NonFiniteReport {
+	pub positions: usize,
+	pub scales: usize,
+	pub rotations: usize,
+	pub alphas: usize,
+	pub colors: usize,
+	pub spherical_harmonics: usize,
+}
+
+impl NonFiniteReport {
+	/// Total number of non-finite values across all arrays.
+	#[inline]
+	pub fn total(&self) -> usize {
+		self.positions
+			+ self.scales + self.rotations
+			+ self.alphas + self.colors
+			+ self.spherical_harmonics
+	}
+
+	/// Returns `true` if there are no non-finite values at all.
+	#[inline]
+	pub fn is_clean(&self) -> bool {
+		self.total() == 0
+	}
+}
+
+/// Distribution statistics of a [`GaussianSplat`], see
+/// [`GaussianSplat::stats`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SplatStats {
+	/// Position extents per axis.
+	pub bbox: BoundingBox,
+	/// 10th percentile of the per-gaussian log-scale sum.
+	pub scale_sum_p10: f32,
+	/// Median of the per-gaussian log-scale sum.
+	pub scale_sum_p50: f32,
+	/// 90th percentile of the per-gaussian log-scale sum.
+	pub scale_sum_p90: f32,
+	/// Counts of opacities (after the sigmoid activation) in
+	/// [`Self::OPACITY_BINS`] equal-width bins over `[0, 1]`.
+	pub opacity_histogram: [usize; Self::OPACITY_BINS],
+	/// Number of `NaN` or infinite values across all attributes.
+	pub non_finite: usize,
+}
+
+impl SplatStats {
+	pub const OPACITY_BINS: usize = 10;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use approx::assert_relative_eq;
+	use rstest::rstest;
+
+	#[rstest]
+	#[case(
+		GaussianSplat::default(),
+		vec![
+			-1.0, -1.0, -1.0, // First gaussian: scale sum = -3
+			0.0, 0.0, 0.0, // Second gaussian: scale sum = 0
+			1.0, 1.0, 1.0, // Third gaussian: scale sum = 3
+		],
+		(4.0 / 3.0) * std::f32::consts::PI * 0.0_f32.exp(),
+		1e-5_f32,
+	)]
+	#[case(
+		GaussianSplat::default(),
+		vec![
+			-2.0, -2.0, -2.0, // First gaussian: scale sum = -6
+			-1.0, -1.0, -1.0, // Second gaussian: scale sum = -3
+			0.0, 0.0, 0.0, // Third gaussian: scale sum = 0 (median)
+			1.0, 1.0, 1.0, // Fourth gaussian: scale sum = 3
+			2.0, 2.0, 2.0, // Fifth gaussian: scale sum = 6
+		],
+		(4.0 / 3.0) * std::f32::consts::PI * 0.0_f32.exp(),
+		1e-5_f32,
+	)]
+	fn test_median_volume(
+		#[case] mut gs: GaussianSplat,
+		#[case] scales: Vec<f32>,
+		#[case] expected_vol: f32,
+		#[case] epsilon: f32,
+	) {
+		gs.scales = scales;
+
+		assert_relative_eq!(gs.median_volume(), expected_vol, epsilon = epsilon);
+	}
+
+	#[rstest]
+	#[case(vec![])]
+	#[case(vec![f32::NAN, f32::NAN, f32::NAN])]
+	#[case(vec![f32::INFINITY, f32::INFINITY, f32::INFINITY])]
+	fn test_median_volume_fallback(#[case] scales: Vec<f32>) {
+		let gs = GaussianSplat {
+			scales,
+			..GaussianSplat::default()
+		};
+
+		assert_relative_eq!(gs.median_volume(), 0.01, epsilon = 1e-6);
+	}
+
+	#[test]
+	fn test_check_sizes_default_is_valid() {
+		let gs = GaussianSplat::default();
+
+		assert!(gs.check_sizes());
+	}
+
+	#[test]
+	fn test_check_sizes_negative_num_points() {
+		let mut gs = GaussianSplat::default();
+
+		gs.header.num_points = -1;
+
+		assert!(!gs.check_sizes());
+	}
+
+	#[test]
+	fn test_check_sizes_invalid_sh_degree() {
+		let mut gs = GaussianSplat::default();
+
+		gs.header.spherical_harmonics_degree = 4;
+
+		assert!(!gs.check_sizes());
+	}
+
+	#[test]
+	fn test_check_sizes_wrong_positions_len() {
+		let gs = GaussianSplat {
+			header: Header {
+				num_points: 1,
+				..Default::default()
+			},
+			positions: vec![0.0; 2],
+			scales: vec![0.0; 3],
+			rotations: vec![0.0; 4],
+			alphas: vec![0.0; 1],
+			colors: vec![0.0; 3],
+			spherical_harmonics: vec![],
+		};
+		assert!(!gs.check_sizes());
+	}
+
+	#[test]
+	fn test_check_sizes_correct_for_one_point_sh1() {
+		let gs = GaussianSplat {
+			header: Header {
+				num_points: 1,
+				spherical_harmonics_degree: 1,
+				..Default::default()
+			},
+			positions: vec![0.0; 3],
+			scales: vec![0.0; 3],
+			rotations: vec![0.0; 4],
+			alphas: vec![0.0; 1],
+			colors: vec![0.0; 3],
+			spherical_harmonics: vec![0.0; 9],
+		};
+		assert!(gs.check_sizes());
+	}
+
+	#[rstest]
+	#[case((-1.0, 1.0, 0.0, 5.0, -3.0, 3.0), (2.0, 5.0, 6.0), (0.0, 2.5, 0.0))]
+	#[case((-2.0, 2.0, -1.0, 3.0, 0.0, 10.0), (4.0, 4.0, 10.0), (0.0, 1.0, 5.0))]
+	#[case((5.0, 5.0, 5.0, 5.0, 5.0, 5.0), (0.0, 0.0, 0.0), (5.0, 5.0, 5.0))]
+	fn test_bounding_box(
+		#[case] bounds: (f32, f32, f32, f32, f32, f32),
+		#[case] expected_size: (f32, f32, f32),
+		#[case] expected_center: (f32, f32, f32),
+	) {
+		let bbox = BoundingBox {
+			min_x: bounds.0,
+			max_x: bounds.1,
+			min_y: bounds.2,
+			max_y: bounds.3,
+			min_z: bounds.4,
+			max_z: bounds.5,
+		};
+		assert_eq!(bbox.size(), expected_size);
+		assert_eq!(bbox.center(), expected_center);
+	}
+
+	#[test]
+	fn test_builder_default() {
+		let builder = GaussianSplatBuilder::default();
+
+		assert_eq!(builder.coord_sys, CoordinateSystem::Unspecified);
+	}
+
+	#[rstest]
+	#[case(false, true)]
+	#[case(true, false)]
+	fn test_builder_packed(#[case] packed: bool, #[case] expect_err: bool) {
+		let builder = GaussianSplatBuilder::default();
+
+		assert_eq!(builder.packed(packed).is_err(), expect_err);
+	}
+
+	#[test]
+	fn test_builder_coord_sys() {
+		let builder = GaussianSplat::builder().coord_sys(CoordinateSystem::RightDownFront);
+
+		assert_eq!(builder.coord_sys, CoordinateSystem::RightDownFront);
+	}
+
+	#[test]
+	fn test_builder_antialiased_version() {
+		let bytes = make_grid_splat()
+			.serialize_to_packed_bytes(&SaveOptions::default())
+			.unwrap();
+		let gs = GaussianSplat::builder()
+			.antialiased(true)
+			.version(Version::V2)
+			.load_from_bytes(&bytes)
+			.unwrap();
+
+		assert!(gs.header.flags.is_antialiased());
+		assert_eq!(gs.header.version, Version::V2);
+
+		let saved = gs
+			.serialize_to_packed_bytes(&SaveOptions::from_header(&gs.header))
+			.unwrap();
+		let reloaded = GaussianSplat::builder().load_from_bytes(&saved).unwrap();
+
+		assert!(reloaded.header.flags.is_antialiased());
+		assert_eq!(reloaded.header.version, Version::V2);
+
+		let cleared = GaussianSplat::builder()
+			.antialiased(false)
+			.load_from_bytes(&saved)
+			.unwrap();
+
+		assert!(!cleared.header.flags.is_antialiased());
+	}
+
+	#[test]
+	fn test_load_options_builder() {
+		let opts = LoadOptions::builder()
+			.coord_sys(CoordinateSystem::LeftUpFront)
+			.build();
+
+		assert_eq!(opts.coord_sys, CoordinateSystem::LeftUpFront);
+	}
+
+	#[test]
+	fn test_save_options_builder() {
+		let opts = SaveOptions::builder()
+			.coord_sys(CoordinateSystem::RightUpFront)
+			.build();
+
+		assert_eq!(opts.coord_sys, CoordinateSystem::RightUpFront);
+	}
+
+	#[test]
+	fn test_convert_coordinates_zero_points_noop() {
+		let mut gs = GaussianSplat::default();
+
+		gs.convert_coordinates(
+			CoordinateSystem::RightUpBack,
+			CoordinateSystem::RightDownFront,
+		);
+		assert!(gs.positions.is_empty());
+	}
+
+	#[test]
+	fn test_convert_coordinates_unspecified_noop() {
+		let mut gs = GaussianSplat {
+			header: Header {
+				num_points: 1,
+				..Default::default()
+			},
+			positions: vec![1.0, 2.0, 3.0],
+			scales: vec![0.0; 3],
+			rotations: vec![0.0, 0.0, 0.0, 1.0],
+			alphas: vec![0.5],
+			colors: vec![0.0; 3],
+			spherical_harmonics: vec![],
+		};
+		let original_pos = gs.positions.clone();
+
+		gs.convert_coordinates(
+			CoordinateSystem::Unspecified,
+			CoordinateSystem::RightDownFront,
+		);
+		assert_eq!(gs.positions, original_pos);
+	}
+
+	#[test]
+	fn test_apply_transform_identity_noop() {
+		let mut gs = GaussianSplat {
+			header: Header {
+				num_points: 2,
+				..Default::default()
+			},
+			positions: vec![1.0, 2.0, 3.0, -4.0, 5.0, -6.0],
+			scales: vec![-1.0, -2.0, -3.0, 0.5, 0.25, 0.0],
+			rotations: vec![0.0, 0.0, 0.0, 1.0, 0.5, 0.5, 0.5, 0.5],
+			alphas: vec![0.5, -0.5],
+			colors: vec![0.0; 6],
+			spherical_harmonics: vec![],
+		};
+		let original = gs.clone();
+
+		gs.apply_transform([0.0; 3], [0.0, 0.0, 0.0, 1.0], 1.0)
+			.unwrap();
+
+		for (a, b) in gs.positions.iter().zip(original.positions.iter()) {
+			assert_relative_eq!(a, b, epsilon = 1e-6);
+		}
+		for (a, b) in gs.scales.iter().zip(original.scales.iter()) {
+			assert_relative_eq!(a, b, epsilon = 1e-6);
+		}
+		for (a, b) in gs.rotations.iter().zip(original.rotations.iter()) {
+			assert_relative_eq!(a, b, epsilon = 1e-6);
+		}
+	}
+
+	#[rstest]
+	#[case([1.0, 2.0, 3.0], [0.0, 0.0, 0.0, 1.0], 1.0, [2.0, 4.0, 6.0])]
+	#[case([0.0, 0.0, 0.0], [0.0, 0.0, 0.0, 1.0], 2.0, [2.0, 4.0, 6.0])]
+	#[case(
+		[1.0, 0.0, 0.0],
+		[0.0, 0.0, std::f32::consts::FRAC_1_SQRT_2, std::f32::consts::FRAC_1_SQRT_2],
+		1.0,
+		[-1.0, 1.0, 3.0]
+	)]
+	fn test_apply_transform_single_point(
+		#[case] translation: [f32; 3],
+		#[case] rotation: [f32; 4],
+		#[case] scale: f32,
+		#[case] expected: [f32; 3],
+	) {
+		let mut gs = GaussianSplat {
+			header: Header {
+				num_points: 1,
+				..Default::default()
+			},
+			positions: vec![1.0, 2.0, 3.0],
+			scales: vec![0.0; 3],
+			rotations: vec![0.0, 0.0, 0.0, 1.0],
+			alphas: vec![0.0],
+			colors: vec![0.0; 3],
+			spherical_harmonics: vec![],
+		};
+		gs.apply_transform(translation, rotation, scale).unwrap();
+
+		for i in 0..3 {
+			assert_relative_eq!(gs.positions[i], expected[i], epsilon = 1e-5);
+			assert_relative_eq!(gs.scales[i], scale.ln(), epsilon = 1e-6);
+		}
+		for (a, b) in gs.rotations.iter().zip(rotation.iter()) {
+			assert_relative_eq!(a, b, epsilon = 1e-6);
+		}
+	}
+
+	#[rstest]
+	#[case(0.0)]
+	#[case(-1.0)]
+	#[case(f32::NAN)]
+	fn test_apply_transform_invalid_scale(#[case] scale: f32) {
+		let mut gs = GaussianSplat::default();
+
+		assert!(gs
+			.apply_transform([0.0; 3], [0.0, 0.0, 0.0, 1.0], scale)
+			.is_err());
+	}
+
+	fn make_single_point_splat(position: [f32; 3]) -> GaussianSplat {
+		GaussianSplat {
+			header: Header {
+				num_points: 1,
+				..Default::default()
+			},
+			positions: position.to_vec(),
+			scales: vec![0.0; 3],
+			rotations: vec![0.0, 0.0, 0.0, 1.0],
+			alphas: vec![0.0],
+			colors: vec![0.0; 3],
+			spherical_harmonics: vec![],
+		}
+	}
+
+	#[test]
+	fn test_merge_single_points() {
+		let mut gs = make_single_point_splat([1.0, 2.0, 3.0]);
+		let other = make_single_point_splat([4.0, 5.0, 6.0]);
+
+		gs.merge(&other).unwrap();
+
+		assert!(gs.check_sizes());
+		assert_eq!(gs.header.num_points, 2);
+		assert_eq!(gs.positions, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+	}
+
+	#[rstest]
+	#[case(false, false, false)]
+	#[case(true, false, false)]
+	#[case(false, true, false)]
+	#[case(true, true, true)]
+	fn test_merge_antialiased_policy(
+		#[case] self_aa: bool,
+		#[case] other_aa: bool,
+		#[case] expected: bool,
+	) {
+		let mut gs = make_single_point_splat([0.0; 3]);
+		let mut other = make_single_point_splat([1.0; 3]);
+
+		gs.header.flags.set(Flags::ANTIALIASED, self_aa);
+		other.header.flags.set(Flags::ANTIALIASED, other_aa);
+
+		gs.merge(&other).unwrap();
+
+		assert_eq!(gs.header.flags.is_antialiased(), expected);
+	}
+
+	#[test]
+	fn test_merge_lengths() {
+		let mut gs = make_grid_splat();
+		let other = make_grid_splat();
+
+		gs.merge(&other).unwrap();
+
+		assert!(gs.check_sizes());
+		assert_eq!(gs.header.num_points, 16);
+		assert_eq!(gs.positions.len(), 2 * other.positions.len());
+		assert_eq!(gs.scales.len(), 2 * other.scales.len());
+		assert_eq!(gs.rotations.len(), 2 * other.rotations.len());
+		assert_eq!(gs.alphas.len(), 2 * other.alphas.len());
+		assert_eq!(gs.colors.len(), 2 * other.colors.len());
+		assert_eq!(
+			gs.spherical_harmonics.len(),
+			2 * other.spherical_harmonics.len()
+		);
+		assert_eq!(gs.alphas[8..], other.alphas[..]);
+	}
+
+	#[test]
+	fn test_merge_sh_degree_mismatch_fails() {
+		let mut gs = make_single_point_splat([0.0; 3]);
+		let other = make_grid_splat();
+
+		assert!(gs.merge(&other).is_err());
+		assert_eq!(gs.header.num_points, 1);
+	}
+
+	#[test]
+	fn test_concat() {
+		let splats = [
+			make_single_point_splat([1.0, 1.0, 1.0]),
+			make_single_point_splat([2.0, 2.0, 2.0]),
+			make_single_point_splat([3.0, 3.0, 3.0]),
+		];
+		let gs = GaussianSplat::concat(&splats).unwrap();
+
+		assert!(gs.check_sizes());
+		assert_eq!(gs.header.num_points, 3);
+		assert_eq!(
+			gs.positions,
+			vec![1.0, 1.0, 1.0, 2.0, 2.0, 2.0, 3.0, 3.0, 3.0]
+		);
+		assert_eq!(
+			GaussianSplat::concat(&[]).unwrap(),
+			GaussianSplat::default()
+		);
+	}
+
+	#[rstest]
+	#[case([0.0, 0.0, 1.0], vec![5.0, 2.0, -3.0])]
+	#[case([0.0, 0.0, -1.0], vec![-3.0, 2.0, 5.0])]
+	fn test_sort_by_depth(#[case] view_dir: [f32; 3], #[case] expected_z: Vec<f32>) {
+		let mut gs = GaussianSplat::concat(&[
+			make_single_point_splat([0.0, 0.0, 2.0]),
+			make_single_point_splat([0.0, 0.0, -3.0]),
+			make_single_point_splat([0.0, 0.0, 5.0]),
+		])
+		.unwrap();
+		gs.alphas = gs.positions.chunks_exact(3).map(|p| p[2]).collect();
+
+		gs.sort_by_depth(view_dir);
+
+		assert!(gs.check_sizes());
+
+		let z = gs
+			.positions
+			.chunks_exact(3)
+			.map(|p| p[2])
+			.collect::<Vec<_>>();
+
+		assert_eq!(z, expected_z);
+		assert_eq!(gs.alphas, expected_z);
+	}
+
+	#[test]
+	fn test_sort_by_depth_keeps_sh_with_gaussian() {
+		let mut gs = make_grid_splat();
+
+		gs.sort_by_depth([1.0, 2.0, 4.0]);
+
+		assert!(gs.check_sizes());
+		// grid index equals depth rank for this view direction
+		for (i, sh) in gs.spherical_harmonics.chunks_exact(9).enumerate() {
+			assert_eq!(sh, [(7 - i) as f32; 9]);
+			assert_eq!(gs.alphas[i], (7 - i) as f32);
+		}
+	}
+
+	#[test]
+	fn test_center_at_origin_reversible() {
+		let mut gs = make_grid_splat();
+
+		gs.translate([10.0, -3.0, 0.5]);
+		let original = gs.positions.clone();
+
+		let offset = gs.center_at_origin();
+
+		assert_relative_eq!(offset[0], -10.0, epsilon = 1e-5);
+		assert_relative_eq!(offset[1], 3.0, epsilon = 1e-5);
+		assert_relative_eq!(offset[2], -0.5, epsilon = 1e-5);
+
+		let (cx, cy, cz) = gs.bbox().center();
+
+		assert_relative_eq!(cx, 0.0, epsilon = 1e-5);
+		assert_relative_eq!(cy, 0.0, epsilon = 1e-5);
+		assert_relative_eq!(cz, 0.0, epsilon = 1e-5);
+
+		gs.translate(offset.map(|o| -o));
+
+		for (a, b) in gs.positions.iter().zip(original.iter()) {
+			assert_relative_eq!(a, b, epsilon = 1e-5);
+		}
+	}
+
+	#[test]
+	fn test_center_at_origin_empty() {
+		let mut gs = GaussianSplat::default();
+
+		assert_eq!(gs.center_at_origin(), [0.0; 3]);
+	}
+
+	#[rstest]
+	#[case(3)]
+	#[case(2)]
+	#[case(1)]
+	#[case(0)]
+	fn test_truncate_sh_degree(#[case] new_degree: u8) {
+		const NUM_POINTS: usize = 4;
+
+		let mut gs = GaussianSplat {
+			header: Header {
+				num_points: NUM_POINTS as i32,
+				spherical_harmonics_degree: 3,
+				..Default::default()
+			},
+			positions: vec![0.0; NUM_POINTS * 3],
+			scales: vec![0.0; NUM_POINTS * 3],
+			rotations: vec![0.0; NUM_POINTS * 4],
+			alphas: vec![0.0; NUM_POINTS],
+			colors: vec![0.0; NUM_POINTS * 3],
+			spherical_harmonics: (0..NUM_POINTS * 45).map(|i| i as f32).collect(),
+		};
+		gs.truncate_sh_degree(new_degree).unwrap();
+
+		let stride = dim_for_degree(new_degree) as usize * 3;
+
+		assert!(gs.check_sizes());
+		assert_eq!(gs.header.spherical_harmonics_degree, new_degree);
+		assert_eq!(gs.spherical_harmonics.len(), NUM_POINTS * stride);
+
+		for (i, coeffs) in gs
+			.spherical_harmonics
+			.chunks_exact(stride.max(1))
+			.enumerate()
+		{
+			let expected = (i * 45..i * 45 + stride)
+				.map(|v| v as f32)
+				.collect::<Vec<_>>();
+
+			assert_eq!(coeffs, expected.as_slice());
+		}
+	}
+
+	#[test]
+	fn test_truncate_sh_degree_raise_fails() {
+		let mut gs = make_single_point_splat([0.0; 3]);
+
+		assert!(gs.truncate_sh_degree(1).is_err());
+		assert_eq!(gs.header.spherical_harmonics_degree, 0);
+	}
+
+	#[rstest]
+	#[case(-1.0)]
+	#[case(f32::NAN)]
+	#[case(f32::INFINITY)]
+	fn test_clamp_opacity_logits_invalid(#[case] max_abs: f32) {
+		let mut gs = make_grid_splat();
+		let alphas = gs.alphas.clone();
+
+		assert!(gs.clamp_opacity_logits(max_abs).is_err());
+		assert_eq!(gs.alphas, alphas);
+	}
+
+	#[rstest]
+	#[case(100.0, 8.0)]
+	#[case(-100.0, -8.0)]
+	#[case(3.0, 3.0)]
+	fn test_clamp_opacity_logits(#[case] logit: f32, #[case] expected: f32) {
+		let mut gs = GaussianSplat {
+			header: Header {
+				num_points: 1,
+				..Default::default()
+			},
+			positions: vec![0.0; 3],
+			scales: vec![0.0; 3],
+			rotations: vec![0.0, 0.0, 0.0, 1.0],
+			alphas: vec![logit],
+			colors: vec![0.0; 3],
+			spherical_harmonics: vec![],
+		};
+		gs.clamp_opacity_logits(8.0).unwrap();
+
+		assert_eq!(gs.alphas[0], expected);
+
+		let opts = SaveOptions::default();
+		let packed = gs.to_packed_gaussians(&opts).unwrap();
+		let once =
+			GaussianSplat::new_from_packed_gaussians(&packed, &LoadOptions::default())
+				.unwrap();
+		let twice = GaussianSplat::new_from_packed_gaussians(
+			&once.to_packed_gaussians(&opts).unwrap(),
+			&LoadOptions::default(),
+		)
+		.unwrap();
+
+		assert!(once.alphas[0].is_finite());
+		assert_eq!(once.alphas, twice.alphas);
+	}
+
+	/// Temporary directory of a test, removed on drop so the cleanup runs
+	/// after the asserts, even failing ones.
+	struct TmpDir(std::path::PathBuf);
+
+	impl TmpDir {
+		fn join(&self, name: &str) -> std::path::PathBuf {
+			self.0.join(name)
+		}
+	}
+
+	impl Drop for TmpDir {
+		fn drop(&mut self) {
+			let _ = std::fs::remove_dir_all(&self.0);
+		}
+	}
+
+	/// Creates an empty temporary directory for the test `name`, unique per
+	/// process.
+	fn tmp_dir(name: &str) -> TmpDir {
+		let dir = std::env::temp_dir()
+			.join(format!("spz_test_{name}_{}", std::process::id()));
+
+		let _ = std::fs::remove_dir_all(&dir);
+		std::fs::create_dir_all(&dir).unwrap();
+
+		TmpDir(dir)
+	}
+
+	/// 2x2x2 grid of degree-1 gaussians at (+-1, +-1, +-1), with every
+	/// attribute of gaussian `i` set to `i` so compaction can be checked.
+	fn make_grid_splat() -> GaussianSplat {
+		let mut gs = GaussianSplat {
+			header: Header {
+				num_points: 8,
+				spherical_harmonics_degree: 1,
+				..Default::default()
+			},
+			..Default::default()
+		};
+		for i in 0..8 {
+			let f = i as f32;
+
+			gs.positions.extend([
+				if i & 1 == 0 { -1.0 } else { 1.0 },
+				if i & 2 == 0 { -1.0 } else { 1.0 },
+				if i & 4 == 0 { -1.0 } else { 1.0 },
+			]);
+			gs.scales.extend([f; 3]);
+			gs.rotations.extend([f; 4]);
+			gs.alphas.push(f);
+			gs.colors.extend([f; 3]);
+			gs.spherical_harmonics.extend([f; 9]);
+		}
+		gs
+	}
+
+	#[test]
+	fn test_crop_to_octant() {
+		let mut gs = make_grid_splat();
+		let bbox = BoundingBox {
+			min_x: 0.0,
+			max_x: 2.0,
+			min_y: 0.0,
+			max_y: 2.0,
+			min_z: 0.0,
+			max_z: 2.0,
+		};
+		gs.crop(&bbox);
+
+		assert!(gs.check_sizes());
+		assert_eq!(gs.header.num_points, 1);
+		assert_eq!(gs.positions, vec![1.0, 1.0, 1.0]);
+		assert_eq!(gs.alphas, vec![7.0]);
+		assert_eq!(gs.rotations, vec![7.0; 4]);
+		assert_eq!(gs.spherical_harmonics, vec![7.0; 9]);
+	}
+
+	#[test]
+	fn test_crop_to_empty() {
+		let gs = make_grid_splat();
+		let bbox = BoundingBox {
+			min_x: 5.0,
+			max_x: 6.0,
+			min_y: 5.0,
+			max_y: 6.0,
+			min_z: 5.0,
+			max_z: 6.0,
+		};
+		let cropped = gs.cropped(&bbox);
+
+		assert!(cropped.check_sizes());
+		assert_eq!(cropped.header.num_points, 0);
+		assert!(cropped.positions.is_empty());
+		assert!(cropped.spherical_harmonics.is_empty());
+		assert_eq!(gs.header.num_points, 8);
+	}
+
+	#[test]
+	fn test_filter_by_opacity() {
+		let opacities = [0.0_f32, 0.1, 0.25, 0.49, 0.5, 0.75, 0.9, 1.0];
+		let mut gs = make_grid_splat();
+
+		gs.alphas = opacities.iter().map(|o| math::inv_sigmoid(*o)).collect();
+		gs.filter_by_opacity(0.5);
+
+		assert!(gs.check_sizes());
+		assert_eq!(gs.header.num_points, 4);
+		// attributes of the surviving gaussians 4..8 were set to their index
+		assert_eq!(
+			gs.colors,
+			[4.0, 4.0, 4.0, 5.0, 5.0, 5.0, 6.0, 6.0, 6.0, 7.0, 7.0, 7.0]
+		);
+
+		for (alpha, expected) in gs.alphas.iter().zip(&opacities[4..]) {
+			assert_relative_eq!(math::sigmoid(*alpha), *expected, epsilon = 1e-5);
+		}
+	}
+
+	#[cfg(feature = "rayon")]
+	#[test]
+	fn test_pack_spherical_harmonics_par_matches_serial() {
+		use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+		const NUM_POINTS: usize = 10_000;
+		const SH_PER_POINT: usize = 45;
+
+		let mut rng = StdRng::seed_from_u64(42);
+		let src = (0..NUM_POINTS * SH_PER_POINT)
+			.map(|_| rng.random_range(-1.0_f32..1.0))
+			.collect::<Vec<_>>();
+		let flips = CoordinateSystem::RightDownFront
+			.axis_flips_to(CoordinateSystem::RightUpBack);
+
+		let mut serial = vec![0_u8; src.len()];
+		let mut parallel = vec![0_u8; src.len()];
+
+		pack_spherical_harmonics(&mut serial, &src, SH_PER_POINT, &flips);
+		pack_spherical_harmonics_par(&mut parallel, &src, SH_PER_POINT, &flips);
+
+		assert_eq!(serial, parallel);
+	}
+
+	#[rstest]
+	#[case(Compression::Gzip)]
+	#[cfg_attr(feature = "zstd", case(Compression::Zstd))]
+	fn test_serialize_roundtrip_compression(#[case] compression: Compression) {
+		let gs = make_grid_splat();
+		let bytes = gs
+			.serialize_to_packed_bytes(
+				&SaveOptions::builder().compression(compression).build(),
+			)
+			.unwrap();
+
+		assert_eq!(Compression::detect(&bytes), Some(compression));
+
+		let detected = GaussianSplat::read_from(bytes.as_slice(), &LoadOptions::default())
+			.unwrap();
+		let forced = GaussianSplat::read_from(
+			bytes.as_slice(),
+			&LoadOptions::builder().compression(compression).build(),
+		)
+		.unwrap();
+
+		assert_eq!(detected, forced);
+		assert_eq!(detected.header.num_points, 8);
+		assert_eq!(detected.positions, gs.positions);
+	}
+
+	#[rstest]
+	#[case(Version::V3, 12)]
+	#[case(Version::V2, 12)]
+	#[case(Version::V3, 16)]
+	#[case(Version::V2, 0)]
+	fn test_serialize_roundtrip_version_fractional_bits(
+		#[case] version: Version,
+		#[case] fractional_bits: u8,
+	) {
+		let mut gs = make_grid_splat();
+		let q = math::normalize_quaternion(&[0.1, -0.2, 0.3, -0.9]);
+
+		for r in gs.rotations.chunks_exact_mut(4) {
+			r.copy_from_slice(&q);
+		}
+		let opts = SaveOptions::builder()
+			.version(version)
+			.fractional_bits(fractional_bits)
+			.build();
+		let packed = gs.to_packed_gaussians(&opts).unwrap();
+
+		assert_eq!(packed.to_header().version, version);
+		assert_eq!(packed.fractional_bits, fractional_bits as i32);
+
+		let bytes = gs.serialize_to_packed_bytes(&opts).unwrap();
+		let loaded = GaussianSplat::read_from(bytes.as_slice(), &LoadOptions::default())
+			.unwrap();
+
+		assert_eq!(loaded.header.fractional_bits, fractional_bits);
+		assert_eq!(loaded.positions, gs.positions);
+
+		for r in loaded.rotations.chunks_exact(4) {
+			let dot: f32 = r.iter().zip(q.iter()).map(|(a, b)| a * b).sum();
+
+			assert_relative_eq!(dot.abs(), 1.0, epsilon = 0.01);
+		}
+	}
+
+	#[rstest]
+	#[case(Version::V1, 12)]
+	#[case(Version::V3, 24)]
+	fn test_to_packed_gaussians_invalid_save_options_fails(
+		#[case] version: Version,
+		#[case] fractional_bits: u8,
+	) {
+		let opts = SaveOptions::builder()
+			.version(version)
+			.fractional_bits(fractional_bits)
+			.build();
+
+		assert!(make_grid_splat().to_packed_gaussians(&opts).is_err());
+	}
+
+	#[test]
+	fn test_to_packed_gaussians_inconsistent_sizes_fails() {
+		let gs = GaussianSplat {
+			header: Header {
+				num_points: 1,
+				..Default::default()
+			},
+			positions: vec![1.0],
+			..Default::default()
+		};
+		assert!(gs.to_packed_gaussians(&SaveOptions::default()).is_err());
+	}
+
+	#[test]
+	fn test_new_from_packed_gaussians_inconsistent_sizes_fails() {
+		let packed = PackedGaussianSplat {
+			num_points: 2,
+			positions: vec![0; 9],
+			..Default::default()
+		};
+		assert!(matches!(
+			GaussianSplat::new_from_packed_gaussians(&packed, &LoadOptions::default()),
+			Err(SpzError::InconsistentSizes)
+		));
+	}
+
+	#[test]
+	fn test_header_matches_data() {
+		let mut gs = make_grid_splat();
+
+		assert!(gs.header_matches_data());
+
+		gs.header.spherical_harmonics_degree = 2;
+
+		assert!(!gs.header_matches_data());
+	}
+
+	#[rstest]
+	#[case(7)]
+	#[case(9)]
+	#[case(-1)]
+	fn test_save_wrong_num_points_fails(#[case] num_points: i32) {
+		let mut gs = make_grid_splat();
+
+		gs.header.num_points = num_points;
+
+		assert!(!gs.header_matches_data());
+
+		let dir = tmp_dir(&format!("save_wrong_num_points_{num_points}"));
+		let err = gs
+			.save(dir.join("grid.spz"), &SaveOptions::default())
+			.unwrap_err();
+
+		assert!(err.to_string().contains("header does not match data"));
+		assert!(matches!(
+			err.downcast_ref::<SpzError>(),
+			Some(SpzError::InconsistentSizes)
+		));
+	}
+
+	#[test]
+	fn test_scale_sum_percentiles_outliers() {
+		const NUM_POINTS: usize = 1000;
+
+		// log-scale sums -3.0 to ~0.0, with the last 5 points huge
+		let mut scales = (0..NUM_POINTS)
+			.flat_map(|i| [-1.0 + i as f32 / NUM_POINTS as f32; 3])
+			.collect::<Vec<_>>();
+
+		for s in scales[(NUM_POINTS - 5) * 3..].iter_mut() {
+			*s = 5.0;
+		}
+		let gs = GaussianSplat {
+			scales,
+			..Default::default()
+		};
+		let p = gs.scale_sum_percentiles(&[0.0, 50.0, 99.0, 100.0]);
+
+		assert_relative_eq!(p[0], -3.0, epsilon = 1e-5);
+		assert_relative_eq!(p[1], -1.5, epsilon = 1e-2);
+		assert!(p[2] > p[1] + 1.0);
+		assert_relative_eq!(p[3], 15.0, epsilon = 1e-5);
+	}
+
+	#[test]
+	fn test_scale_sum_percentiles_empty() {
+		let p = GaussianSplat::default().scale_sum_percentiles(&[50.0, 99.0]);
+
+		assert_eq!(p.len(), 2);
+		assert!(p.iter().all(|v| v.is_nan()));
+	}
+
+	#[rstest]
+	#[case(1)]
+	#[case(1_000)]
+	#[case(100_000)]
+	fn test_debug_len_bounded(#[case] num_points: usize) {
+		let gs = GaussianSplat {
+			header: Header {
+				num_points: num_points as i32,
+				spherical_harmonics_degree: 3,
+				..Default::default()
+			},
+			positions: vec![1.25; num_points * 3],
+			scales: vec![-2.5; num_points * 3],
+			rotations: vec![0.5; num_points * 4],
+			alphas: vec![0.75; num_points],
+			colors: vec![0.125; num_points * 3],
+			spherical_harmonics: vec![0.0625; num_points * 45],
+		};
+		let plain = format!("{gs:?}");
+		let pretty = format!("{gs:#?}");
+
+		assert!(plain.len() < 1024, "{plain}");
+		assert!(pretty.len() < 4096, "{pretty}");
+		assert!(plain.contains(&format!("positions: [f32; {}]", num_points * 3)));
+		assert!(plain.contains("bbox"));
+	}
+
+	#[test]
+	fn test_debug_alternate_truncates() {
+		let gs = make_grid_splat();
+		let pretty = format!("{gs:#?}");
+
+		assert!(pretty.contains("alphas: [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, ... (8 total)]"));
+		assert!(!pretty.contains("6.0, 7.0"));
+	}
+
+	#[test]
+	fn test_depth_keys() {
+		let mut gs = make_grid_splat();
+
+		gs.positions[..6].copy_from_slice(&[0.0, 0.0, 1.0, 0.0, 0.0, -1.0]);
+		let original = gs.clone();
+
+		let keys = gs.depth_keys([0.0, 0.0, 1.0]);
+
+		assert_eq!(keys.len(), 8);
+		assert!(keys[0] > keys[1]);
+		assert_relative_eq!(keys[0], 1.0);
+		assert_relative_eq!(keys[1], -1.0);
+		assert_eq!(gs, original);
+	}
+
+	#[test]
+	fn test_colors_clip_report() {
+		let mut gs = make_grid_splat();
+
+		for c in gs.colors.iter_mut() {
+			*c = 0.0;
+		}
+		assert_eq!(gs.colors_clip_report(), 0);
+
+		gs.colors[0] = 5.0;
+		gs.colors[4] = -5.0;
+		gs.colors[8] = f32::NAN;
+
+		assert_eq!(gs.colors_clip_report(), 3);
+
+		let packed = gs.to_packed_gaussians(&SaveOptions::default()).unwrap();
+
+		assert_eq!(packed.colors[0], 255);
+		assert_eq!(packed.colors[4], 0);
+	}
+
+	#[rstest]
+	#[case(CoordinateSystem::LeftDownBack)]
+	#[case(CoordinateSystem::RightDownBack)]
+	#[case(CoordinateSystem::LeftUpBack)]
+	#[case(CoordinateSystem::RightUpBack)]
+	#[case(CoordinateSystem::LeftDownFront)]
+	#[case(CoordinateSystem::RightDownFront)]
+	#[case(CoordinateSystem::LeftUpFront)]
+	#[case(CoordinateSystem::RightUpFront)]
+	fn test_rotation_roundtrip_all_coordinate_systems(
+		#[case] coord_sys: CoordinateSystem,
+		#[values(Version::V2, Version::V3)] version: Version,
+	) {
+		use std::f32::consts::FRAC_1_SQRT_2;
+
+		let rotations = [
+			[0.0, 0.0, 0.0, 1.0],
+			[1.0, 0.0, 0.0, 0.0],
+			[0.0, 0.0, FRAC_1_SQRT_2, FRAC_1_SQRT_2],
+			[0.5, -0.5, 0.5, 0.5],
+			[0.1825742, -0.3651484, 0.5477226, -0.7302967],
+			[-0.6, 0.0, 0.8, 0.0],
+		];
+		let mut gs = GaussianSplat {
+			header: Header {
+				num_points: rotations.len() as i32,
+				..Default::default()
+			},
+			positions: vec![0.0; rotations.len() * 3],
+			scales: vec![-1.0; rotations.len() * 3],
+			rotations: rotations.concat(),
+			alphas: vec![0.0; rotations.len()],
+			colors: vec![0.0; rotations.len() * 3],
+			spherical_harmonics: vec![],
+		};
+		gs.header.version = version;
+
+		let bytes = gs
+			.serialize_to_packed_bytes(
+				&SaveOptions::builder()
+					.coord_sys(coord_sys)
+					.version(version)
+					.build(),
+			)
+			.unwrap();
+		let packed = PackedGaussianSplat::from_bytes(&bytes).unwrap();
+		let loaded = GaussianSplat::new_from_packed_gaussians(
+			&packed,
+			&LoadOptions::builder().coord_sys(coord_sys).build(),
+		)
+		.unwrap();
+
+		// V2 derives w from the 8-bit x, y, z, which is coarse for w near 0.
+		let min_dot = if version == Version::V2 { 0.99 } else { 0.999 };
+
+		for (expected, actual) in rotations.iter().zip(loaded.rotations.chunks_exact(4)) {
+			let dot = expected
+				.iter()
+				.zip(actual)
+				.map(|(a, b)| a * b)
+				.sum::<f32>()
+				.abs();
+
+			assert!(
+				dot > min_dot,
+				"{:?}: expected {:?}, got {:?} (dot {})",
+				coord_sys,
+				expected,
+				actual,
+				dot
+			);
 		}
 	}
-}
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Arbitrary)]
-pub struct BoundingBox {
-	pub min_x: f32,
-	pub max_x: f32,
-	pub min_y: f32,
-	pub max_y: f32,
-	pub min_z: f32,
-	pub max_z: f32,
-}
+	#[test]
+	fn test_sh_at() {
+		let mut gs = make_grid_splat();
+
+		for (i, v) in gs.spherical_harmonics.iter_mut().enumerate() {
+			*v = i as f32;
+		}
+		assert_eq!(gs.sh_at(0, 0, 0), Some(0.0));
+		assert_eq!(gs.sh_at(2, 1, 2), Some((2 * 9 + 3 + 2) as f32));
+		assert_eq!(gs.sh_at(7, 2, 2), Some(71.0));
+
+		assert_eq!(gs.sh_at(8, 0, 0), None);
+		assert_eq!(gs.sh_at(0, 3, 0), None);
+		assert_eq!(gs.sh_at(0, 0, 3), None);
+		assert_eq!(gs.sh_at(usize::MAX, 0, 0), None);
+	}
+
+	#[test]
+	fn test_fmt_kv() {
+		let splat = make_grid_splat();
+		let out = splat.fmt_kv();
+
+		assert!(out.contains("num_points="));
+
+		let pairs = out
+			.lines()
+			.map(|line| line.split_once('=').expect("line without '='"))
+			.collect::<Vec<_>>();
+
+		assert_eq!(pairs.len(), 10);
+		assert_eq!(pairs[0], ("num_points", "8"));
+		assert_eq!(pairs[1], ("sh_degree", "1"));
+		for (key, value) in &pairs {
+			assert!(!key.is_empty());
+			assert!(!value.is_empty());
+		}
+		assert_eq!(pairs[4], ("bbox_min_x", "-1"));
+		assert_eq!(pairs[5], ("bbox_max_x", "1"));
+	}
+
+	#[test]
+	fn test_stats() {
+		let mut splat = make_grid_splat();
+
+		splat.alphas = vec![-10.0, -10.0, 0.0, 0.0, 0.0, 10.0, 10.0, f32::NAN];
+		splat.positions[0] = f32::INFINITY;
+
+		let stats = splat.stats();
+
+		assert_eq!(stats.non_finite, 2);
+		assert_eq!(stats.opacity_histogram[0], 2);
+		assert_eq!(stats.opacity_histogram[5], 3);
+		assert_eq!(stats.opacity_histogram[SplatStats::OPACITY_BINS - 1], 2);
+		assert_eq!(stats.opacity_histogram.iter().sum::<usize>(), 7);
+		assert_relative_eq!(stats.scale_sum_p50, 10.5);
+		assert!(stats.scale_sum_p10 <= stats.scale_sum_p50);
+		assert!(stats.scale_sum_p50 <= stats.scale_sum_p90);
+		assert_eq!(stats.bbox.max_y, 1.0);
+	}
+
+	#[test]
+	fn test_merge_invalid_input() {
+		let mut bad = make_grid_splat();
+
+		bad.alphas.pop();
+
+		let err = make_grid_splat().merge(&bad).unwrap_err();
+		let msg = format!("{err:#}");
+
+		assert!(msg.contains("other"), "{msg}");
+		assert!(msg.contains("alphas has length 7, expected 8"), "{msg}");
+
+		let err = bad.clone().merge(&make_grid_splat()).unwrap_err();
+		let msg = format!("{err:#}");
+
+		assert!(msg.contains("self"), "{msg}");
+		assert!(msg.contains("alphas"), "{msg}");
+	}
+
+	#[test]
+	fn test_find_non_finite_and_sanitize() {
+		let mut splat = make_grid_splat();
+
+		assert!(splat.find_non_finite().is_clean());
+
+		splat.positions[1] = f32::NAN;
+		splat.scales[4] = f32::NEG_INFINITY;
+		splat.rotations[8] = f32::NAN;
+		splat.rotations[..4].copy_from_slice(&[0.0; 4]);
+		splat.alphas[2] = f32::INFINITY;
+
+		let report = splat.find_non_finite();
+
+		assert_eq!(
+			report,
+			NonFiniteReport {
+				positions: 1,
+				scales: 1,
+				rotations: 1,
+				alphas: 1,
+				colors: 0,
+				spherical_harmonics: 0,
+			}
+		);
+		assert_eq!(report.total(), 4);
+
+		splat.sanitize();
+
+		let report = splat.find_non_finite();
+
+		assert_eq!(report.positions, 0);
+		assert_eq!(report.scales, 0);
+		assert_eq!(report.rotations, 0);
+		assert_eq!(report.alphas, 1);
+		assert_eq!(splat.positions[1], 0.0);
+		assert_eq!(splat.scales[4], 0.0);
+		assert_eq!(&splat.rotations[..4], &[0.0, 0.0, 0.0, 1.0]);
+		assert_eq!(&splat.rotations[8..12], &[0.0, 0.0, 0.0, 1.0]);
+		assert_eq!(&splat.rotations[4..8], &[1.0; 4]);
+	}
+
+	#[test]
+	fn test_sh_downgrade_error() {
+		let splat = make_grid_splat();
+
+		assert_eq!(splat.sh_downgrade_error(1, 64), 0.0);
+		assert_eq!(splat.sh_downgrade_error(0, 0), 0.0);
+
+		let err = splat.sh_downgrade_error(0, 256);
+
+		assert!(err > 0.0);
+
+		let mut zeros = make_grid_splat();
+
+		zeros.spherical_harmonics.fill(0.0);
+		assert_eq!(zeros.sh_downgrade_error(0, 64), 0.0);
+	}
+
+	#[test]
+	fn test_merge_with_dedup() {
+		let mut a = make_single_point_splat([1.0, 2.0, 3.0]);
+		let b = make_single_point_splat([1.0, 2.0, 3.001]);
+		let c = make_single_point_splat([5.0, 5.0, 5.0]);
+
+		a.merge(&c).unwrap();
+
+		let mut plain = a.clone();
+
+		plain.merge_with(&b, &MergeOptions::default()).unwrap();
+		assert_eq!(plain.header.num_points, 3);
+
+		a.merge_with(
+			&b,
+			&MergeOptions {
+				dedup_radius: Some(0.01),
+			},
+		)
+		.unwrap();
+
+		assert!(a.check_sizes());
+		assert_eq!(a.header.num_points, 2);
+		assert_eq!(&a.positions, &[1.0, 2.0, 3.0, 5.0, 5.0, 5.0]);
+	}
+
+	#[rstest]
+	#[case(0.01)]
+	#[case(0.25)]
+	#[case(0.5)]
+	#[case(0.75)]
+	#[case(0.99)]
+	fn test_opacities_roundtrip(#[case] opacity: f32) {
+		let mut splat = make_single_point_splat([0.0; 3]);
+
+		splat.alphas[0] = math::inv_sigmoid(opacity);
+
+		assert_relative_eq!(splat.opacities()[0], opacity, epsilon = 1e-6);
+	}
+
+	#[test]
+	fn test_linear_scales() {
+		let mut splat = make_single_point_splat([0.0; 3]);
+
+		splat.scales = vec![0.5_f32.ln(), 0.0, 2.0_f32.ln()];
+
+		let scales = splat.linear_scales();
+
+		assert_eq!(scales.len(), 3);
+		assert_relative_eq!(scales[0], 0.5, epsilon = 1e-6);
+		assert_relative_eq!(scales[1], 1.0, epsilon = 1e-6);
+		assert_relative_eq!(scales[2], 2.0, epsilon = 1e-6);
+	}
+
+	#[test]
+	fn test_gaussian_view() {
+		let splat = make_grid_splat();
+
+		assert_eq!(splat.gaussians().count(), 8);
+
+		for (i, g) in splat.gaussians().enumerate() {
+			assert_eq!(g.position.as_slice(), &splat.positions[i * 3..i * 3 + 3]);
+			assert_eq!(g.scale.as_slice(), &splat.scales[i * 3..i * 3 + 3]);
+			assert_eq!(g.rotation.as_slice(), &splat.rotations[i * 4..i * 4 + 4]);
+			assert_eq!(g.alpha, splat.alphas[i]);
+			assert_eq!(g.color.as_slice(), &splat.colors[i * 3..i * 3 + 3]);
+			assert_eq!(
+				g.spherical_harmonics,
+				&splat.spherical_harmonics[i * 9..i * 9 + 9]
+			);
+		}
+		assert!(splat.gaussian(7).is_some());
+		assert!(splat.gaussian(8).is_none());
+		assert!(splat.gaussian(usize::MAX / 2).is_none());
+	}
+
+	#[test]
+	fn test_from_channel_sh() {
+		let sh_r = [1.0, 2.0, 3.0, 11.0, 12.0, 13.0];
+		let sh_g = [4.0, 5.0, 6.0, 14.0, 15.0, 16.0];
+		let sh_b = [7.0, 8.0, 9.0, 17.0, 18.0, 19.0];
+
+		let splat = GaussianSplat::from_channel_sh(
+			vec![0.0; 6],
+			vec![0.0; 6],
+			vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+			vec![0.0; 2],
+			vec![0.0; 6],
+			&sh_r,
+			&sh_g,
+			&sh_b,
+			1,
+		)
+		.unwrap();
+
+		assert_eq!(splat.header.num_points, 2);
+		assert_eq!(splat.header.spherical_harmonics_degree, 1);
+		assert_eq!(
+			&splat.spherical_harmonics[..9],
+			&[1.0, 4.0, 7.0, 2.0, 5.0, 8.0, 3.0, 6.0, 9.0]
+		);
+		for point in 0..2 {
+			for coeff in 0..3 {
+				assert_eq!(
+					splat.sh_at(point, coeff, 0),
+					Some(sh_r[point * 3 + coeff])
+				);
+				assert_eq!(
+					splat.sh_at(point, coeff, 1),
+					Some(sh_g[point * 3 + coeff])
+				);
+				assert_eq!(
+					splat.sh_at(point, coeff, 2),
+					Some(sh_b[point * 3 + coeff])
+				);
+			}
+		}
+		let err = GaussianSplat::from_channel_sh(
+			vec![0.0; 6],
+			vec![0.0; 6],
+			vec![0.0; 8],
+			vec![0.0; 2],
+			vec![0.0; 6],
+			&sh_r,
+			&sh_g[..5],
+			&sh_b,
+			1,
+		)
+		.unwrap_err();
+
+		assert!(err.to_string().contains("sh_g"), "{err}");
+	}
+
+	#[test]
+	fn test_builder_load_from_bytes_and_reader() {
+		let splat = make_grid_splat();
+		let bytes = splat
+			.serialize_to_packed_bytes(&SaveOptions::default())
+			.unwrap();
+
+		let from_bytes = GaussianSplat::builder().load_from_bytes(&bytes).unwrap();
+		let from_reader = GaussianSplat::builder()
+			.load_from_reader(bytes.as_slice())
+			.unwrap();
+
+		assert_eq!(from_bytes.header.num_points, 8);
+		assert!(from_bytes == from_reader);
+
+		let flipped = GaussianSplat::builder()
+			.coord_sys(CoordinateSystem::RightDownFront)
+			.load_from_bytes(&bytes)
+			.unwrap();
+
+		assert_relative_eq!(flipped.positions[1], -from_bytes.positions[1]);
+		assert!(GaussianSplat::builder()
+			.load_from_bytes(&[1, 2, 3])
+			.is_err());
+	}
+
+	#[test]
+	fn test_shrink_to_fit() {
+		let mut splat = GaussianSplat::concat(&vec![make_grid_splat(); 1000]).unwrap();
+
+		splat.crop(&BoundingBox {
+			min_x: 0.0,
+			max_x: 2.0,
+			min_y: 0.0,
+			max_y: 2.0,
+			min_z: 0.0,
+			max_z: 2.0,
+		});
+		assert_eq!(splat.header.num_points, 1000);
+		assert!(splat.positions.capacity() >= 8000 * 3);
+
+		splat.shrink_to_fit();
+
+		assert!(splat.check_sizes());
+		for (capacity, len) in [
+			(splat.positions.capacity(), splat.positions.len()),
+			(splat.scales.capacity(), splat.scales.len()),
+			(splat.rotations.capacity(), splat.rotations.len()),
+			(splat.alphas.capacity(), splat.alphas.len()),
+			(splat.colors.capacity(), splat.colors.len()),
+			(
+				splat.spherical_harmonics.capacity(),
+				splat.spherical_harmonics.len(),
+			),
+		] {
+			assert!(capacity - len <= len / 10, "capacity {capacity}, len {len}");
+		}
+	}
+
+	#[rstest]
+	fn test_load_with_madvise(#[values(true, false)] madvise: bool) {
+		let splat = make_grid_splat();
+		let dir = tmp_dir(&format!("load_with_madvise_{madvise}"));
+		let path = dir.join("grid.spz");
+		splat.save(&path, &SaveOptions::default()).unwrap();
+
+		let opts = LoadOptions::builder().madvise(madvise).build();
+		let loaded = GaussianSplat::load_with(&path, &opts).unwrap();
+
+		assert_eq!(loaded.header.num_points, 8);
+		assert!(LoadOptions::default().madvise);
+	}
+
+	#[test]
+	fn test_decimate() {
+		let mut splat = GaussianSplat::concat(&vec![make_grid_splat(); 125]).unwrap();
+
+		for (i, a) in splat.alphas.iter_mut().enumerate() {
+			*a = if i % 2 == 0 { 5.0 } else { -5.0 };
+		}
+		assert_eq!(splat.header.num_points, 1000);
+
+		let mut a = splat.clone();
+		let mut b = splat.clone();
+
+		a.decimate(100, 7);
+		b.decimate(100, 7);
+
+		assert_eq!(a.header.num_points, 100);
+		assert!(a.check_sizes());
+		assert!(a == b);
+
+		// Opaque gaussians are far more likely to be kept.
+		let opaque = a.alphas.iter().filter(|a| **a > 0.0).count();
+
+		assert!(opaque > 90, "{opaque}");
+
+		let mut c = splat.clone();
+
+		c.decimate(2000, 7);
+		assert_eq!(c.header.num_points, 1000);
+
+		c.decimate(0, 7);
+		assert_eq!(c.header.num_points, 0);
+		assert!(c.check_sizes());
+	}
+
+	#[test]
+	fn test_to_packed_gaussians_with_report() {
+		let mut splat = make_grid_splat();
+
+		splat.spherical_harmonics.fill(0.5);
+		splat.colors.fill(0.5);
+
+		let opts = SaveOptions::default();
+		let (packed, report) = splat.to_packed_gaussians_with_report(&opts).unwrap();
+
+		assert!(report.is_clean(), "{report:?}");
+		assert!(packed == splat.to_packed_gaussians(&opts).unwrap());
+
+		// 12 fractional bits leave 11 integer bits, about +-2048.
+		splat.positions[0] = 5000.0;
+		splat.positions[4] = -f32::INFINITY;
+		splat.colors[1] = 10.0;
+		splat.spherical_harmonics[2] = -3.0;
+		splat.spherical_harmonics[3] = 2.0;
+
+		let (_, report) = splat.to_packed_gaussians_with_report(&opts).unwrap();
+
+		assert_eq!(
+			report,
+			PackReport {
+				position_overflows: 2,
+				color_clips: 1,
+				sh_clips: 2,
+			}
+		);
+		assert!(!report.is_clean());
+	}
+
+	#[test]
+	fn test_voxel_downsample() {
+		// 10x10x10 points spaced 0.1 apart, in 2x2x2 voxels of size 0.5.
+		let mut splat = GaussianSplat::default();
+
+		for i in 0..1000 {
+			splat.merge(&make_single_point_splat([
+				(i % 10) as f32 * 0.1 + 0.05,
+				(i / 10 % 10) as f32 * 0.1 + 0.05,
+				(i / 100) as f32 * 0.1 + 0.05,
+			]))
+			.unwrap();
+		}
+		splat.alphas[999] = 1.0;
+
+		splat.voxel_downsample(0.5).unwrap();
+
+		assert_eq!(splat.header.num_points, 8);
+		assert!(splat.check_sizes());
+		// The most opaque gaussian represents its voxel.
+		assert_eq!(splat.alphas[7], 1.0);
+		assert_relative_eq!(splat.positions[21], 0.95, epsilon = 1e-5);
+
+		assert!(splat.voxel_downsample(0.0).is_err());
+		assert!(splat.voxel_downsample(-1.0).is_err());
+		assert!(splat.voxel_downsample(f32::NAN).is_err());
+		assert_eq!(splat.header.num_points, 8);
+	}
+
+	#[test]
+	fn test_centroid_and_recenter() {
+		let mut splat = make_single_point_splat([1.0, 2.0, 3.0]);
+
+		splat.merge(&make_single_point_splat([3.0, 4.0, 5.0]))
+			.unwrap();
+		splat.merge(&make_single_point_splat([11.0, 0.0, -2.0]))
+			.unwrap();
+
+		assert_eq!(splat.centroid(), [5.0, 2.0, 2.0]);
+
+		let offset = splat.recenter();
+
+		assert_eq!(offset, [-5.0, -2.0, -2.0]);
+		for axis in 0..3 {
+			let sum = splat.positions.iter().skip(axis).step_by(3).sum::<f32>();
+
+			assert_relative_eq!(sum, 0.0, epsilon = 1e-5);
+		}
+		splat.translate(offset.map(|o| -o));
+
+		assert_eq!(&splat.positions[..3], &[1.0, 2.0, 3.0]);
+		assert_eq!(GaussianSplat::default().centroid(), [0.0; 3]);
+	}
+
+	#[test]
+	fn test_append_gaussians() {
+		let mut splat = make_grid_splat();
+		let gs = (0..100)
+			.map(|i| {
+				let f = i as f32;
+				let mut g = UnpackedGaussian {
+					position: [f, -f, 0.5],
+					rotation: [0.0, 0.0, 0.0, 1.0],
+					alpha: f,
+					..Default::default()
+				};
+				g.sh_r[..3].copy_from_slice(&[1.0, 2.0, 3.0]);
+				g.sh_b[2] = f;
+				g
+			})
+			.collect::<Vec<_>>();
+
+		splat.append_gaussians(&gs).unwrap();
+
+		assert_eq!(splat.header.num_points, 108);
+		assert!(splat.check_sizes());
+		assert_eq!(&splat.positions[8 * 3 + 3..8 * 3 + 6], &[1.0, -1.0, 0.5]);
+		assert_eq!(splat.sh_at(107, 2, 0), Some(3.0));
+		assert_eq!(splat.sh_at(107, 2, 2), Some(99.0));
+		assert_eq!(splat.alphas[107], 99.0);
+
+		let mut too_high = UnpackedGaussian::default();
+
+		too_high.sh_g[3] = 1.0;
+
+		let err = splat.append_gaussian(&too_high).unwrap_err();
+
+		assert!(err.to_string().contains("above degree 1"), "{err}");
+		assert_eq!(splat.header.num_points, 108);
+	}
+
+	#[test]
+	fn test_swap_remove() {
+		let mut gs = GaussianSplat::concat(&[
+			make_single_point_splat([1.0, 1.0, 1.0]),
+			make_single_point_splat([2.0, 2.0, 2.0]),
+			make_single_point_splat([3.0, 3.0, 3.0]),
+		])
+		.unwrap();
+
+		gs.swap_remove(0).unwrap();
+
+		assert!(gs.check_sizes());
+		assert_eq!(gs.header.num_points, 2);
+		assert_eq!(gs.positions, vec![3.0, 3.0, 3.0, 2.0, 2.0, 2.0]);
+
+		assert!(gs.swap_remove(2).is_err());
+	}
+
+	#[test]
+	fn test_swap_remove_sh() {
+		let mut gs = make_grid_splat();
+
+		gs.swap_remove(2).unwrap();
+
+		assert!(gs.check_sizes());
+		assert_eq!(gs.header.num_points, 7);
+		assert_eq!(gs.alphas[2], 7.0);
+		assert_eq!(gs.sh_at(2, 0, 0), Some(7.0));
+
+		gs.swap_remove(6).unwrap();
+
+		assert!(gs.check_sizes());
+		assert_eq!(gs.alphas, vec![0.0, 1.0, 7.0, 3.0, 4.0, 5.0]);
+	}
+
+	#[test]
+	fn test_reserve_exact_for_header() {
+		let mut gs = GaussianSplat {
+			header: Header {
+				num_points: 10,
+				spherical_harmonics_degree: 2,
+				..Default::default()
+			},
+			..Default::default()
+		};
+		gs.reserve_exact_for_header();
+
+		assert!(gs.positions.capacity() >= 30);
+		assert!(gs.rotations.capacity() >= 40);
+		assert!(gs.alphas.capacity() >= 10);
+		assert!(gs.spherical_harmonics.capacity() >= 240);
+		assert!(gs.positions.is_empty());
+	}
+
+	#[test]
+	fn test_density_grid() {
+		let mut gs = make_grid_splat();
+
+		assert_eq!(gs.density_grid([2, 2, 2]).unwrap(), vec![1; 8]);
+
+		// pull everything but the (-1, -1, -1) corner into the +x+y+z octant
+		for p in gs.positions.chunks_exact_mut(3).skip(1) {
+			p.iter_mut().for_each(|v| *v = v.abs() * 0.9 + 0.1);
+		}
+		let counts = gs.density_grid([2, 2, 2]).unwrap();
+
+		assert_eq!(counts, vec![1, 0, 0, 0, 0, 0, 0, 7]);
+		assert_eq!(counts.iter().sum::<u32>(), 8);
+
+		assert!(gs.density_grid([2, 0, 2]).unwrap().is_empty());
+		assert_eq!(
+			GaussianSplat::default().density_grid([2, 1, 1]).unwrap(),
+			vec![0, 0]
+		);
+		assert!(gs.density_grid([1 << 22; 3]).is_err());
+	}
+
+	#[test]
+	fn test_load_max_points() {
+		let bytes = make_grid_splat()
+			.serialize_to_packed_bytes(&SaveOptions::default())
+			.unwrap();
+		let packed = PackedGaussianSplat::from_bytes(&bytes).unwrap();
+
+		let opts = LoadOptions::builder().max_points(4).build();
+
+		assert!(matches!(
+			GaussianSplat::new_from_packed_gaussians(&packed, &opts),
+			Err(SpzError::TooManyPoints {
+				num_points: 8,
+				max: 4
+			})
+		));
+
+		let opts = LoadOptions::builder().max_points(8).build();
+
+		assert!(GaussianSplat::new_from_packed_gaussians(&packed, &opts).is_ok());
+		assert_eq!(
+			LoadOptions::default().max_points,
+			consts::MAX_POINTS_TO_READ
+		);
+	}
+
+	#[test]
+	fn test_load_many() {
+		let dir = tmp_dir("load_many");
+		let grid = dir.join("grid.spz");
+		let single = dir.join("single.spz");
+		let missing = dir.join("missing.spz");
+
+		make_grid_splat()
+			.save(&grid, &SaveOptions::default())
+			.unwrap();
+		make_single_point_splat([1.0, 2.0, 3.0])
+			.save(&single, &SaveOptions::default())
+			.unwrap();
+
+		let loaded = GaussianSplat::load_many(
+			&[&grid, &missing, &single],
+			&LoadOptions::default(),
+		);
+
+		assert_eq!(loaded.len(), 3);
+		assert_eq!(loaded[0].as_ref().unwrap().header.num_points, 8);
+		assert!(loaded[1].is_err());
+		assert_eq!(loaded[2].as_ref().unwrap().header.num_points, 1);
+	}
+
+	#[test]
+	fn test_merge_write_packed_to() {
+		let mut gs = make_grid_splat();
+
+		gs.merge(&make_grid_splat()).unwrap();
+
+		let mut out = Vec::new();
+
+		gs.write_packed_to(&mut out, &SaveOptions::default())
+			.unwrap();
+
+		assert_eq!(
+			out,
+			gs.serialize_to_packed_bytes(&SaveOptions::default())
+				.unwrap()
+		);
+
+		let packed = PackedGaussianSplat::from_bytes(&out).unwrap();
+		let loaded =
+			GaussianSplat::new_from_packed_gaussians(&packed, &LoadOptions::default())
+				.unwrap();
+
+		assert_eq!(loaded.header.num_points, 16);
+		assert_eq!(loaded.header.spherical_harmonics_degree, 1);
+		assert_eq!(loaded.positions[..24], loaded.positions[24..]);
+	}
+
+	#[test]
+	fn test_approx_eq_quaternion_double_cover() {
+		let mut gs = make_grid_splat();
+
+		for q in gs.rotations.chunks_exact_mut(4) {
+			q.copy_from_slice(&[0.1, 0.2, 0.3, 0.9]);
+		}
+		let mut negated = gs.clone();
+
+		negated.rotations.iter_mut().for_each(|v| *v = -*v);
+
+		assert!(gs.approx_eq(&negated, 1e-6));
+		assert!(negated.approx_eq(&gs, 1e-6));
+
+		let mut rotated = gs.clone();
+
+		rotated.rotations[..4].copy_from_slice(&[0.9, 0.2, 0.3, 0.1]);
+
+		assert!(!gs.approx_eq(&rotated, 1e-3));
+
+		let mut moved = gs.clone();
+
+		moved.positions[0] += 0.01;
+
+		assert!(gs.approx_eq(&moved, 0.02));
+		assert!(!gs.approx_eq(&moved, 0.001));
+		assert!(!gs.approx_eq(&make_single_point_splat([0.0; 3]), 1.0));
+	}
+
+	#[test]
+	fn test_to_packed_gaussians_unspecified_is_rub() {
+		let gs = make_grid_splat();
+		let unspecified = gs
+			.serialize_to_packed_bytes(&SaveOptions::default())
+			.unwrap();
+		let rub = gs
+			.serialize_to_packed_bytes(
+				&SaveOptions::builder()
+					.coord_sys(CoordinateSystem::RightUpBack)
+					.build(),
+			)
+			.unwrap();
+
+		assert_eq!(unspecified, rub);
+
+		let strict = SaveOptions::builder().strict_coord_sys(true).build();
+		let err = gs.to_packed_gaussians(&strict).unwrap_err();
+
+		assert!(matches!(
+			err.downcast_ref::<SpzError>(),
+			Some(SpzError::UnspecifiedCoordinateSystem)
+		));
+
+		let packed = gs.to_packed_gaussians(&SaveOptions::default()).unwrap();
+		let strict = LoadOptions::builder().strict_coord_sys(true).build();
+
+		assert!(matches!(
+			GaussianSplat::new_from_packed_gaussians(&packed, &strict),
+			Err(SpzError::UnspecifiedCoordinateSystem)
+		));
+	}
+
+	#[test]
+	fn test_render_thumbnail() {
+		let mut gs = make_single_point_splat([1.0, 2.0, 3.0]);
 
-impl BoundingBox {
-	#[inline]
-	pub fn size(&self) -> (f32, f32, f32) {
-		(
-			self.max_x - self.min_x,
-			self.max_y - self.min_y,
-			self.max_z - self.min_z,
-		)
+		gs.alphas[0] = 10.0;
+		gs.colors.fill((1.0 - 0.5) / consts::SH_C0);
+
+		let image = gs.render_thumbnail(32, 24, [0.0, 0.0, -1.0]);
+		let pixel = |x: usize, y: usize| &image[(y * 32 + x) * 4..][..4];
+
+		assert_eq!(image.len(), 32 * 24 * 4);
+		assert_eq!(pixel(16, 12), [255, 255, 255, 255]);
+		assert_eq!(pixel(0, 0), [0, 0, 0, 0]);
+
+		let side = gs.render_thumbnail(8, 8, [1.0, 0.0, 0.0]);
+
+		assert_ne!(&side[(4 * 8 + 4) * 4..][..4], [0, 0, 0, 0]);
+		assert!(gs.render_thumbnail(0, 8, [0.0; 3]).is_empty());
 	}
 
-	/// Get the center of the bounding box.
-	///
-	/// # Returns
-	///
-	/// A tuple of (x, y, z) center coordinates.
-	#[inline]
-	pub fn center(&self) -> (f32, f32, f32) {
-		(
-			(self.min_x + self.max_x) / 2.0,
-			(self.min_y + self.max_y) / 2.0,
-			(self.min_z + self.max_z) / 2.0,
+	#[test]
+	fn test_serialize_to_packed_bytes_streams() {
+		let gs = make_grid_splat();
+		let opts = SaveOptions::default();
+		let streamed = gs.serialize_to_packed_bytes(&opts).unwrap();
+
+		let mut two_step = Vec::new();
+
+		compression::compress_bytes(
+			&gs.to_packed_gaussians(&opts)
+				.unwrap()
+				.to_bytes_vec()
+				.unwrap(),
+			&mut two_step,
+			opts.compression,
 		)
+		.unwrap();
+
+		let decompress = |compressed: &[u8]| {
+			let mut out = Vec::new();
+
+			compression::decompress_end(compressed, &mut out, None).unwrap();
+			out
+		};
+		assert_eq!(decompress(&streamed), decompress(&two_step));
 	}
-}
 
-#[cfg(test)]
-mod tests {
-	use super::*;
-	use approx::assert_relative_eq;
-	use rstest::rstest;
+	#[test]
+	fn test_load_strict_rotations() {
+		let mut packed = make_grid_splat()
+			.to_packed_gaussians(&SaveOptions::default())
+			.unwrap();
 
-	#[rstest]
-	#[case(
-		GaussianSplat::default(),
-		vec![
-			-1.0, -1.0, -1.0, // First gaussian: scale sum = -3
-			0.0, 0.0, 0.0, // Second gaussian: scale sum = 0
-			1.0, 1.0, 1.0, // Third gaussian: scale sum = 3
-		],
-		(4.0 / 3.0) * std::f32::consts::PI * 0.0_f32.exp(),
-		1e-5_f32,
-	)]
-	#[case(
-		GaussianSplat::default(),
-		vec![
-			-2.0, -2.0, -2.0, // First gaussian: scale sum = -6
-			-1.0, -1.0, -1.0, // Second gaussian: scale sum = -3
-			0.0, 0.0, 0.0, // Third gaussian: scale sum = 0 (median)
-			1.0, 1.0, 1.0, // Fourth gaussian: scale sum = 3
-			2.0, 2.0, 2.0, // Fifth gaussian: scale sum = 6
-		],
-		(4.0 / 3.0) * std::f32::consts::PI * 0.0_f32.exp(),
-		1e-5_f32,
-	)]
-	fn test_median_volume(
-		#[case] mut gs: GaussianSplat,
-		#[case] scales: Vec<f32>,
-		#[case] expected_vol: f32,
-		#[case] epsilon: f32,
-	) {
-		gs.scales = scales;
+		assert!(packed.uses_quaternion_smallest_three);
 
-		assert_relative_eq!(gs.median_volume(), expected_vol, epsilon = epsilon);
-	}
+		let corrupt = 0x1ff_u32 | (0x1ff << 10) | (0x1ff << 20) | (3 << 30);
 
-	#[rstest]
-	#[case(vec![])]
-	#[case(vec![f32::NAN, f32::NAN, f32::NAN])]
-	#[case(vec![f32::INFINITY, f32::INFINITY, f32::INFINITY])]
-	fn test_median_volume_fallback(#[case] scales: Vec<f32>) {
-		let gs = GaussianSplat {
-			scales,
-			..GaussianSplat::default()
-		};
+		packed.rotations[8..12].copy_from_slice(&corrupt.to_le_bytes());
 
-		assert_relative_eq!(gs.median_volume(), 0.01, epsilon = 1e-6);
+		assert!(
+			GaussianSplat::new_from_packed_gaussians(&packed, &LoadOptions::default())
+				.is_ok()
+		);
+
+		let strict = LoadOptions::builder().strict_rotations(true).build();
+
+		assert!(matches!(
+			GaussianSplat::new_from_packed_gaussians(&packed, &strict),
+			Err(SpzError::CorruptRotation { index: 2 })
+		));
 	}
 
 	#[test]
-	fn test_check_sizes_default_is_valid() {
-		let gs = GaussianSplat::default();
+	fn test_bake_sh_to_color() {
+		let mut gs = make_grid_splat();
+
+		gs.spherical_harmonics.fill(0.0);
 
+		let colors = gs.colors.clone();
+
+		gs.bake_sh_to_color([1.0, 2.0, 3.0]).unwrap();
+
+		assert_eq!(gs.colors, colors);
+		assert_eq!(gs.header.spherical_harmonics_degree, 0);
+		assert!(gs.spherical_harmonics.is_empty());
 		assert!(gs.check_sizes());
+
+		let mut gs = make_grid_splat();
+
+		gs.spherical_harmonics.fill(0.0);
+		// coefficient 1 of band 1 is SH_C1 * z, red channel
+		gs.spherical_harmonics[3] = 1.0;
+
+		gs.bake_sh_to_color([0.0, 0.0, 2.0]).unwrap();
+
+		assert_relative_eq!(gs.colors[0], 0.488_602_5 / consts::SH_C0, epsilon = 1e-5);
+		assert_eq!(gs.colors[1..], colors[1..]);
+		assert!(gs.bake_sh_to_color([0.0; 3]).is_err());
 	}
 
 	#[test]
-	fn test_check_sizes_negative_num_points() {
-		let mut gs = GaussianSplat::default();
+	fn test_load_allow_uncompressed() {
+		let dir = tmp_dir("load_allow_uncompressed");
+		let path = dir.join("raw.spz");
+		let gs = make_grid_splat();
+		let raw = gs
+			.to_packed_gaussians(&SaveOptions::default())
+			.unwrap()
+			.to_bytes_vec()
+			.unwrap();
 
-		gs.header.num_points = -1;
+		std::fs::write(&path, &raw).unwrap();
 
-		assert!(!gs.check_sizes());
+		let opts = LoadOptions::builder().allow_uncompressed(true).build();
+		let loaded = GaussianSplat::load_with(&path, &opts);
+		let rejected = GaussianSplat::load_with(&path, &LoadOptions::default());
+		let expected = GaussianSplat::read_from(
+			gs.serialize_to_packed_bytes(&SaveOptions::default())
+				.unwrap()
+				.as_slice(),
+			&LoadOptions::default(),
+		)
+		.unwrap();
+
+		assert_eq!(loaded.unwrap(), expected);
+		assert!(matches!(rejected, Err(SpzError::Decompress(_))));
+		assert_eq!(
+			GaussianSplat::read_from(raw.as_slice(), &opts).unwrap(),
+			expected
+		);
 	}
 
-	#[test]
-	fn test_check_sizes_invalid_sh_degree() {
-		let mut gs = GaussianSplat::default();
+	#[rstest]
+	#[case(Version::V2)]
+	#[case(Version::V3)]
+	fn test_estimated_packed_size(#[case] version: Version) {
+		let opts = SaveOptions::builder().version(version).build();
 
-		gs.header.spherical_harmonics_degree = 4;
+		for gs in [make_grid_splat(), make_single_point_splat([1.0, 2.0, 3.0])] {
+			let packed = gs.to_packed_gaussians(&opts).unwrap();
 
-		assert!(!gs.check_sizes());
+			assert_eq!(
+				gs.estimated_packed_size(&opts),
+				packed.to_bytes_vec().unwrap().len()
+			);
+		}
 	}
 
 	#[test]
-	fn test_check_sizes_wrong_positions_len() {
-		let gs = GaussianSplat {
-			header: Header {
-				num_points: 1,
-				..Default::default()
-			},
-			positions: vec![0.0; 2],
-			scales: vec![0.0; 3],
-			rotations: vec![0.0; 4],
-			alphas: vec![0.0; 1],
-			colors: vec![0.0; 3],
-			spherical_harmonics: vec![],
-		};
-		assert!(!gs.check_sizes());
+	fn test_center_extent() {
+		let mut gs = make_grid_splat();
+
+		for p in gs.positions.chunks_exact_mut(3) {
+			p[0] = p[0] * 2.0 + 3.0;
+			p[2] -= 1.0;
+		}
+		let bbox = gs.bbox();
+		let (cx, cy, cz) = bbox.center();
+		let (sx, sy, sz) = bbox.size();
+
+		assert_eq!(gs.center(), [cx, cy, cz]);
+		assert_eq!(gs.extent(), [sx, sy, sz]);
+		assert_eq!(gs.center(), [3.0, 0.0, -1.0]);
+		assert_eq!(gs.extent(), [4.0, 2.0, 2.0]);
 	}
 
 	#[test]
-	fn test_check_sizes_correct_for_one_point_sh1() {
-		let gs = GaussianSplat {
-			header: Header {
-				num_points: 1,
-				spherical_harmonics_degree: 1,
-				..Default::default()
-			},
-			positions: vec![0.0; 3],
-			scales: vec![0.0; 3],
-			rotations: vec![0.0; 4],
-			alphas: vec![0.0; 1],
-			colors: vec![0.0; 3],
-			spherical_harmonics: vec![0.0; 9],
-		};
-		assert!(gs.check_sizes());
+	fn test_from_gzip_reader() {
+		let bytes = make_grid_splat()
+			.serialize_to_packed_bytes(&SaveOptions::default())
+			.unwrap();
+		let opts = LoadOptions::default();
+
+		let streamed = GaussianSplat::from_gzip_reader(bytes.as_slice(), &opts).unwrap();
+
+		assert_eq!(
+			streamed,
+			GaussianSplat::read_from(bytes.as_slice(), &opts).unwrap()
+		);
+
+		let mut raw = make_grid_splat()
+			.to_packed_gaussians(&SaveOptions::default())
+			.unwrap()
+			.to_bytes_vec()
+			.unwrap();
+		raw.truncate(raw.len() - 1);
+
+		let mut truncated = Vec::new();
+		compression::compress_bytes(&raw, &mut truncated, Compression::Gzip).unwrap();
+
+		let err = GaussianSplat::from_gzip_reader(truncated.as_slice(), &opts).unwrap_err();
+
+		assert!(matches!(
+			err.downcast_ref::<SpzError>(),
+			Some(SpzError::Truncated {
+				section: "spherical harmonics",
+				..
+			})
+		));
 	}
 
-	#[rstest]
-	#[case((-1.0, 1.0, 0.0, 5.0, -3.0, 3.0), (2.0, 5.0, 6.0), (0.0, 2.5, 0.0))]
-	#[case((-2.0, 2.0, -1.0, 3.0, 0.0, 10.0), (4.0, 4.0, 10.0), (0.0, 1.0, 5.0))]
-	#[case((5.0, 5.0, 5.0, 5.0, 5.0, 5.0), (0.0, 0.0, 0.0), (5.0, 5.0, 5.0))]
-	fn test_bounding_box(
-		#[case] bounds: (f32, f32, f32, f32, f32, f32),
-		#[case] expected_size: (f32, f32, f32),
-		#[case] expected_center: (f32, f32, f32),
-	) {
-		let bbox = BoundingBox {
-			min_x: bounds.0,
-			max_x: bounds.1,
-			min_y: bounds.2,
-			max_y: bounds.3,
-			min_z: bounds.4,
-			max_z: bounds.5,
-		};
-		assert_eq!(bbox.size(), expected_size);
-		assert_eq!(bbox.center(), expected_center);
+	#[test]
+	fn test_apply_transform_rotates_sh() {
+		let mut gs = make_grid_splat();
+		let rotation = [
+			0.0,
+			0.0,
+			std::f32::consts::FRAC_1_SQRT_2,
+			std::f32::consts::FRAC_1_SQRT_2,
+		];
+		let mut expected = gs.spherical_harmonics.clone();
+
+		for sh in expected.chunks_exact_mut(9) {
+			math::rotate_sh(sh, 1, rotation);
+		}
+		gs.apply_transform([0.0; 3], rotation, 1.0).unwrap();
+
+		assert_eq!(gs.spherical_harmonics, expected);
+
+		// band 1 basis is (-y, z, -x), a 90 degree turn about z yields (c2, c1, -c0)
+		let mut sh = [1.0, 1.0, 1.0, 2.0, 2.0, 2.0, 3.0, 3.0, 3.0];
+
+		math::rotate_sh(&mut sh, 1, rotation);
+
+		for (a, b) in sh
+			.iter()
+			.zip([3.0, 3.0, 3.0, 2.0, 2.0, 2.0, -1.0, -1.0, -1.0])
+		{
+			assert_relative_eq!(*a, b, epsilon = 1e-6);
+		}
 	}
 
 	#[test]
-	fn test_builder_default() {
-		let builder = GaussianSplatBuilder::default();
+	fn test_memory_footprint() {
+		let gs = make_grid_splat();
+		let report = gs.memory_footprint();
+		let expected = [
+			&gs.positions,
+			&gs.scales,
+			&gs.rotations,
+			&gs.alphas,
+			&gs.colors,
+			&gs.spherical_harmonics,
+		]
+		.iter()
+		.map(|arr| arr.len() * 4)
+		.sum::<usize>();
 
-		assert_eq!(builder.coord_sys, CoordinateSystem::Unspecified);
+		assert_eq!(report.total, expected);
+		assert_eq!(report.total, 8 * (3 + 3 + 4 + 1 + 3 + 9) * 4);
+		assert_eq!(report.positions, 8 * 3 * 4);
+		assert_eq!(report.spherical_harmonics, 8 * 9 * 4);
+		assert_eq!(
+			GaussianSplat::default().memory_footprint(),
+			MemoryReport::default()
+		);
 	}
 
 	#[rstest]
-	#[case(false, true)]
-	#[case(true, false)]
-	fn test_builder_packed(#[case] packed: bool, #[case] expect_err: bool) {
-		let builder = GaussianSplatBuilder::default();
+	#[case(CoordinateSystem::Unspecified, CoordinateSystem::RightDownFront)]
+	#[case(CoordinateSystem::RightDownFront, CoordinateSystem::Unspecified)]
+	#[case(CoordinateSystem::Unspecified, CoordinateSystem::Unspecified)]
+	fn test_convert_coordinates_checked_unspecified(
+		#[case] source_cs: CoordinateSystem,
+		#[case] target_cs: CoordinateSystem,
+	) {
+		let mut gs = make_grid_splat();
+		let original = gs.clone();
 
-		assert_eq!(builder.packed(packed).is_err(), expect_err);
+		assert!(matches!(
+			gs.convert_coordinates_checked(source_cs, target_cs),
+			Err(SpzError::UnspecifiedCoordinateSystem)
+		));
+		assert_eq!(gs, original);
 	}
 
 	#[test]
-	fn test_builder_coord_sys() {
-		let builder = GaussianSplat::builder().coord_sys(CoordinateSystem::RightDownFront);
+	fn test_convert_coordinates_checked() {
+		let mut gs = make_grid_splat();
+		let mut expected = gs.clone();
 
-		assert_eq!(builder.coord_sys, CoordinateSystem::RightDownFront);
+		expected.convert_coordinates(
+			CoordinateSystem::RightUpBack,
+			CoordinateSystem::RightDownFront,
+		);
+		gs.convert_coordinates_checked(
+			CoordinateSystem::RightUpBack,
+			CoordinateSystem::RightDownFront,
+		)
+		.unwrap();
+
+		assert_eq!(gs, expected);
+		assert_ne!(gs, make_grid_splat());
 	}
 
 	#[test]
-	fn test_load_options_builder() {
-		let opts = LoadOptions::builder()
-			.coord_sys(CoordinateSystem::LeftUpFront)
-			.build();
+	fn test_bbox_empty() {
+		let gs = GaussianSplat::default();
 
-		assert_eq!(opts.coord_sys, CoordinateSystem::LeftUpFront);
+		assert_eq!(gs.bbox(), BoundingBox::default());
+		assert_eq!(gs.bbox().size(), (0.0, 0.0, 0.0));
+		assert_eq!(gs.center(), [0.0; 3]);
+		assert_eq!(gs.extent(), [0.0; 3]);
 	}
 
 	#[test]
-	fn test_save_options_builder() {
-		let opts = SaveOptions::builder()
-			.coord_sys(CoordinateSystem::RightUpFront)
-			.build();
+	fn test_load_with_progress() {
+		let dir = tmp_dir("load_with_progress");
+		let path = dir.join("grid.spz");
+		let opts = LoadOptions::default();
 
-		assert_eq!(opts.coord_sys, CoordinateSystem::RightUpFront);
+		make_grid_splat()
+			.save(&path, &SaveOptions::default())
+			.unwrap();
+
+		let mut progress = Vec::new();
+		let loaded = GaussianSplat::load_with_progress(&path, &opts, |p| progress.push(p));
+		let expected = GaussianSplat::load_with(&path, &opts);
+
+		assert_eq!(loaded.unwrap(), expected.unwrap());
+		assert!(
+			progress.len() >= 4,
+			"progress reported {} times",
+			progress.len()
+		);
+		assert!(progress.windows(2).all(|w| w[0] <= w[1]), "{progress:?}");
+		assert_eq!(progress.first(), Some(&0.0));
+		assert_eq!(progress.last(), Some(&1.0));
 	}
 
 	#[test]
-	fn test_convert_coordinates_zero_points_noop() {
+	fn test_len_is_empty() {
 		let mut gs = GaussianSplat::default();
 
-		gs.convert_coordinates(
-			CoordinateSystem::RightUpBack,
-			CoordinateSystem::RightDownFront,
-		);
-		assert!(gs.positions.is_empty());
+		assert_eq!(gs.len(), 0);
+		assert!(gs.is_empty());
+
+		gs.header.num_points = -3;
+
+		assert_eq!(gs.len(), 0);
+		assert!(gs.is_empty());
+
+		let gs = make_grid_splat();
+
+		assert_eq!(gs.len(), 8);
+		assert!(!gs.is_empty());
 	}
 
 	#[test]
-	fn test_convert_coordinates_unspecified_noop() {
-		let mut gs = GaussianSplat {
-			header: Header {
-				num_points: 1,
-				..Default::default()
-			},
-			positions: vec![1.0, 2.0, 3.0],
-			scales: vec![0.0; 3],
-			rotations: vec![0.0, 0.0, 0.0, 1.0],
-			alphas: vec![0.5],
-			colors: vec![0.0; 3],
-			spherical_harmonics: vec![],
-		};
-		let original_pos = gs.positions.clone();
+	fn test_pack_with() {
+		let gs = make_grid_splat();
+		let opts = PackOptions::builder()
+			.from(CoordinateSystem::RightDownFront)
+			.build();
 
-		gs.convert_coordinates(
-			CoordinateSystem::Unspecified,
-			CoordinateSystem::RightDownFront,
+		assert_eq!(
+			gs.pack_with(&opts).unwrap(),
+			gs.to_packed_gaussians(
+				&SaveOptions::builder()
+					.coord_sys(CoordinateSystem::RightDownFront)
+					.build()
+			)
+			.unwrap()
+		);
+		assert_ne!(
+			gs.pack_with(&opts).unwrap(),
+			gs.pack_with(&PackOptions::default()).unwrap()
 		);
-		assert_eq!(gs.positions, original_pos);
 	}
 
 	#[test]
-	fn test_to_packed_gaussians_inconsistent_sizes_fails() {
-		let gs = GaussianSplat {
-			header: Header {
-				num_points: 1,
-				..Default::default()
+	fn test_load_nonexistent_file() {
+		let dir = tmp_dir("load_nonexistent");
+		let path = dir.join("missing.spz");
+
+		match GaussianSplat::load_with(&path, &LoadOptions::default()) {
+			Err(SpzError::Io(err)) => {
+				assert_eq!(err.kind(), std::io::ErrorKind::NotFound)
 			},
-			positions: vec![1.0],
-			..Default::default()
+			other => panic!("expected an io error, got {other:?}"),
+		}
+	}
+
+	#[test]
+	fn test_from_gzip_reader_too_many_points() {
+		let header = Header {
+			num_points: 1_000_000,
+			..Header::default()
 		};
-		assert!(gs.to_packed_gaussians(&SaveOptions::default()).is_err());
+		let mut compressed = Vec::new();
+
+		compression::compress_bytes(
+			&<[u8; 16]>::from(header),
+			&mut compressed,
+			Compression::Gzip,
+		)
+		.unwrap();
+
+		let opts = LoadOptions::builder().max_points(1000).build();
+		let err =
+			GaussianSplat::from_gzip_reader(compressed.as_slice(), &opts).unwrap_err();
+
+		assert!(matches!(
+			err.downcast_ref::<SpzError>(),
+			Some(SpzError::TooManyPoints {
+				num_points: 1_000_000,
+				max: 1000,
+			})
+		));
 	}
 }
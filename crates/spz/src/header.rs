@@ -3,7 +3,7 @@
 use std::io::Write;
 use std::{fmt::Display, io::Read, path::Path};
 
-use anyhow::{Context, Error, Result, anyhow, bail};
+use anyhow::{Context, Error, Result, bail};
 use arbitrary::Arbitrary;
 use bitflags::bitflags;
 use likely_stable::{likely, unlikely};
@@ -12,7 +12,9 @@ use strum::EnumIter;
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, TryFromBytes};
 
 use crate::compression;
+use crate::error::SpzError;
 use crate::mmap::mmap_range;
+use crate::packed::sh_bytes_per_point;
 
 /// Header Magic Value. "NGSP" in little-endian (LE).
 /// Every SPZ file's 1st 4 bytes are this magic number.
@@ -163,6 +165,42 @@ pub struct Header {
 }
 
 impl Header {
+	/// Header magic value, see [`MAGIC_VALUE`].
+	pub const MAGIC: i32 = MAGIC_VALUE;
+
+	/// Returns the magic value as it appears at the start of the
+	/// decompressed stream, i.e. `b"NGSP"`.
+	///
+	/// Useful for file-type sniffing.
+	#[inline]
+	pub const fn magic_bytes() -> [u8; 4] {
+		Self::MAGIC.to_le_bytes()
+	}
+
+	/// Parses and validates a header from its raw, decompressed bytes.
+	///
+	/// Unlike the [`TryFrom`] conversions, the error tells why the bytes
+	/// aren't a valid header.
+	pub fn parse(bytes: &[u8; HEADER_SIZE]) -> Result<Self, SpzError> {
+		let magic = i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+
+		if unlikely(magic != MAGIC_VALUE) {
+			return Err(SpzError::BadMagic(magic));
+		}
+		let version = i32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+
+		if unlikely(!matches!(version, 2 | 3)) {
+			return Err(SpzError::UnsupportedVersion(version));
+		}
+		let header: Header = zerocopy::TryFromBytes::try_read_from_bytes(bytes)
+			.map_err(|_| SpzError::HeaderValidation)?;
+
+		if unlikely(!header.is_valid()) {
+			return Err(SpzError::HeaderValidation);
+		}
+		Ok(header)
+	}
+
 	/// Decompresses and reads a header from the given compressed bytes.
 	///
 	/// Does NOT validate whether the read header is a valid SPZ header,
@@ -172,12 +210,16 @@ impl Header {
 	where
 		C: AsRef<[u8]>,
 	{
-		let mut decompressed = [0_u8; COMPRESSED_BLOCK_READ_SIZE as usize];
+		let mut decompressed = [0_u8; HEADER_SIZE];
 
-		compression::gzip::decompress(compressed, &mut decompressed)
+		let filled = compression::decompress(compressed, &mut decompressed)
 			.with_context(|| "unable to decompress header bytes")?;
 
-		decompressed[..HEADER_SIZE]
+		if unlikely(filled < HEADER_SIZE) {
+			bail!("truncated header: {filled} of {HEADER_SIZE} bytes");
+		}
+		decompressed
+			.as_slice()
 			.try_into()
 			.with_context(|| "unable to read header")
 	}
@@ -196,6 +238,46 @@ impl Header {
 		Ok(header)
 	}
 
+	/// Decompresses and reads a header from the given compressed reader.
+	///
+	/// Consumes at most the first 512 bytes of `reader`, enough to
+	/// decompress the header, so metadata can be read from streaming
+	/// sources like network responses without fetching the whole file.
+	///
+	/// Does NOT validate whether the read header is a valid SPZ header,
+	/// simply reads the bytes and interprets them as a header.
+	#[inline]
+	pub fn from_compressed_reader_unchecked<R>(reader: R) -> Result<Self>
+	where
+		R: Read,
+	{
+		let mut compressed = Vec::with_capacity(COMPRESSED_BLOCK_READ_SIZE as usize);
+
+		reader.take(COMPRESSED_BLOCK_READ_SIZE as u64)
+			.read_to_end(&mut compressed)
+			.with_context(|| "unable to read compressed header bytes")?;
+
+		Self::from_compressed_bytes_unchecked(compressed)
+	}
+
+	/// Decompresses and reads a header from the given compressed reader.
+	///
+	/// Consumes at most the first 512 bytes of `reader`, enough to
+	/// decompress the header, so metadata can be read from streaming
+	/// sources like network responses without fetching the whole file.
+	#[inline]
+	pub fn from_compressed_reader<R>(reader: R) -> Result<Self>
+	where
+		R: Read,
+	{
+		let header = Self::from_compressed_reader_unchecked(reader)?;
+
+		if unlikely(!header.is_valid()) {
+			bail!("header fails validation");
+		}
+		Ok(header)
+	}
+
 	/// Reads a header directly from a file path using memory mapping.
 	///
 	/// Efficient for quickly inspecting SPZ file metadata without
@@ -315,6 +397,35 @@ impl Header {
 			&& self.reserved == 0)
 	}
 
+	/// Returns the number of bytes a single gaussian takes in the
+	/// decompressed stream, depending on the version and the spherical
+	/// harmonics degree.
+	///
+	/// `v1` stores positions as float16 (6 bytes), later versions as 24-bit
+	/// fixed point (9 bytes). `v3` stores rotations in 4 bytes, earlier
+	/// versions in 3.
+	pub fn packed_bytes_per_point(&self) -> usize {
+		let (position_bytes, rotation_bytes) = match self.version {
+			Version::V1 => (6, 3),
+			Version::V2 => (9, 3),
+			Version::V3 => (9, 4),
+		};
+		// + alpha, color and scale
+		position_bytes
+			+ 1 + 3 + 3 + rotation_bytes
+			+ sh_bytes_per_point(self.spherical_harmonics_degree)
+	}
+
+	/// Returns the exact length of the decompressed stream described by this
+	/// header, including the header itself, without touching any data.
+	///
+	/// Useful to pre-size buffers, for progress reporting, or to reject
+	/// files whose decompressed size doesn't match before fully
+	/// decompressing them. A negative `num_points` counts as `0`.
+	pub fn expected_decompressed_len(&self) -> usize {
+		HEADER_SIZE + self.num_points.max(0) as usize * self.packed_bytes_per_point()
+	}
+
 	pub fn pretty_fmt(&self) -> String {
 		use std::fmt::Write;
 
@@ -378,15 +489,11 @@ impl From<Header> for [u8; 16] {
 impl TryFrom<[u8; 16]> for Header {
 	type Error = Error;
 
+	/// Validates the magic and the [`Version`] discriminant before reading
+	/// the header, see [`Header::parse`].
 	#[inline]
 	fn try_from(from: [u8; 16]) -> Result<Self> {
-		let header: Header = zerocopy::TryFromBytes::try_read_from_bytes(&from)
-			.map_err(|_| anyhow!("invalid header bytes"))?;
-
-		if unlikely(!header.is_valid()) {
-			bail!("header fails validation");
-		}
-		Ok(header)
+		Ok(Self::parse(&from)?)
 	}
 }
 
@@ -403,14 +510,11 @@ impl TryFrom<&[u8]> for Header {
 				from.len()
 			);
 		}
-		let header: Header =
-			zerocopy::TryFromBytes::try_read_from_bytes(&from[..HEADER_SIZE])
-				.map_err(|_| anyhow::anyhow!("invalid header bytes"))?;
+		let bytes: &[u8; HEADER_SIZE] = from[..HEADER_SIZE]
+			.try_into()
+			.with_context(|| "unable to read header bytes")?;
 
-		if unlikely(!header.is_valid()) {
-			bail!("header fails validation");
-		}
-		Ok(header)
+		Ok(Self::parse(bytes)?)
 	}
 }
 
@@ -585,6 +689,16 @@ mod tests {
 		assert!(h.is_valid());
 	}
 
+	#[test]
+	fn test_header_magic_bytes() {
+		assert_eq!(Header::MAGIC, MAGIC_VALUE);
+		assert_eq!(&Header::magic_bytes(), b"NGSP");
+		assert_eq!(
+			<[u8; HEADER_SIZE]>::from(Header::default())[..4],
+			Header::magic_bytes()
+		);
+	}
+
 	#[test]
 	fn test_header_display() {
 		let h = Header::default();
@@ -685,4 +799,184 @@ mod tests {
 
 		assert!(result.is_err());
 	}
+
+	#[rstest]
+	#[case(99)]
+	#[case(-1)]
+	#[case(1)]
+	fn test_header_try_from_invalid_version(#[case] version: i32) {
+		let mut bytes: [u8; 16] = Header::default().into();
+
+		bytes[4..8].copy_from_slice(&version.to_le_bytes());
+
+		let err = Header::try_from(bytes).unwrap_err();
+
+		assert!(matches!(
+			err.downcast_ref::<SpzError>(),
+			Some(SpzError::UnsupportedVersion(v)) if *v == version
+		));
+
+		let err = Header::try_from(bytes.as_slice()).unwrap_err();
+
+		assert_eq!(err.to_string(), format!("unsupported version: {version}"));
+	}
+
+	#[rstest]
+	fn test_expected_decompressed_len(
+		#[values(Version::V2, Version::V3)] version: Version,
+		#[values(0, 1, 2, 3)] sh_degree: u8,
+	) {
+		let header = Header {
+			version,
+			num_points: 7,
+			spherical_harmonics_degree: sh_degree,
+			..Default::default()
+		};
+		let len = header.expected_decompressed_len();
+
+		let mut bytes = <[u8; 16]>::from(header).to_vec();
+		bytes.resize(len, 0);
+
+		let packed =
+			crate::packed::PackedGaussianSplat::try_from(bytes.as_slice()).unwrap();
+		let sections = packed.positions.len()
+			+ packed.scales.len() + packed.rotations.len()
+			+ packed.alphas.len() + packed.colors.len()
+			+ packed.spherical_harmonics.len();
+
+		assert_eq!(len, HEADER_SIZE + sections);
+		assert!(crate::packed::PackedGaussianSplat::try_from(&bytes[..len - 1]).is_err());
+	}
+
+	#[rstest]
+	#[case(Version::V1, 0, 16)]
+	#[case(Version::V1, 3, 61)]
+	#[case(Version::V2, 0, 19)]
+	#[case(Version::V3, 0, 20)]
+	#[case(Version::V3, 3, 65)]
+	fn test_packed_bytes_per_point(
+		#[case] version: Version,
+		#[case] sh_degree: u8,
+		#[case] expected: usize,
+	) {
+		let header = Header {
+			version,
+			spherical_harmonics_degree: sh_degree,
+			..Default::default()
+		};
+		assert_eq!(header.packed_bytes_per_point(), expected);
+		assert_eq!(header.expected_decompressed_len(), HEADER_SIZE);
+	}
+
+	#[test]
+	fn test_from_compressed_reader() {
+		let original = Header {
+			num_points: 4096,
+			spherical_harmonics_degree: 2,
+			..Default::default()
+		};
+		let mut decompressed = Vec::new();
+
+		original.serialize_to(&mut decompressed)
+			.expect("serialize failed");
+		decompressed.extend((0..100_000_u32).map(|i| i.wrapping_mul(2_654_435_761) as u8));
+
+		let mut compressed = Vec::new();
+
+		compression::compress_bytes(
+			&decompressed,
+			&mut compressed,
+			compression::Compression::Gzip,
+		)
+		.expect("compression failed");
+
+		let mut cursor = std::io::Cursor::new(&compressed);
+		let header = Header::from_compressed_reader(&mut cursor).expect("read failed");
+
+		assert_eq!(header, original);
+		assert_eq!(cursor.position(), COMPRESSED_BLOCK_READ_SIZE as u64);
+
+		let mut bad_magic: [u8; 16] = Header::default().into();
+		let mut invalid = Vec::new();
+
+		bad_magic[0] ^= 0xff;
+		compression::compress_bytes(
+			&bad_magic,
+			&mut invalid,
+			compression::Compression::Gzip,
+		)
+		.expect("compression failed");
+
+		assert!(Header::from_compressed_reader(invalid.as_slice()).is_err());
+		assert!(Header::from_compressed_reader(&invalid[..HEADER_SIZE]).is_err());
+	}
+
+	/// Compresses a header followed by `len` poorly compressible bytes, so
+	/// the first compressed block decompresses to less than 512 bytes.
+	fn compress_noisy(header: &Header, len: usize) -> Vec<u8> {
+		let mut decompressed = Vec::new();
+		let mut state = 0x2545_f491_u32;
+
+		header.serialize_to(&mut decompressed)
+			.expect("serialize failed");
+		decompressed.extend((0..len).map(|_| {
+			state ^= state << 13;
+			state ^= state >> 17;
+			state ^= state << 5;
+			state as u8
+		}));
+
+		let mut compressed = Vec::new();
+
+		compression::compress_bytes(
+			&decompressed,
+			&mut compressed,
+			compression::Compression::Gzip,
+		)
+		.expect("compression failed");
+
+		compressed
+	}
+
+	#[test]
+	fn test_from_compressed_bytes_incompressible() {
+		let original = Header {
+			num_points: 20_000,
+			..Default::default()
+		};
+		let compressed = compress_noisy(&original, 250_000);
+		let block = &compressed[..COMPRESSED_BLOCK_READ_SIZE as usize];
+
+		assert_eq!(
+			Header::from_compressed_bytes(block).expect("read failed"),
+			original
+		);
+		assert_eq!(
+			Header::from_compressed_bytes_unchecked(block).expect("read failed"),
+			original
+		);
+		// too short to hold the whole header
+		assert!(Header::from_compressed_bytes(&compressed[..12]).is_err());
+	}
+
+	#[test]
+	fn test_from_compressed_reader_incompressible() {
+		let original = Header {
+			num_points: 20_000,
+			spherical_harmonics_degree: 1,
+			..Default::default()
+		};
+		let compressed = compress_noisy(&original, 250_000);
+
+		let mut cursor = std::io::Cursor::new(&compressed);
+		let header = Header::from_compressed_reader(&mut cursor).expect("read failed");
+
+		assert_eq!(header, original);
+		assert_eq!(cursor.position(), COMPRESSED_BLOCK_READ_SIZE as u64);
+		assert_eq!(
+			Header::from_compressed_reader_unchecked(compressed.as_slice())
+				.expect("read failed"),
+			original
+		);
+	}
 }
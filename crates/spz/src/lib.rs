@@ -8,19 +8,43 @@
 pub mod compression;
 pub mod consts;
 pub mod coord;
+pub mod detect;
+pub mod error;
 pub mod gaussian_splat;
 pub mod header;
 pub mod math;
 pub mod mmap;
 pub mod packed;
+pub mod ply;
 pub mod unpacked;
 
+/// Commonly used types.
+///
+/// ```
+/// use spz::prelude::*;
+///
+/// let splat = GaussianSplat::default();
+/// let header: Header = splat.header;
+/// let load_opts = LoadOptions::builder()
+///     .coord_sys(CoordinateSystem::RightUpBack)
+///     .build();
+/// let save_opts = SaveOptions::default();
+///
+/// assert_eq!(header.num_points, 0);
+/// assert_eq!(load_opts.coord_sys, CoordinateSystem::RightUpBack);
+/// assert_eq!(save_opts.coord_sys, CoordinateSystem::Unspecified);
+/// ```
 pub mod prelude {
 	pub use super::*;
 
 	pub use super::coord::{AxisFlips, CoordinateSystem};
-	pub use super::gaussian_splat::{BoundingBox, GaussianSplat, LoadOptions, SaveOptions};
+	pub use super::detect::FileKind;
+	pub use super::error::SpzError;
+	pub use super::gaussian_splat::{
+		BoundingBox, GaussianSplat, GaussianView, LoadOptions, MemoryReport, MergeOptions,
+		NonFiniteReport, PackReport, SaveOptions, SplatStats,
+	};
 	pub use super::header::Header;
-	pub use super::packed::{PackedGaussian, PackedGaussianSplat};
-	pub use super::unpacked::UnpackedGaussian;
+	pub use super::packed::{PackOptions, PackedGaussian, PackedGaussianSplat};
+	pub use super::unpacked::{UnpackOptions, UnpackedGaussian};
 }
@@ -59,11 +59,40 @@ pub fn unpack_quaternion_smallest_three(rotation: &mut [f32], r: &[u8]) {
 	unpack_quaternion_smallest_three_with_flip(rotation, r, [1.0_f32, 1.0_f32, 1.0_f32]);
 }
 
+/// Slack for quantization when checking the smallest three components of a
+/// unit quaternion, see [`unpack_quaternion_smallest_three_checked`].
+const SMALLEST_THREE_EPSILON: f32 = 1e-4;
+
+/// Like [`unpack_quaternion_smallest_three`], but detects corrupt data.
+///
+/// # Returns
+///
+/// `false` if the squares of the three stored components sum to more than
+/// 1, so they can't be part of a unit quaternion. The largest component is
+/// set to 0 then, leaving a non-unit quaternion.
+#[inline]
+pub fn unpack_quaternion_smallest_three_checked(rotation: &mut [f32], r: &[u8]) -> bool {
+	let sum_squares = unpack_quaternion_smallest_three_with_flip(
+		rotation,
+		r,
+		[1.0_f32, 1.0_f32, 1.0_f32],
+	);
+
+	sum_squares <= 1.0 + SMALLEST_THREE_EPSILON
+}
+
+/// Unpacks a quaternion stored with the _smallest three_ encoding, flipping
+/// the signs of its `x`, `y` and `z` components by `flip_q`.
+///
+/// # Returns
+///
+/// The sum of the squares of the three stored components, at most 1 for
+/// valid data.
 pub fn unpack_quaternion_smallest_three_with_flip(
 	rotation: &mut [f32],
 	r: &[u8],
 	flip_q: [f32; 3],
-) {
+) -> f32 {
 	debug_assert!(rotation.len() >= 4 && r.len() >= 4);
 
 	let mut comp: u32 = (r[0] as u32)
@@ -97,6 +126,7 @@ pub fn unpack_quaternion_smallest_three_with_flip(
 	for i in 0..3 {
 		rotation[i] *= flip_q[i];
 	}
+	sum_squares
 }
 
 pub fn pack_quaternion_smallest_three(rotation: &[f32; 4], flip_q: [f32; 3]) -> [u8; 4] {
@@ -180,6 +210,335 @@ pub fn normalize_quaternion(q: &[f32; 4]) -> [f32; 4] {
 	]
 }
 
+/// Hamilton product `a * b` of two quaternions stored as (x, y, z, w).
+#[inline]
+pub fn multiply_quaternions(a: &[f32; 4], b: &[f32; 4]) -> [f32; 4] {
+	let [ax, ay, az, aw] = *a;
+	let [bx, by, bz, bw] = *b;
+
+	[
+		aw * bx + ax * bw + ay * bz - az * by,
+		aw * by - ax * bz + ay * bw + az * bx,
+		aw * bz + ax * by - ay * bx + az * bw,
+		aw * bw - ax * bx - ay * by - az * bz,
+	]
+}
+
+/// Rotates the vector `v` by the unit quaternion `q` stored as (x, y, z, w).
+#[inline]
+pub fn rotate_vector(q: &[f32; 4], v: &[f32; 3]) -> [f32; 3] {
+	let [qx, qy, qz, qw] = *q;
+
+	// t = 2 * cross(q.xyz, v)
+	let tx = 2.0 * (qy * v[2] - qz * v[1]);
+	let ty = 2.0 * (qz * v[0] - qx * v[2]);
+	let tz = 2.0 * (qx * v[1] - qy * v[0]);
+
+	// v' = v + w * t + cross(q.xyz, t)
+	[
+		v[0] + qw * tx + (qy * tz - qz * ty),
+		v[1] + qw * ty + (qz * tx - qx * tz),
+		v[2] + qw * tz + (qx * ty - qy * tx),
+	]
+}
+
+/// Packs a quaternion into the v2 _first three_ encoding: x, y and z in
+/// `[-1, 1]` quantized to a byte each, with `w` made non-negative so it can
+/// be recovered on unpack.
+pub fn pack_quaternion_first_three(rotation: &[f32; 4], flip_q: [f32; 3]) -> [u8; 3] {
+	let q = normalize_quaternion(rotation);
+	let s = if q[3] < 0.0 { -127.5_f32 } else { 127.5_f32 };
+
+	[
+		to_u8(q[0] * flip_q[0] * s + 127.5),
+		to_u8(q[1] * flip_q[1] * s + 127.5),
+		to_u8(q[2] * flip_q[2] * s + 127.5),
+	]
+}
+
+/// Real spherical harmonics basis constants of band 1, 2 and 3, as used by
+/// the reference Gaussian Splatting renderer.
+const SH_C1: f32 = 0.488_602_5;
+const SH_C2: [f32; 5] = [
+	1.092_548_4,
+	-1.092_548_4,
+	0.315_391_57,
+	-1.092_548_4,
+	0.546_274_2,
+];
+const SH_C3: [f32; 7] = [
+	-0.590_043_6,
+	2.890_611_4,
+	-0.457_045_8,
+	0.373_176_33,
+	-0.457_045_8,
+	1.445_305_7,
+	-0.590_043_6,
+];
+
+/// Evaluates the spherical harmonics basis functions of bands 1 to 3 (the
+/// DC band excluded) for the unit direction `dir`.
+///
+/// # Returns
+///
+/// The 15 basis values, in the order of the coefficients stored in a
+/// [`GaussianSplat`](crate::gaussian_splat::GaussianSplat).
+pub fn sh_basis(dir: &[f32; 3]) -> [f32; 15] {
+	let [x, y, z] = *dir;
+	let (xx, yy, zz) = (x * x, y * y, z * z);
+
+	[
+		-SH_C1 * y,
+		SH_C1 * z,
+		-SH_C1 * x,
+		SH_C2[0] * x * y,
+		SH_C2[1] * y * z,
+		SH_C2[2] * (2.0 * zz - xx - yy),
+		SH_C2[3] * x * z,
+		SH_C2[4] * (xx - yy),
+		SH_C3[0] * y * (3.0 * xx - yy),
+		SH_C3[1] * x * y * z,
+		SH_C3[2] * y * (4.0 * zz - xx - yy),
+		SH_C3[3] * z * (2.0 * zz - 3.0 * xx - 3.0 * yy),
+		SH_C3[4] * x * (4.0 * zz - xx - yy),
+		SH_C3[5] * z * (xx - yy),
+		SH_C3[6] * x * (xx - 3.0 * yy),
+	]
+}
+
+/// Rotates the spherical harmonics coefficients of a single gaussian by
+/// `rotation_quat`, so its view-dependent color follows the rotation.
+///
+/// To rotate many gaussians by the same rotation, build a [`ShRotation`]
+/// once and [apply](ShRotation::apply) it to each of them instead.
+///
+/// # Args
+///
+/// `coeffs` - the coefficients of bands 1 to `degree`, interleaved per
+/// color channel as stored in a
+/// [`GaussianSplat`](crate::gaussian_splat::GaussianSplat), i.e.
+/// `dim_for_degree(degree) * 3` values.
+/// `degree` - spherical harmonics degree, 0 to 3.
+/// `rotation_quat` - rotation as a (x, y, z, w) quaternion, normalized
+/// before use.
+///
+/// # Panics
+///
+/// If `coeffs` holds fewer than `dim_for_degree(degree) * 3` values.
+#[inline]
+pub fn rotate_sh(coeffs: &mut [f32], degree: u8, rotation_quat: [f32; 4]) {
+	ShRotation::new(degree, rotation_quat).apply(coeffs);
+}
+
+/// Per band rotation (Wigner-D) matrices of real spherical harmonics, see
+/// [`rotate_sh`].
+///
+/// Built with the recursion of Ivanic and Ruedenberg, which derives the
+/// matrix of each band from band 1 and the band below it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShRotation {
+	degree: u8,
+	/// Row major `(2l + 1) x (2l + 1)` matrix of band `l`, indexed by
+	/// `m + l` and `n + l`.
+	bands: [[[f32; 7]; 7]; 4],
+}
+
+impl ShRotation {
+	/// Builds the rotation matrices of bands 1 to `degree`, clamped to 3.
+	pub fn new(degree: u8, rotation_quat: [f32; 4]) -> Self {
+		let degree = degree.min(3);
+		let [x, y, z, w] = normalize_quaternion(&rotation_quat);
+		let r = [
+			[
+				1.0 - 2.0 * (y * y + z * z),
+				2.0 * (x * y - z * w),
+				2.0 * (x * z + y * w),
+			],
+			[
+				2.0 * (x * y + z * w),
+				1.0 - 2.0 * (x * x + z * z),
+				2.0 * (y * z - x * w),
+			],
+			[
+				2.0 * (x * z - y * w),
+				2.0 * (y * z + x * w),
+				1.0 - 2.0 * (x * x + y * y),
+			],
+		];
+		let mut bands = [[[0.0_f32; 7]; 7]; 4];
+
+		bands[0][0][0] = 1.0;
+
+		// band 1 basis functions are proportional to (y, z, x)
+		const AXES: [usize; 3] = [1, 2, 0];
+
+		for (i, &a) in AXES.iter().enumerate() {
+			for (j, &b) in AXES.iter().enumerate() {
+				bands[1][i][j] = r[a][b];
+			}
+		}
+		for l in 2..=degree as i32 {
+			for m in -l..=l {
+				for n in -l..=l {
+					let entry = band_entry(&bands, l, m, n);
+
+					bands[l as usize][(m + l) as usize][(n + l) as usize] =
+						entry;
+				}
+			}
+		}
+		// The recursion works on the real basis without the Condon-Shortley
+		// phase, the stored coefficients include it, i.e. odd orders are
+		// negated, see `sh_basis`.
+		for (l, band) in bands.iter_mut().enumerate().skip(1) {
+			for (i, row) in band.iter_mut().take(2 * l + 1).enumerate() {
+				for (j, v) in row.iter_mut().take(2 * l + 1).enumerate() {
+					if (i + j) % 2 == 1 {
+						*v = -*v;
+					}
+				}
+			}
+		}
+		Self { degree, bands }
+	}
+
+	/// Rotates the coefficients of a single gaussian in place, see
+	/// [`rotate_sh`] for the layout of `coeffs`.
+	///
+	/// # Panics
+	///
+	/// If `coeffs` holds fewer than `dim_for_degree(degree) * 3` values.
+	pub fn apply(&self, coeffs: &mut [f32]) {
+		let mut offset = 0_usize;
+
+		for l in 1..=self.degree as usize {
+			let size = 2 * l + 1;
+			let band = &self.bands[l];
+
+			for c in 0..3 {
+				let mut rotated = [0.0_f32; 7];
+
+				for (i, v) in rotated.iter_mut().take(size).enumerate() {
+					*v = (0..size)
+						.map(|j| band[i][j] * coeffs[(offset + j) * 3 + c])
+						.sum();
+				}
+				for (i, v) in rotated.iter().take(size).enumerate() {
+					coeffs[(offset + i) * 3 + c] = *v;
+				}
+			}
+			offset += size;
+		}
+	}
+}
+
+/// Entry `(m, n)` of the band `l` rotation matrix, from band 1 and `l - 1`.
+fn band_entry(bands: &[[[f32; 7]; 7]; 4], l: i32, m: i32, n: i32) -> f32 {
+	let d = if m == 0 { 1.0_f32 } else { 0.0 };
+	let abs_m = m.abs();
+	let denom = if n.abs() == l {
+		(2 * l * (2 * l - 1)) as f32
+	} else {
+		((l + n) * (l - n)) as f32
+	};
+	let p = |i: i32, a: i32, b: i32| band_p(bands, l, i, a, b);
+	let mut entry = 0.0;
+
+	if abs_m < l {
+		let u = (((l + m) * (l - m)) as f32 / denom).sqrt();
+
+		entry += u * p(0, m, n);
+	}
+	let v = 0.5
+		* ((1.0 + d) * ((l + abs_m - 1) * (l + abs_m)) as f32 / denom).sqrt()
+		* (1.0 - 2.0 * d);
+	let big_v = if m == 0 {
+		p(1, 1, n) + p(-1, -1, n)
+	} else if m > 0 {
+		let d1 = if m == 1 { 1.0_f32 } else { 0.0 };
+
+		p(1, m - 1, n) * (1.0 + d1).sqrt() - p(-1, -m + 1, n) * (1.0 - d1)
+	} else {
+		let d1 = if m == -1 { 1.0_f32 } else { 0.0 };
+
+		p(1, m + 1, n) * (1.0 - d1) + p(-1, -m - 1, n) * (1.0 + d1).sqrt()
+	};
+	entry += v * big_v;
+
+	if m != 0 && abs_m < l - 1 {
+		let w = -0.5 * (((l - abs_m - 1) * (l - abs_m)) as f32 / denom).sqrt();
+		let big_w = if m > 0 {
+			p(1, m + 1, n) + p(-1, -m - 1, n)
+		} else {
+			p(1, m - 1, n) - p(-1, -m + 1, n)
+		};
+		entry += w * big_w;
+	}
+	entry
+}
+
+/// The `P` helper of the Ivanic-Ruedenberg recursion.
+fn band_p(bands: &[[[f32; 7]; 7]; 4], l: i32, i: i32, a: i32, b: i32) -> f32 {
+	let r1 = |x: i32, y: i32| bands[1][(x + 1) as usize][(y + 1) as usize];
+	let prev = |x: i32, y: i32| {
+		let k = l - 1;
+
+		bands[k as usize][(x + k) as usize][(y + k) as usize]
+	};
+
+	if b == l {
+		r1(i, 1) * prev(a, l - 1) - r1(i, -1) * prev(a, -l + 1)
+	} else if b == -l {
+		r1(i, 1) * prev(a, -l + 1) + r1(i, -1) * prev(a, l - 1)
+	} else {
+		r1(i, 0) * prev(a, b)
+	}
+}
+
+/// Returns `n` unit directions spread evenly over the sphere, on a
+/// Fibonacci lattice. Deterministic, unlike random sampling.
+pub fn fibonacci_sphere(n: usize) -> Vec<[f32; 3]> {
+	let golden_angle = std::f32::consts::PI * (3.0 - 5.0_f32.sqrt());
+
+	(0..n).map(|i| {
+		let y = 1.0 - 2.0 * (i as f32 + 0.5) / n as f32;
+		let r = (1.0 - y * y).max(0.0).sqrt();
+		let theta = golden_angle * i as f32;
+
+		[r * theta.cos(), y, r * theta.sin()]
+	})
+	.collect()
+}
+
+/// Small, fast, deterministic pseudo random number generator (SplitMix64),
+/// for reproducible sampling. Not suitable for cryptography.
+#[derive(Clone, Debug)]
+pub struct SplitMix64(u64);
+
+impl SplitMix64 {
+	#[inline]
+	pub fn new(seed: u64) -> Self {
+		Self(seed)
+	}
+
+	#[inline]
+	pub fn next_u64(&mut self) -> u64 {
+		self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+		let mut z = self.0;
+
+		z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+		z ^ (z >> 31)
+	}
+
+	/// Returns a uniformly distributed value in `(0, 1]`.
+	#[inline]
+	pub fn next_f64(&mut self) -> f64 {
+		((self.next_u64() >> 11) + 1) as f64 / (1_u64 << 53) as f64
+	}
+}
+
 #[inline]
 pub fn to_u8(x: f32) -> u8 {
 	x.clamp(0.0, 255.0).round() as u8
@@ -322,6 +681,39 @@ mod tests {
 		assert_relative_eq!(norm_sq, 1.0, epsilon = 1e-6);
 	}
 
+	#[rstest]
+	#[case([0.0, 0.0, 0.0, 1.0], [0.3, -0.1, 0.2, 0.9], [0.3, -0.1, 0.2, 0.9])]
+	#[case([0.3, -0.1, 0.2, 0.9], [0.0, 0.0, 0.0, 1.0], [0.3, -0.1, 0.2, 0.9])]
+	#[case([0.0, 0.0, 1.0, 0.0], [0.0, 0.0, 1.0, 0.0], [0.0, 0.0, 0.0, -1.0])]
+	#[case([1.0, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0, 0.0], [0.0, 0.0, 1.0, 0.0])]
+	fn test_multiply_quaternions(
+		#[case] a: [f32; 4],
+		#[case] b: [f32; 4],
+		#[case] expected: [f32; 4],
+	) {
+		let q = multiply_quaternions(&a, &b);
+
+		for i in 0..4 {
+			assert_relative_eq!(q[i], expected[i], epsilon = 1e-6);
+		}
+	}
+
+	#[rstest]
+	#[case([0.0, 0.0, FRAC_1_SQRT_2, FRAC_1_SQRT_2], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0])]
+	#[case([FRAC_1_SQRT_2, 0.0, 0.0, FRAC_1_SQRT_2], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0])]
+	#[case([0.0, 0.0, 0.0, 1.0], [1.0, 2.0, 3.0], [1.0, 2.0, 3.0])]
+	fn test_rotate_vector(
+		#[case] q: [f32; 4],
+		#[case] v: [f32; 3],
+		#[case] expected: [f32; 3],
+	) {
+		let r = rotate_vector(&q, &v);
+
+		for i in 0..3 {
+			assert_relative_eq!(r[i], expected[i], epsilon = 1e-6);
+		}
+	}
+
 	#[rstest]
 	#[case(0.0, 0)]
 	#[case(127.5, 128)]
@@ -353,6 +745,27 @@ mod tests {
 		assert_relative_eq!(dot.abs(), 1.0, epsilon = 0.01);
 	}
 
+	#[test]
+	fn test_unpack_quaternion_smallest_three_checked() {
+		let valid = pack_quaternion_smallest_three(&[0.5, 0.5, 0.5, 0.5], [1.0; 3]);
+		let mut unpacked = [0.0_f32; 4];
+
+		assert!(unpack_quaternion_smallest_three_checked(
+			&mut unpacked,
+			&valid
+		));
+
+		// largest is w, x, y and z all stored with the maximum magnitude of
+		// 1/sqrt(2), their squares sum to 1.5
+		let corrupt = (0x1ff_u32 | (0x1ff << 10) | (0x1ff << 20) | (3 << 30)).to_le_bytes();
+
+		assert!(!unpack_quaternion_smallest_three_checked(
+			&mut unpacked,
+			&corrupt
+		));
+		assert_eq!(unpacked[3], 0.0);
+	}
+
 	#[test]
 	fn test_pack_unpack_quaternion_with_flip() {
 		let q = [0.1_f32, 0.2, 0.3, 0.9];
@@ -368,6 +781,23 @@ mod tests {
 		assert_relative_eq!(dot.abs(), 1.0, epsilon = 0.02);
 	}
 
+	#[rstest]
+	#[case([0.0, 0.0, 0.0, 1.0])]
+	#[case([0.0, 0.0, 0.0, -1.0])]
+	#[case([0.1, 0.2, 0.3, 0.9])]
+	#[case([0.5, -0.5, 0.5, -0.5])]
+	fn test_pack_unpack_quaternion_first_three_roundtrip(#[case] input: [f32; 4]) {
+		let normed = normalize_quaternion(&input);
+		let packed = pack_quaternion_first_three(&normed, [1.0, 1.0, 1.0]);
+		let mut unpacked = [0.0_f32; 4];
+
+		unpack_quaternion_first_three(&mut unpacked, &packed);
+
+		let dot: f32 = normed.iter().zip(unpacked.iter()).map(|(a, b)| a * b).sum();
+
+		assert_relative_eq!(dot.abs(), 1.0, epsilon = 0.01);
+	}
+
 	#[rstest]
 	#[case([128_u8, 128, 128], [0.0, 0.0, 0.0, 1.0])]
 	fn test_unpack_quaternion_first_three_identity(
@@ -395,4 +825,110 @@ mod tests {
 		assert_relative_eq!(norm, 1.0, epsilon = 0.01);
 		assert!(rot[3] >= 0.0, "w component must be non-negative");
 	}
+
+	#[test]
+	fn test_sh_basis() {
+		let basis = sh_basis(&[0.0, 0.0, 1.0]);
+
+		assert_relative_eq!(basis[0], 0.0);
+		assert_relative_eq!(basis[1], SH_C1);
+		assert_relative_eq!(basis[2], 0.0);
+		assert_relative_eq!(basis[5], 2.0 * SH_C2[2]);
+		assert_relative_eq!(basis[11], 2.0 * SH_C3[3]);
+	}
+
+	#[test]
+	fn test_fibonacci_sphere() {
+		let dirs = fibonacci_sphere(64);
+
+		assert_eq!(dirs.len(), 64);
+		for d in &dirs {
+			assert_relative_eq!(
+				(d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt(),
+				1.0,
+				epsilon = 1e-5
+			);
+		}
+		let mean_y = dirs.iter().map(|d| d[1]).sum::<f32>() / dirs.len() as f32;
+
+		assert_relative_eq!(mean_y, 0.0, epsilon = 1e-5);
+	}
+
+	#[test]
+	fn test_split_mix_64() {
+		let mut a = SplitMix64::new(42);
+		let mut b = SplitMix64::new(42);
+
+		for _ in 0..100 {
+			let x = a.next_f64();
+
+			assert_eq!(x, b.next_f64());
+			assert!(x > 0.0 && x <= 1.0);
+		}
+		assert_ne!(SplitMix64::new(1).next_u64(), SplitMix64::new(2).next_u64());
+	}
+
+	/// Evaluates bands 1 to 3 of channel `c` in direction `dir`.
+	fn eval_sh(coeffs: &[f32], c: usize, dir: &[f32; 3]) -> f32 {
+		sh_basis(dir)
+			.iter()
+			.enumerate()
+			.map(|(k, b)| b * coeffs[k * 3 + c])
+			.sum()
+	}
+
+	#[test]
+	fn test_rotate_sh_matches_rotated_function() {
+		let mut rng = SplitMix64::new(7);
+		let original: Vec<f32> = (0..45).map(|_| rng.next_f64() as f32 - 0.5).collect();
+		let q = normalize_quaternion(&[0.3, -0.5, 0.2, 0.8]);
+		let mut rotated = original.clone();
+
+		rotate_sh(&mut rotated, 3, q);
+
+		// the rotated function seen from the rotated direction matches
+		// the original one
+		for dir in fibonacci_sphere(32) {
+			let rotated_dir = rotate_vector(&q, &dir);
+
+			for c in 0..3 {
+				assert_relative_eq!(
+					eval_sh(&rotated, c, &rotated_dir),
+					eval_sh(&original, c, &dir),
+					epsilon = 1e-4
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn test_rotate_sh_z_roundtrip() {
+		let half = FRAC_1_SQRT_2;
+		let original: Vec<f32> = (0..45).map(|i| (i as f32 * 0.37).sin()).collect();
+		let mut coeffs = original.clone();
+
+		rotate_sh(&mut coeffs, 3, [0.0, 0.0, half, half]);
+
+		assert!(coeffs
+			.iter()
+			.zip(&original)
+			.any(|(a, b)| (a - b).abs() > 1e-3));
+
+		rotate_sh(&mut coeffs, 3, [0.0, 0.0, -half, half]);
+
+		for (a, b) in coeffs.iter().zip(&original) {
+			assert_relative_eq!(a, b, epsilon = 1e-5);
+		}
+	}
+
+	#[test]
+	fn test_rotate_sh_degree_0_invariant() {
+		let original = [0.1_f32, 0.2, 0.3, 0.4];
+		let mut coeffs = original;
+
+		rotate_sh(&mut coeffs, 0, [0.3, -0.5, 0.2, 0.8]);
+		assert_eq!(coeffs, original);
+
+		rotate_sh(&mut [], 0, [0.3, -0.5, 0.2, 0.8]);
+	}
 }
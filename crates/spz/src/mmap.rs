@@ -1,14 +1,23 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use std::{fs::File, path::Path};
+use std::{fs::File, io, path::Path};
 
-use anyhow::Context;
-use anyhow::Result;
 use memmap2::Mmap;
 
 /// Memory-maps a file for efficient read-only access.
+///
+/// # Args
+///
+/// `filepath` - the file to map.
+/// `sequential` - hint the kernel with `madvise(MADV_SEQUENTIAL)` that the
+///   whole mapping is read once from start to end, e.g. for decompression,
+///   so it can read ahead aggressively. Not done on macOS and non-unix
+///   targets. A failing hint is ignored.
+///
+/// Fails with the [`io::Error`] of opening or mapping the file, so a missing
+/// file keeps its [`io::ErrorKind::NotFound`].
 #[inline]
-pub fn mmap<F>(filepath: F) -> Result<Mmap>
+pub fn mmap<F>(filepath: F, sequential: bool) -> io::Result<Mmap>
 where
 	F: AsRef<Path>,
 {
@@ -16,12 +25,28 @@ where
 
 	// SAFETY: The file handle remains alive for the duration of the mmap,
 	// and the returned mapping is read-only so no aliasing or mutation is introduced.
-	unsafe { Mmap::map(&infile).with_context(|| "unable to open file with mmap()") }
+	let map = unsafe { Mmap::map(&infile) }.map_err(|err| {
+		io::Error::new(
+			err.kind(),
+			format!("unable to open file with mmap(): {err}"),
+		)
+	})?;
+
+	#[cfg(all(unix, not(target_os = "macos")))]
+	if sequential {
+		let _ = map.advise(memmap2::Advice::Sequential);
+	}
+	#[cfg(not(all(unix, not(target_os = "macos"))))]
+	let _ = sequential;
+
+	Ok(map)
 }
 
 /// Memory-maps a file for efficient read-only access with a specified range.
+///
+/// Fails like [`mmap`].
 #[inline]
-pub fn mmap_range<F>(filepath: F, offset: usize, len: usize) -> Result<Mmap>
+pub fn mmap_range<F>(filepath: F, offset: usize, len: usize) -> io::Result<Mmap>
 where
 	F: AsRef<Path>,
 {
@@ -34,13 +59,16 @@ where
 			.offset(offset as u64)
 			.len(len)
 			.map(&infile)
-			.with_context(|| {
-				format!(
-					"unable to open file with mmap() (range {}..{}, len {})",
-					offset,
-					offset + len,
-					len
-				)
-			})
 	}
+	.map_err(|err| {
+		io::Error::new(
+			err.kind(),
+			format!(
+				"unable to open file with mmap() (range {}..{}, len {}): {err}",
+				offset,
+				offset + len,
+				len
+			),
+		)
+	})
 }
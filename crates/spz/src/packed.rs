@@ -16,15 +16,20 @@ use std::io::BufReader;
 use std::io::Read;
 use std::io::Write;
 
+use anyhow::Result;
 use anyhow::bail;
-use anyhow::{Context, Result};
 use arbitrary::Arbitrary;
-use likely_stable::{if_unlikely, unlikely};
+use likely_stable::unlikely;
 use serde::{Deserialize, Serialize};
 
-use crate::header::Header;
+use crate::compression::Compression;
+use crate::error::SpzError;
+use crate::header::{HEADER_SIZE, Header};
 use crate::{consts, math};
-use crate::{coord::AxisFlips, unpacked::UnpackedGaussian};
+use crate::{
+	coord::{AxisFlips, CoordinateSystem},
+	unpacked::{UnpackOptions, UnpackedGaussian},
+};
 
 static_assertions::const_assert_eq!(std::mem::size_of::<PackedGaussian>(), 65);
 
@@ -157,33 +162,153 @@ pub struct PackedGaussianSplat {
 }
 
 impl PackedGaussianSplat {
-	/// Deserializes packed Gaussian data from gzip-compressed bytes.
+	/// Deserializes packed Gaussian data from compressed bytes.
 	///
-	/// `bytes` - gzip compressed, packed gaussian data.
-	pub fn from_bytes<B>(bytes: B) -> Result<Self>
+	/// The compression is detected from the magic bytes.
+	///
+	/// `bytes` - gzip (or zstd) compressed, packed gaussian data.
+	#[inline]
+	pub fn from_bytes<B>(bytes: B) -> Result<Self, SpzError>
+	where
+		B: AsRef<[u8]>,
+	{
+		Self::from_compressed_bytes(bytes, None)
+	}
+
+	/// Deserializes packed Gaussian data from compressed bytes.
+	///
+	/// `bytes` - compressed, packed gaussian data.
+	/// `compression` - compression of `bytes`, detected from the magic
+	/// bytes if `None`.
+	#[inline]
+	pub fn from_compressed_bytes<B>(
+		bytes: B,
+		compression: Option<Compression>,
+	) -> Result<Self, SpzError>
+	where
+		B: AsRef<[u8]>,
+	{
+		Self::from_compressed_bytes_with_buf(bytes, compression, &mut Vec::new())
+	}
+
+	/// Deserializes packed Gaussian data from compressed bytes, like
+	/// [`Self::from_bytes`], but decompresses into `scratch` to reuse its
+	/// allocation across calls.
+	///
+	/// `bytes` - gzip (or zstd) compressed, packed gaussian data.
+	/// `scratch` - buffer for the decompressed data, its contents are
+	/// replaced.
+	#[inline]
+	pub fn from_bytes_with_buf<B>(bytes: B, scratch: &mut Vec<u8>) -> Result<Self, SpzError>
+	where
+		B: AsRef<[u8]>,
+	{
+		Self::from_compressed_bytes_with_buf(bytes, None, scratch)
+	}
+
+	/// Deserializes packed Gaussian data from compressed bytes, like
+	/// [`Self::from_compressed_bytes`], but decompresses into `scratch` to
+	/// reuse its allocation across calls.
+	///
+	/// `bytes` - compressed, packed gaussian data.
+	/// `compression` - compression of `bytes`, detected from the magic
+	/// bytes if `None`.
+	/// `scratch` - buffer for the decompressed data, its contents are
+	/// replaced.
+	pub fn from_compressed_bytes_with_buf<B>(
+		bytes: B,
+		compression: Option<Compression>,
+		scratch: &mut Vec<u8>,
+	) -> Result<Self, SpzError>
 	where
 		B: AsRef<[u8]>,
 	{
 		if unlikely(bytes.as_ref().is_empty()) {
 			// we cannot return an empty struct as there is no header
-			bail!("data is empty");
+			return Err(SpzError::Empty);
 		}
-		let mut decompressed = Vec::<u8>::new();
+		scratch.clear();
 
-		crate::compression::gzip::decompress_end(bytes, &mut decompressed)
-			.with_context(|| "unable to decompress gzip data")?;
+		crate::compression::decompress_end(bytes, &mut *scratch, compression)
+			.map_err(SpzError::Decompress)?;
 
-		let packed: Self = decompressed
-			.try_into()
-			.with_context(|| "unable to parse packed gaussian data")?;
+		Self::try_from(scratch.as_slice())
+	}
+
+	/// Deserializes decompressed, packed Gaussian data incrementally from
+	/// `reader`.
+	///
+	/// Unlike parsing from a byte slice, the total length is unknown
+	/// upfront, so each section grows as its data arrives instead of
+	/// trusting the header with the allocation size.
+	///
+	/// Fails with [`SpzError::TooManyPoints`] right after the header if it
+	/// claims more than `max_points` points, before reading any section.
+	///
+	/// # Args
+	///
+	/// `reader` - decompressed, packed gaussian data.
+	/// `max_points` - the maximum number of points to accept, see
+	///   [`LoadOptions::max_points`](crate::gaussian_splat::LoadOptions::max_points).
+	pub fn read_from<R>(mut reader: R, max_points: usize) -> Result<Self, SpzError>
+	where
+		R: Read,
+	{
+		let mut header_buf = [0_u8; HEADER_SIZE];
+
+		read_section(&mut reader, &mut header_buf, "header")?;
+
+		let header = Header::parse(&header_buf)?;
+		let num_points = header.num_points as usize;
 
-		Ok(packed)
+		if unlikely(num_points > max_points) {
+			return Err(SpzError::TooManyPoints {
+				num_points: header.num_points,
+				max: max_points,
+			});
+		}
+		let uses_quaternion_smallest_three =
+			is_encoding_quaternion_smallest_three_used(header.version);
+		let rotation_bytes = if uses_quaternion_smallest_three { 4 } else { 3 };
+		let sh_bytes = sh_bytes_per_point(header.spherical_harmonics_degree);
+
+		let positions = read_section_to_vec(&mut reader, num_points * 9, "positions")?;
+		let alphas = read_section_to_vec(&mut reader, num_points, "alphas")?;
+		let colors = read_section_to_vec(&mut reader, num_points * 3, "colors")?;
+		let scales = read_section_to_vec(&mut reader, num_points * 3, "scales")?;
+		let rotations =
+			read_section_to_vec(&mut reader, num_points * rotation_bytes, "rotations")?;
+		let spherical_harmonics = read_section_to_vec(
+			&mut reader,
+			num_points * sh_bytes,
+			"spherical harmonics",
+		)?;
+
+		Ok(PackedGaussianSplat {
+			num_points: header.num_points,
+			sh_degree: header.spherical_harmonics_degree as i32,
+			fractional_bits: header.fractional_bits as i32,
+			antialiased: header.flags.is_antialiased(),
+			uses_quaternion_smallest_three,
+
+			positions,
+			scales,
+			rotations,
+			alphas,
+			colors,
+			spherical_harmonics,
+		})
 	}
 
 	/// Constructs an SPZ header from this packed data's metadata.
 	#[inline]
 	pub fn to_header(&self) -> Header {
 		Header {
+			version: if self.uses_quaternion_smallest_three {
+				crate::header::Version::V3
+			} else {
+				crate::header::Version::V2
+			},
 			num_points: self.num_points,
 			spherical_harmonics_degree: self.sh_degree as u8,
 			fractional_bits: self.fractional_bits as u8,
@@ -216,11 +341,13 @@ impl PackedGaussianSplat {
 	}
 
 	/// Writes this packed data to a writer in SPZ format.
+	///
+	/// Doesn't flush `stream`, so it can be a compressing encoder.
 	pub fn write_self_to<W>(&self, stream: &mut W) -> Result<()>
 	where
-		W: Write,
+		W: Write + ?Sized,
 	{
-		self.to_header().serialize_to(stream)?;
+		stream.write_all(zerocopy::IntoBytes::as_bytes(&self.to_header()))?;
 
 		stream.write_all(&self.positions)?;
 		stream.write_all(&self.alphas)?;
@@ -232,6 +359,19 @@ impl PackedGaussianSplat {
 		Ok(())
 	}
 
+	/// Serializes to a gzip compressed SPZ file, as is, without unpacking.
+	///
+	/// Unlike a [`GaussianSplat`](crate::gaussian_splat::GaussianSplat)
+	/// round trip through
+	/// [`serialize_to_packed_bytes`](crate::gaussian_splat::GaussianSplat::serialize_to_packed_bytes),
+	/// the quantized arrays are kept bit for bit, so repacking only changes
+	/// the compression container and never accumulates quantization error.
+	pub fn recompress(&self) -> Result<Vec<u8>> {
+		crate::compression::compress_to(Vec::new(), Compression::Gzip, |w| {
+			self.write_self_to(w)
+		})
+	}
+
 	/// Returns the packed data for a single splat at index `i`.
 	pub fn at(&self, i: usize) -> Result<PackedGaussian> {
 		if unlikely(i >= self.num_points as usize) {
@@ -295,6 +435,50 @@ impl PackedGaussianSplat {
 		Ok(result)
 	}
 
+	/// Decodes only the positions of all gaussians, `(x, y, z)` each,
+	/// applying `coord_flip`.
+	///
+	/// Together with [`Self::decode_colors_only`] and
+	/// [`Self::decode_alphas_only`], viewers can get the geometry quickly and
+	/// defer the rest. The values are identical to those of a full
+	/// [`GaussianSplat`](crate::gaussian_splat::GaussianSplat) load.
+	pub fn decode_positions_only(&self, coord_flip: &AxisFlips) -> Vec<f32> {
+		let scale = 1.0_f32 / (1_u32 << (self.fractional_bits as u32)) as f32;
+
+		self.positions
+			.chunks_exact(3)
+			.enumerate()
+			.map(|(i, src)| {
+				let mut fixed32 = src[0] as i32
+					| ((src[1] as i32) << 8) | ((src[2] as i32)
+					<< 16);
+
+				if (fixed32 & 0x800000) != 0 {
+					fixed32 |= 0xff000000_u32 as i32;
+				}
+				coord_flip.position[i % 3] * (fixed32 as f32 * scale)
+			})
+			.collect()
+	}
+
+	/// Decodes only the DC colors of all gaussians, `(r, g, b)` each.
+	///
+	/// Colors don't depend on the coordinate system.
+	pub fn decode_colors_only(&self) -> Vec<f32> {
+		self.colors
+			.iter()
+			.map(|c| ((*c as f32 / 255.0) - 0.5) / consts::COLOR_SCALE)
+			.collect()
+	}
+
+	/// Decodes only the alphas (opacity logits) of all gaussians.
+	pub fn decode_alphas_only(&self) -> Vec<f32> {
+		self.alphas
+			.iter()
+			.map(|a| math::inv_sigmoid(*a as f32 / 255.0))
+			.collect()
+	}
+
 	/// Unpacks a single splat at index `i` with coordinate transformation.
 	///
 	/// Applies the given axis flips during decompression.
@@ -307,6 +491,13 @@ impl PackedGaussianSplat {
 		)
 	}
 
+	/// Unpacks a single splat at index `i`, converting it to the coordinate
+	/// system of `opts`.
+	#[inline]
+	pub fn unpack_with(&self, i: usize, opts: &UnpackOptions) -> Result<UnpackedGaussian> {
+		self.unpack(i, &opts.axis_flips())
+	}
+
 	/// Validates that all internal arrays have the expected sizes.
 	///
 	/// Returns `true` if sizes match the expected layout for the given
@@ -338,7 +529,7 @@ impl PackedGaussianSplat {
 }
 
 impl TryFrom<Vec<u8>> for PackedGaussianSplat {
-	type Error = anyhow::Error;
+	type Error = SpzError;
 
 	fn try_from(b: Vec<u8>) -> Result<Self, Self::Error> {
 		Self::try_from(b.as_slice())
@@ -346,21 +537,32 @@ impl TryFrom<Vec<u8>> for PackedGaussianSplat {
 }
 
 impl TryFrom<&[u8]> for PackedGaussianSplat {
-	type Error = anyhow::Error;
+	type Error = SpzError;
 
 	fn try_from(b: &[u8]) -> Result<Self, Self::Error> {
 		let mut from_reader = BufReader::new(b);
 
-		let header = Header::read_from(&mut from_reader)
-			.with_context(|| "unable to read packed gaussians header")?;
+		let mut header_buf = [0_u8; HEADER_SIZE];
 
-		if unlikely(!header.is_valid()) {
-			bail!("invalid header");
-		}
+		read_section(&mut from_reader, &mut header_buf, "header")?;
+
+		let header = Header::parse(&header_buf)?;
 		let num_points = header.num_points;
 		let uses_quaternion_smallest_three =
 			is_encoding_quaternion_smallest_three_used(header.version);
 
+		// Don't trust the header with the allocation size, the sections
+		// have to fit into the rest of the data.
+		let rotation_bytes = if uses_quaternion_smallest_three { 4 } else { 3 };
+		let sh_bytes = sh_bytes_per_point(header.spherical_harmonics_degree);
+		let max_points = (b.len() - HEADER_SIZE) / header.packed_bytes_per_point();
+
+		if unlikely(num_points as usize > max_points) {
+			return Err(SpzError::NumPointsExceedsData {
+				num_points,
+				max: max_points,
+			});
+		}
 		let mut result = PackedGaussianSplat {
 			num_points,
 			sh_degree: header.spherical_harmonics_degree as i32,
@@ -370,49 +572,62 @@ impl TryFrom<&[u8]> for PackedGaussianSplat {
 
 			positions: vec![0; num_points as usize * 9],
 			scales: vec![0; (num_points as usize) * 3],
-			rotations: vec![
-				0;
-				num_points as usize
-					* if uses_quaternion_smallest_three { 4 } else { 3 }
-			],
+			rotations: vec![0; num_points as usize * rotation_bytes],
 			alphas: vec![0; num_points as usize],
 			colors: vec![0; (num_points as usize) * 3],
-			spherical_harmonics: vec![
-				0;
-				num_points as usize
-					* math::dim_for_degree(header.spherical_harmonics_degree)
-						as usize * 3
-			],
+			spherical_harmonics: vec![0; num_points as usize * sh_bytes],
 		};
-		// despite the `err` from `let Err(err) = ...` is used in the if
-		// block, it produces an `unused_variables` warning because of a
-		// bug in the `likely` crate.
-		// https://gitlab.com/okannen/likely/-/merge_requests/1
-		#[allow(unused_variables)]
-		{
-			if_unlikely! { let Err(err) = from_reader.read_exact(&mut result.positions) => {
-				bail!("read error (positions): {}", err);
-			}};
-			if_unlikely! { let Err(err) = from_reader.read_exact(&mut result.alphas) => {
-				bail!("read error (alphas): {}", err);
-			}};
-			if_unlikely! { let Err(err) = from_reader.read_exact(&mut result.colors) => {
-				bail!("read error (colors): {}", err);
-			}};
-			if_unlikely! { let Err(err) = from_reader.read_exact(&mut result.scales) => {
-				bail!("read error (scales): {}", err);
-			}};
-			if_unlikely! { let Err(err) = from_reader.read_exact(&mut result.rotations) => {
-				bail!("read error (rotations): {}", err);
-			}};
-			if_unlikely! { let Err(err) = from_reader.read_exact(&mut result.spherical_harmonics) => {
-				bail!("read error (spherical harmonics): {}", err);
-			}};
-		}
+		read_section(&mut from_reader, &mut result.positions, "positions")?;
+		read_section(&mut from_reader, &mut result.alphas, "alphas")?;
+		read_section(&mut from_reader, &mut result.colors, "colors")?;
+		read_section(&mut from_reader, &mut result.scales, "scales")?;
+		read_section(&mut from_reader, &mut result.rotations, "rotations")?;
+		read_section(
+			&mut from_reader,
+			&mut result.spherical_harmonics,
+			"spherical harmonics",
+		)?;
+
 		Ok(result)
 	}
 }
 
+/// Fills `buf` from `reader`, naming `section` if the data ends early.
+#[inline]
+fn read_section<R>(reader: &mut R, buf: &mut [u8], section: &'static str) -> Result<(), SpzError>
+where
+	R: Read,
+{
+	reader.read_exact(buf)
+		.map_err(|source| SpzError::Truncated { section, source })
+}
+
+/// Reads `len` bytes of `section` from `reader`, growing the buffer as the
+/// data arrives.
+#[inline]
+fn read_section_to_vec<R>(
+	reader: &mut R,
+	len: usize,
+	section: &'static str,
+) -> Result<Vec<u8>, SpzError>
+where
+	R: Read,
+{
+	let mut buf = Vec::new();
+
+	reader.take(len as u64)
+		.read_to_end(&mut buf)
+		.map_err(|source| SpzError::Truncated { section, source })?;
+
+	if unlikely(buf.len() < len) {
+		return Err(SpzError::Truncated {
+			section,
+			source: std::io::ErrorKind::UnexpectedEof.into(),
+		});
+	}
+	Ok(buf)
+}
+
 /// Returns `true` if _smallest-three quaternion encoding_ is used in the given
 /// version.
 #[inline]
@@ -423,6 +638,57 @@ pub fn is_encoding_quaternion_smallest_three_used(version: crate::header::Versio
 	}
 }
 
+/// Returns the number of packed spherical harmonics bytes per gaussian for
+/// the given degree, i.e. one byte per coefficient and color channel.
+#[inline]
+pub fn sh_bytes_per_point(degree: u8) -> usize {
+	math::dim_for_degree(degree) as usize * 3
+}
+
+/// Options for packing gaussians into the SPZ internal coordinate system
+/// (RightUpBack|RUB), see
+/// [`GaussianSplat::pack_with`](crate::gaussian_splat::GaussianSplat::pack_with).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Arbitrary)]
+pub struct PackOptions {
+	/// Coordinate system the source data is in.
+	pub from: CoordinateSystem,
+}
+
+impl PackOptions {
+	/// Creates a new [`PackOptionsBuilder`].
+	#[inline]
+	pub fn builder() -> PackOptionsBuilder {
+		PackOptionsBuilder::default()
+	}
+
+	/// Axis flips from [`Self::from`] to the SPZ internal coordinate system.
+	#[inline]
+	pub fn axis_flips(&self) -> AxisFlips {
+		self.from.axis_flips_to(CoordinateSystem::RightUpBack)
+	}
+}
+
+/// Builder for [`PackOptions`].
+#[derive(Clone, Debug, Default, Arbitrary)]
+pub struct PackOptionsBuilder {
+	from: CoordinateSystem,
+}
+
+impl PackOptionsBuilder {
+	/// Sets the coordinate system the source data is in.
+	#[inline]
+	pub fn from(mut self, coord_sys: CoordinateSystem) -> Self {
+		self.from = coord_sys;
+		self
+	}
+
+	/// Builds the [`PackOptions`].
+	#[inline]
+	pub fn build(self) -> PackOptions {
+		PackOptions { from: self.from }
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -565,6 +831,39 @@ mod tests {
 		assert_eq!(bytes, stream);
 	}
 
+	#[test]
+	fn test_recompress_roundtrip() {
+		let packed = PackedGaussianSplat {
+			num_points: 2,
+			sh_degree: 1,
+			fractional_bits: 12,
+			antialiased: true,
+			uses_quaternion_smallest_three: true,
+			positions: (0..18).collect(),
+			scales: vec![20; 6],
+			rotations: vec![30, 31, 32, 33, 34, 35, 36, 37],
+			alphas: vec![40, 41],
+			colors: vec![50; 6],
+			spherical_harmonics: (100..118).collect(),
+		};
+		let mut compressed = Vec::new();
+
+		crate::compression::gzip::compress_bytes(
+			&packed.to_bytes_vec().unwrap(),
+			&mut compressed,
+		)
+		.unwrap();
+
+		let loaded = PackedGaussianSplat::from_bytes(&compressed).unwrap();
+		let recompressed = loaded.recompress().unwrap();
+
+		assert_eq!(Compression::detect(&recompressed), Some(Compression::Gzip));
+		assert_eq!(
+			PackedGaussianSplat::from_bytes(&recompressed).unwrap(),
+			packed
+		);
+	}
+
 	#[rstest]
 	#[case(1)]
 	#[case(100)]
@@ -628,7 +927,13 @@ mod tests {
 	#[case(vec![])]
 	#[case(vec![0_u8; 8])]
 	fn test_try_from_too_short(#[case] bytes: Vec<u8>) {
-		assert!(PackedGaussianSplat::try_from(bytes).is_err());
+		assert!(matches!(
+			PackedGaussianSplat::try_from(bytes),
+			Err(SpzError::Truncated {
+				section: "header",
+				..
+			})
+		));
 	}
 
 	#[test]
@@ -636,7 +941,85 @@ mod tests {
 		let mut bytes = [0_u8; 16];
 		bytes[0..4].copy_from_slice(&(0xDEADBEEF_u32 as i32).to_le_bytes());
 
-		assert!(PackedGaussianSplat::try_from(bytes.to_vec()).is_err());
+		assert!(matches!(
+			PackedGaussianSplat::try_from(bytes.to_vec()),
+			Err(SpzError::BadMagic(magic)) if magic == 0xDEADBEEF_u32 as i32
+		));
+	}
+
+	#[rstest]
+	#[case(1)]
+	#[case(4)]
+	#[case(99)]
+	fn test_try_from_unsupported_version(#[case] version: i32) {
+		let mut bytes: [u8; 16] = Header::default().into();
+		bytes[4..8].copy_from_slice(&version.to_le_bytes());
+
+		assert!(matches!(
+			PackedGaussianSplat::try_from(bytes.as_slice()),
+			Err(SpzError::UnsupportedVersion(v)) if v == version
+		));
+	}
+
+	#[test]
+	fn test_try_from_invalid_flags() {
+		let header = Header {
+			flags: Flags(0x80),
+			..Default::default()
+		};
+		let bytes: [u8; 16] = header.into();
+
+		assert!(matches!(
+			PackedGaussianSplat::try_from(bytes.as_slice()),
+			Err(SpzError::HeaderValidation)
+		));
+	}
+
+	#[test]
+	fn test_try_from_truncated_body() {
+		let header = Header {
+			num_points: 2,
+			..Default::default()
+		};
+		let mut bytes: Vec<u8> = <[u8; 16]>::from(header).to_vec();
+		bytes.extend_from_slice(&[0; 9]);
+
+		assert!(matches!(
+			PackedGaussianSplat::try_from(bytes),
+			Err(SpzError::NumPointsExceedsData {
+				num_points: 2,
+				max: 0
+			})
+		));
+	}
+
+	#[test]
+	fn test_try_from_forged_num_points() {
+		let header = Header {
+			num_points: 2_000_000_000,
+			spherical_harmonics_degree: 3,
+			..Default::default()
+		};
+		let mut bytes: Vec<u8> = <[u8; 16]>::from(header).to_vec();
+		bytes.extend_from_slice(&[0; 65 * 3]);
+
+		assert!(matches!(
+			PackedGaussianSplat::try_from(bytes.as_slice()),
+			Err(SpzError::NumPointsExceedsData {
+				num_points: 2_000_000_000,
+				max: 3
+			})
+		));
+
+		let mut compressed = Vec::new();
+
+		crate::compression::compress_bytes(&bytes, &mut compressed, Compression::Gzip)
+			.unwrap();
+
+		assert!(matches!(
+			PackedGaussianSplat::from_bytes(&compressed),
+			Err(SpzError::NumPointsExceedsData { .. })
+		));
 	}
 
 	#[test]
@@ -658,10 +1041,125 @@ mod tests {
 		assert!(packed.positions.is_empty());
 	}
 
+	#[test]
+	fn test_from_bytes_empty() {
+		assert!(matches!(
+			PackedGaussianSplat::from_bytes([]),
+			Err(SpzError::Empty)
+		));
+	}
+
+	#[test]
+	fn test_from_bytes_not_compressed() {
+		assert!(matches!(
+			PackedGaussianSplat::from_bytes([1_u8, 2, 3, 4]),
+			Err(SpzError::Decompress(_))
+		));
+	}
+
+	#[test]
+	fn test_pack_options_builder() {
+		let opts = PackOptions::builder()
+			.from(CoordinateSystem::RightDownFront)
+			.build();
+
+		assert_eq!(opts.from, CoordinateSystem::RightDownFront);
+		assert_eq!(
+			opts.axis_flips(),
+			CoordinateSystem::RightDownFront
+				.axis_flips_to(CoordinateSystem::RightUpBack)
+		);
+		assert_eq!(PackOptions::builder().build(), PackOptions::default());
+	}
+
+	#[test]
+	fn test_unpack_options_builder() {
+		let opts = UnpackOptions::builder()
+			.to_coord_system(CoordinateSystem::LeftUpFront)
+			.build();
+
+		assert_eq!(opts.to_coord_sys, CoordinateSystem::LeftUpFront);
+		assert_eq!(
+			opts.axis_flips(),
+			CoordinateSystem::RightUpBack.axis_flips_to(CoordinateSystem::LeftUpFront)
+		);
+		assert_eq!(UnpackOptions::builder().build(), UnpackOptions::default());
+	}
+
+	#[test]
+	fn test_decode_attributes_only() {
+		use crate::gaussian_splat::{GaussianSplat, LoadOptions, SaveOptions};
+
+		let splat = GaussianSplat {
+			header: Header {
+				num_points: 3,
+				..Default::default()
+			},
+			positions: vec![1.0, -2.0, 3.5, -100.25, 0.0, 7.0, 0.001, 2.5, -3.0],
+			scales: vec![-1.0; 9],
+			rotations: vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+			alphas: vec![-2.0, 0.0, 3.0],
+			colors: vec![0.1, -0.5, 1.0, 0.0, 0.2, -1.0, 2.0, 0.3, 0.4],
+			spherical_harmonics: vec![],
+		};
+		let bytes = splat
+			.serialize_to_packed_bytes(&SaveOptions::default())
+			.unwrap();
+		let packed = PackedGaussianSplat::from_bytes(&bytes).unwrap();
+
+		for coord_sys in [
+			CoordinateSystem::RightUpBack,
+			CoordinateSystem::LeftDownFront,
+		] {
+			let full = GaussianSplat::new_from_packed_gaussians(
+				&packed,
+				&LoadOptions::builder().coord_sys(coord_sys).build(),
+			)
+			.unwrap();
+			let flips = CoordinateSystem::RightUpBack.axis_flips_to(coord_sys);
+
+			assert_eq!(packed.decode_positions_only(&flips), full.positions);
+			assert_eq!(packed.decode_colors_only(), full.colors);
+			assert_eq!(packed.decode_alphas_only(), full.alphas);
+		}
+	}
+
+	#[test]
+	fn test_from_bytes_with_buf() {
+		let header = Header {
+			num_points: 1,
+			..Default::default()
+		};
+		let mut bytes: Vec<u8> = <[u8; 16]>::from(header).to_vec();
+		bytes.extend((0..20).map(|i| i as u8));
+
+		let mut compressed = Vec::new();
+
+		crate::compression::compress_bytes(&bytes, &mut compressed, Compression::Gzip)
+			.unwrap();
+
+		let mut scratch = vec![0xff; 1024];
+
+		for _ in 0..2 {
+			assert_eq!(
+				PackedGaussianSplat::from_bytes_with_buf(&compressed, &mut scratch)
+					.unwrap(),
+				PackedGaussianSplat::from_bytes(&compressed).unwrap()
+			);
+			assert_eq!(scratch, bytes);
+		}
+		assert!(matches!(
+			PackedGaussianSplat::from_bytes_with_buf([], &mut scratch),
+			Err(SpzError::Empty)
+		));
+	}
+
 	#[rstest]
-	#[case(vec![])]
-	#[case(vec![1_u8, 2, 3, 4])]
-	fn test_from_bytes_errors(#[case] bytes: Vec<u8>) {
-		assert!(PackedGaussianSplat::from_bytes(&bytes).is_err());
+	#[case(0, 0)]
+	#[case(1, 9)]
+	#[case(2, 24)]
+	#[case(3, 45)]
+	fn test_sh_bytes_per_point(#[case] degree: u8, #[case] expected: usize) {
+		assert_eq!(sh_bytes_per_point(degree), expected);
 	}
 }
@@ -0,0 +1,539 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Reading and writing Gaussian Splats as PLY, in the layout written by the
+//! reference 3D Gaussian Splatting training code.
+//!
+//! Only `binary_little_endian` files with `float` vertex properties are
+//! supported. Positions, rotations and spherical harmonics in PLY files are
+//! in [`PLY_COORDINATE_SYSTEM`].
+
+use std::io::{BufRead, Read, Write};
+
+use anyhow::{Context, Result, bail};
+use likely_stable::unlikely;
+
+use crate::{
+	coord::{AxisFlips, CoordinateSystem},
+	error::SpzError,
+	gaussian_splat::{GaussianSplat, LoadOptions, SaveOptions, check_coord_sys},
+	header::Header,
+	math::dim_for_degree,
+};
+
+/// Coordinate system of the data stored in PLY files.
+pub const PLY_COORDINATE_SYSTEM: CoordinateSystem = CoordinateSystem::RightDownFront;
+
+/// Column indices of the vertex properties used by the splat.
+struct Layout {
+	stride: usize,
+	position: [usize; 3],
+	scale: [usize; 3],
+	rotation: [usize; 4],
+	opacity: usize,
+	color: [usize; 3],
+	spherical_harmonics: Vec<usize>,
+	sh_degree: u8,
+}
+
+impl Layout {
+	fn new(properties: &[String]) -> Result<Self> {
+		let find = |name: &str| {
+			properties
+				.iter()
+				.position(|p| p == name)
+				.with_context(|| format!("missing ply property: {}", name))
+		};
+		let num_sh = properties
+			.iter()
+			.filter(|p| p.starts_with("f_rest_"))
+			.count();
+		let sh_degree = (0..=3_u8)
+			.find(|&d| dim_for_degree(d) as usize * 3 == num_sh)
+			.with_context(|| {
+				format!("unsupported number of f_rest properties: {}", num_sh)
+			})?;
+
+		Ok(Self {
+			stride: properties.len(),
+			position: [find("x")?, find("y")?, find("z")?],
+			scale: [find("scale_0")?, find("scale_1")?, find("scale_2")?],
+			rotation: [
+				find("rot_0")?,
+				find("rot_1")?,
+				find("rot_2")?,
+				find("rot_3")?,
+			],
+			opacity: find("opacity")?,
+			color: [find("f_dc_0")?, find("f_dc_1")?, find("f_dc_2")?],
+			spherical_harmonics: (0..num_sh)
+				.map(|i| find(&format!("f_rest_{}", i)))
+				.collect::<Result<_>>()?,
+			sh_degree,
+		})
+	}
+}
+
+/// Parses the PLY header up to and including `end_header`.
+///
+/// # Returns
+///
+/// The number of vertices and the vertex property names, in order.
+fn read_header<R>(reader: &mut R) -> Result<(usize, Vec<String>)>
+where
+	R: BufRead,
+{
+	let mut line = String::new();
+	let mut next_line = |line: &mut String| -> Result<()> {
+		line.clear();
+
+		if unlikely(reader.read_line(line)? == 0) {
+			bail!("unexpected end of ply header");
+		}
+		Ok(())
+	};
+	next_line(&mut line)?;
+
+	if unlikely(line.trim_end() != "ply") {
+		bail!("not a ply file");
+	}
+	let mut num_points = None;
+	let mut properties = Vec::new();
+
+	loop {
+		next_line(&mut line)?;
+
+		let mut tokens = line.split_whitespace();
+
+		match tokens.next() {
+			Some("end_header") => break,
+			Some("comment" | "obj_info") | None => {},
+			Some("format") => {
+				let format = tokens.next().unwrap_or_default();
+
+				if unlikely(format != "binary_little_endian") {
+					bail!("unsupported ply format: {}", format);
+				}
+			},
+			Some("element") => {
+				let (name, count) = (tokens.next(), tokens.next());
+
+				if unlikely(name != Some("vertex") || num_points.is_some()) {
+					bail!("unsupported ply element: {}", line.trim_end());
+				}
+				num_points = Some(count
+					.unwrap_or_default()
+					.parse::<usize>()
+					.with_context(|| "invalid ply vertex count")?);
+			},
+			Some("property") => {
+				let (ty, name) = (tokens.next(), tokens.next());
+
+				if unlikely(!matches!(ty, Some("float" | "float32"))) {
+					bail!("unsupported ply property: {}", line.trim_end());
+				}
+				properties.push(name.unwrap_or_default().to_owned());
+			},
+			Some(_) => bail!("unexpected ply header line: {}", line.trim_end()),
+		}
+	}
+	let num_points = num_points.with_context(|| "ply has no vertex element")?;
+
+	Ok((num_points, properties))
+}
+
+/// Reads a Gaussian Splat from a binary PLY stream.
+///
+/// # Args
+///
+/// * `reader` - PLY data, starting at the `ply` magic line.
+/// * `opts` - [`LoadOptions::coord_sys`] is the coordinate system to convert
+///   to from [`PLY_COORDINATE_SYSTEM`], [`LoadOptions::max_points`] caps the
+///   vertex count. `compression` is ignored.
+pub fn read_ply<R>(reader: &mut R, opts: &LoadOptions) -> Result<GaussianSplat>
+where
+	R: BufRead,
+{
+	let (num_points, properties) = read_header(reader)?;
+	let layout = Layout::new(&properties)?;
+
+	if unlikely(num_points > i32::MAX as usize) {
+		bail!("too many points: {}", num_points);
+	}
+	if unlikely(num_points > opts.max_points) {
+		return Err(SpzError::TooManyPoints {
+			num_points: num_points as i32,
+			max: opts.max_points,
+		}
+		.into());
+	}
+	check_coord_sys(opts.coord_sys, opts.strict_coord_sys)?;
+
+	let len = num_points
+		.checked_mul(layout.stride * 4)
+		.with_context(|| format!("too many points: {}", num_points))?;
+	// grows with the data actually present, so a forged vertex count can't
+	// allocate more than the stream holds
+	let mut data = Vec::new();
+
+	reader.take(len as u64)
+		.read_to_end(&mut data)
+		.with_context(|| "unable to read ply vertex data")?;
+
+	if unlikely(data.len() < len) {
+		bail!("truncated ply vertex data: {} of {} bytes", data.len(), len);
+	}
+
+	let sh_dim = dim_for_degree(layout.sh_degree) as usize;
+	let flips = PLY_COORDINATE_SYSTEM.axis_flips_to(opts.coord_sys);
+
+	let mut gs = GaussianSplat {
+		header: Header {
+			num_points: num_points as i32,
+			spherical_harmonics_degree: layout.sh_degree,
+			..Default::default()
+		},
+		positions: Vec::with_capacity(num_points * 3),
+		scales: Vec::with_capacity(num_points * 3),
+		rotations: Vec::with_capacity(num_points * 4),
+		alphas: Vec::with_capacity(num_points),
+		colors: Vec::with_capacity(num_points * 3),
+		spherical_harmonics: Vec::with_capacity(num_points * sh_dim * 3),
+	};
+	for vertex in data.chunks_exact(layout.stride * 4) {
+		let v = |i: usize| {
+			f32::from_le_bytes([
+				vertex[i * 4],
+				vertex[i * 4 + 1],
+				vertex[i * 4 + 2],
+				vertex[i * 4 + 3],
+			])
+		};
+		for axis in 0..3 {
+			gs.positions
+				.push(v(layout.position[axis]) * flips.position[axis]);
+		}
+		gs.scales.extend(layout.scale.map(v));
+
+		// PLY stores (w, x, y, z), the splat (x, y, z, w).
+		let [w, x, y, z] = layout.rotation.map(v);
+
+		gs.rotations.extend([
+			x * flips.rotation[0],
+			y * flips.rotation[1],
+			z * flips.rotation[2],
+			w,
+		]);
+		gs.alphas.push(v(layout.opacity));
+		gs.colors.extend(layout.color.map(v));
+
+		// PLY stores the coefficients channel-major, the splat
+		// coefficient-major with interleaved channels.
+		for j in 0..sh_dim {
+			for channel in 0..3 {
+				gs.spherical_harmonics
+					.push(v(layout.spherical_harmonics[channel * sh_dim + j])
+						* flips.spherical_harmonics[j]);
+			}
+		}
+	}
+	Ok(gs)
+}
+
+/// Writes a Gaussian Splat as binary little-endian PLY, or as ASCII PLY if
+/// [`SaveOptions::ply_ascii`] is set.
+///
+/// # Args
+///
+/// * `splat` - The splat to write.
+/// * `writer` - Destination of the PLY data.
+/// * `opts` - [`SaveOptions::coord_sys`] is the coordinate system the splat
+///   is in, converted to [`PLY_COORDINATE_SYSTEM`] on write. The remaining
+///   options besides `ply_ascii` only affect SPZ and are ignored.
+pub fn write_ply<W>(splat: &GaussianSplat, writer: &mut W, opts: &SaveOptions) -> Result<()>
+where
+	W: Write,
+{
+	if unlikely(!splat.check_sizes()) {
+		bail!("inconsistent sizes");
+	}
+	check_coord_sys(opts.coord_sys, opts.strict_coord_sys)?;
+
+	let num_points = splat.header.num_points as usize;
+	let sh_dim = dim_for_degree(splat.header.spherical_harmonics_degree) as usize;
+	let flips: AxisFlips = opts.coord_sys.axis_flips_to(PLY_COORDINATE_SYSTEM);
+
+	let format = if opts.ply_ascii {
+		"ascii"
+	} else {
+		"binary_little_endian"
+	};
+	let mut header = format!(
+		"ply\nformat {} 1.0\nelement vertex {}\n",
+		format, num_points
+	);
+	let names = [
+		"x", "y", "z", "nx", "ny", "nz", "f_dc_0", "f_dc_1", "f_dc_2",
+	]
+	.into_iter()
+	.map(str::to_owned)
+	.chain((0..sh_dim * 3).map(|i| format!("f_rest_{}", i)))
+	.chain([
+		"opacity", "scale_0", "scale_1", "scale_2", "rot_0", "rot_1", "rot_2", "rot_3",
+	]
+	.into_iter()
+	.map(str::to_owned));
+	for name in names {
+		header.push_str("property float ");
+		header.push_str(&name);
+		header.push('\n');
+	}
+	header.push_str("end_header\n");
+
+	writer.write_all(header.as_bytes())?;
+
+	let mut vertex = Vec::with_capacity(17 + sh_dim * 3);
+
+	for i in 0..num_points {
+		vertex.clear();
+
+		for axis in 0..3 {
+			vertex.push(splat.positions[i * 3 + axis] * flips.position[axis]);
+		}
+		vertex.extend([0.0; 3]);
+		vertex.extend_from_slice(&splat.colors[i * 3..i * 3 + 3]);
+
+		let sh = &splat.spherical_harmonics[i * sh_dim * 3..(i + 1) * sh_dim * 3];
+
+		for channel in 0..3 {
+			for j in 0..sh_dim {
+				vertex.push(sh[j * 3 + channel] * flips.spherical_harmonics[j]);
+			}
+		}
+		vertex.push(splat.alphas[i]);
+		vertex.extend_from_slice(&splat.scales[i * 3..i * 3 + 3]);
+
+		let r = &splat.rotations[i * 4..i * 4 + 4];
+
+		vertex.extend([
+			r[3],
+			r[0] * flips.rotation[0],
+			r[1] * flips.rotation[1],
+			r[2] * flips.rotation[2],
+		]);
+		if opts.ply_ascii {
+			let line = vertex
+				.iter()
+				.map(f32::to_string)
+				.collect::<Vec<_>>()
+				.join(" ");
+
+			writeln!(writer, "{}", line)?;
+		} else {
+			for f in &vertex {
+				writer.write_all(&f.to_le_bytes())?;
+			}
+		}
+	}
+	writer.flush()?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+
+	use crate::detect::{FileKind, detect};
+
+	fn make_splat(sh_degree: u8) -> GaussianSplat {
+		let sh_dim = dim_for_degree(sh_degree) as usize;
+		let mut gs = GaussianSplat {
+			header: Header {
+				num_points: 3,
+				spherical_harmonics_degree: sh_degree,
+				..Default::default()
+			},
+			..Default::default()
+		};
+		for i in 0..3 {
+			let f = i as f32;
+
+			gs.positions.extend([f, f + 0.5, -f]);
+			gs.scales.extend([-f, -2.0, -3.0]);
+			gs.rotations.extend([0.1 * f, 0.2, 0.3, 0.9]);
+			gs.alphas.push(f - 1.0);
+			gs.colors.extend([f, -f, 0.25]);
+			gs.spherical_harmonics
+				.extend((0..sh_dim * 3).map(|k| f + k as f32 * 0.01));
+		}
+		gs
+	}
+
+	fn to_bytes(gs: &GaussianSplat, coord_sys: CoordinateSystem) -> Vec<u8> {
+		let mut out = Vec::new();
+
+		write_ply(
+			gs,
+			&mut out,
+			&SaveOptions::builder().coord_sys(coord_sys).build(),
+		)
+		.unwrap();
+		out
+	}
+
+	fn from_bytes(bytes: &[u8], coord_sys: CoordinateSystem) -> Result<GaussianSplat> {
+		read_ply(
+			&mut &bytes[..],
+			&LoadOptions::builder().coord_sys(coord_sys).build(),
+		)
+	}
+
+	#[rstest]
+	#[case(0)]
+	#[case(1)]
+	#[case(2)]
+	#[case(3)]
+	fn test_ply_roundtrip(#[case] sh_degree: u8) {
+		let gs = make_splat(sh_degree);
+		let bytes = to_bytes(&gs, CoordinateSystem::Unspecified);
+
+		assert_eq!(detect(&bytes), FileKind::Ply);
+		assert_eq!(
+			from_bytes(&bytes, CoordinateSystem::Unspecified).unwrap(),
+			gs
+		);
+	}
+
+	#[rstest]
+	#[case(CoordinateSystem::RightUpBack)]
+	#[case(CoordinateSystem::LeftUpFront)]
+	#[case(CoordinateSystem::RightDownFront)]
+	fn test_ply_roundtrip_coordinate_system(#[case] coord_sys: CoordinateSystem) {
+		let gs = make_splat(3);
+		let bytes = to_bytes(&gs, coord_sys);
+
+		assert_eq!(from_bytes(&bytes, coord_sys).unwrap(), gs);
+	}
+
+	#[test]
+	fn test_ply_is_rdf() {
+		let gs = make_splat(1);
+		let bytes = to_bytes(&gs, CoordinateSystem::RightUpBack);
+		let raw = from_bytes(&bytes, CoordinateSystem::Unspecified).unwrap();
+
+		assert_eq!(raw.positions[3..6], [1.0, -1.5, 1.0]);
+		assert_eq!(raw.rotations[4..8], [0.1, -0.2, -0.3, 0.9]);
+	}
+
+	#[test]
+	fn test_ply_skips_unknown_properties() {
+		let mut bytes =
+			b"ply\nformat binary_little_endian 1.0\ncomment test\nelement vertex 1\n"
+				.to_vec();
+
+		for name in [
+			"x", "y", "z", "extra", "f_dc_0", "f_dc_1", "f_dc_2", "opacity", "scale_0",
+			"scale_1", "scale_2", "rot_0", "rot_1", "rot_2", "rot_3",
+		] {
+			bytes.extend(format!("property float {}\n", name).as_bytes());
+		}
+		bytes.extend(b"end_header\n");
+
+		for i in 0..15 {
+			bytes.extend((i as f32).to_le_bytes());
+		}
+		let gs = from_bytes(&bytes, CoordinateSystem::Unspecified).unwrap();
+
+		assert_eq!(gs.header.num_points, 1);
+		assert_eq!(gs.header.spherical_harmonics_degree, 0);
+		assert_eq!(gs.positions, [0.0, 1.0, 2.0]);
+		assert_eq!(gs.colors, [4.0, 5.0, 6.0]);
+		assert_eq!(gs.alphas, [7.0]);
+		assert_eq!(gs.rotations, [12.0, 13.0, 14.0, 11.0]);
+	}
+
+	#[rstest]
+	#[case(b"NGSP".to_vec(), "not a ply file")]
+	#[case(b"ply\nformat ascii 1.0\n".to_vec(), "unsupported ply format")]
+	#[case(
+		b"ply\nformat binary_little_endian 1.0\nelement vertex 1\nproperty double x\n".to_vec(),
+		"unsupported ply property"
+	)]
+	#[case(
+		b"ply\nformat binary_little_endian 1.0\nelement face 1\n".to_vec(),
+		"unsupported ply element"
+	)]
+	#[case(
+		b"ply\nformat binary_little_endian 1.0\nelement vertex 1\nproperty float x\nend_header\n"
+			.to_vec(),
+		"missing ply property"
+	)]
+	#[case(b"ply\nformat binary_little_endian 1.0\n".to_vec(), "unexpected end")]
+	fn test_ply_invalid_header(#[case] bytes: Vec<u8>, #[case] expected: &str) {
+		let err = from_bytes(&bytes, CoordinateSystem::Unspecified).unwrap_err();
+
+		assert!(err.to_string().contains(expected), "{}", err);
+	}
+
+	#[test]
+	fn test_ply_truncated() {
+		let bytes = to_bytes(&make_splat(1), CoordinateSystem::Unspecified);
+
+		assert!(
+			from_bytes(&bytes[..bytes.len() - 1], CoordinateSystem::Unspecified)
+				.is_err()
+		);
+	}
+
+	#[test]
+	fn test_ply_vertex_count_exceeds_data() {
+		let bytes = to_bytes(&make_splat(0), CoordinateSystem::Unspecified);
+		let header_end = bytes
+			.windows(11)
+			.position(|w| w == b"end_header\n")
+			.unwrap();
+		let forged = [
+			String::from_utf8_lossy(&bytes[..header_end])
+				.replace("element vertex 3\n", "element vertex 5000000\n")
+				.as_bytes(),
+			&bytes[header_end..],
+		]
+		.concat();
+
+		let err = from_bytes(&forged, CoordinateSystem::Unspecified).unwrap_err();
+
+		assert!(
+			err.to_string().contains("truncated ply vertex data"),
+			"{}",
+			err
+		);
+	}
+
+	#[test]
+	fn test_write_ascii() {
+		let mut splat = make_splat(0);
+
+		splat.filter_by_opacity(0.5);
+		assert_eq!(splat.header.num_points, 2);
+
+		let opts = SaveOptions::builder().ply_ascii(true).build();
+		let mut bytes = Vec::new();
+
+		write_ply(&splat, &mut bytes, &opts).unwrap();
+
+		let text = String::from_utf8(bytes).expect("ascii ply is utf-8");
+		let (header, body) = text.split_once("end_header\n").unwrap();
+
+		assert!(header.contains("format ascii 1.0\n"));
+		assert!(header.contains("element vertex 2\n"));
+
+		let lines = body.lines().collect::<Vec<_>>();
+
+		assert_eq!(lines.len(), 2);
+		for line in lines {
+			assert_eq!(line.split(' ').count(), 17);
+			assert!(line.split(' ').all(|v| v.parse::<f32>().is_ok()));
+		}
+	}
+}
@@ -3,6 +3,8 @@
 use arbitrary::Arbitrary;
 use serde::{Deserialize, Serialize};
 
+use crate::coord::{AxisFlips, CoordinateSystem};
+
 static_assertions::const_assert_eq!(std::mem::size_of::<UnpackedGaussian>(), 236);
 
 /// Intermediate representation. Represents a single inflated gaussian.
@@ -22,3 +24,51 @@ pub struct UnpackedGaussian {
 	pub sh_g: [f32; 15],
 	pub sh_b: [f32; 15],
 }
+
+/// Options for unpacking single gaussians of a
+/// [`PackedGaussianSplat`](crate::packed::PackedGaussianSplat), see
+/// [`PackedGaussianSplat::unpack_with`](crate::packed::PackedGaussianSplat::unpack_with).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Arbitrary)]
+pub struct UnpackOptions {
+	/// Coordinate system to convert to from the SPZ internal one
+	/// (RightUpBack|RUB).
+	pub to_coord_sys: CoordinateSystem,
+}
+
+impl UnpackOptions {
+	/// Creates a new [`UnpackOptionsBuilder`].
+	#[inline]
+	pub fn builder() -> UnpackOptionsBuilder {
+		UnpackOptionsBuilder::default()
+	}
+
+	/// Axis flips from the SPZ internal coordinate system to
+	/// [`Self::to_coord_sys`].
+	#[inline]
+	pub fn axis_flips(&self) -> AxisFlips {
+		CoordinateSystem::RightUpBack.axis_flips_to(self.to_coord_sys)
+	}
+}
+
+/// Builder for [`UnpackOptions`].
+#[derive(Clone, Debug, Default, Arbitrary)]
+pub struct UnpackOptionsBuilder {
+	to_coord_sys: CoordinateSystem,
+}
+
+impl UnpackOptionsBuilder {
+	/// Sets the coordinate system to convert to.
+	#[inline]
+	pub fn to_coord_system(mut self, coord_sys: CoordinateSystem) -> Self {
+		self.to_coord_sys = coord_sys;
+		self
+	}
+
+	/// Builds the [`UnpackOptions`].
+	#[inline]
+	pub fn build(self) -> UnpackOptions {
+		UnpackOptions {
+			to_coord_sys: self.to_coord_sys,
+		}
+	}
+}
@@ -5,6 +5,7 @@ use rand::{RngExt, SeedableRng, rngs::StdRng};
 use rstest::rstest;
 use spz::{
 	coord::CoordinateSystem,
+	error::SpzError,
 	gaussian_splat::{self, GaussianSplat},
 	header::Header,
 	packed::PackedGaussianSplat,
@@ -25,6 +26,36 @@ fn test_load_packed_from_file_no_err(#[case] filename: &str) {
 	let _gs = GaussianSplat::load_with(&spz_path, &unpack_opts).expect("failed to load splat");
 }
 
+#[test]
+fn test_estimated_packed_size() {
+	let spz_path = util::assets_dir().join("racoonfamily.spz");
+	let gs = GaussianSplat::load_with(&spz_path, &LoadOptions::default())
+		.expect("failed to load splat");
+	let opts = SaveOptions::default();
+	let packed = gs.to_packed_gaussians(&opts).expect("failed to pack");
+
+	assert_eq!(
+		gs.estimated_packed_size(&opts),
+		packed.to_bytes_vec().expect("failed to serialize").len()
+	);
+}
+
+#[test]
+fn test_from_gzip_reader_chunked() {
+	let spz_path = util::assets_dir().join("racoonfamily.spz");
+	let bytes = std::fs::read(&spz_path).expect("failed to read file");
+	let reader = util::ChunkedReader {
+		inner: std::io::Cursor::new(bytes),
+		chunk: 61,
+	};
+
+	let streamed = GaussianSplat::from_gzip_reader(reader, &LoadOptions::default())
+		.expect("failed to stream splat");
+	let loaded = GaussianSplat::load(&spz_path).expect("failed to load splat");
+
+	assert_eq!(streamed, loaded);
+}
+
 #[test]
 fn test_empty_gaussian_splat() {
 	let gs = GaussianSplat::default();
@@ -46,7 +77,10 @@ fn test_empty_gaussian_splat() {
 	assert!(packed_gs.colors.is_empty());
 	assert!(packed_gs.spherical_harmonics.is_empty());
 
-	assert!(PackedGaussianSplat::from_bytes([]).is_err());
+	assert!(matches!(
+		PackedGaussianSplat::from_bytes([]),
+		Err(SpzError::Empty)
+	));
 }
 
 #[rstest]
@@ -62,6 +96,54 @@ fn test_load_header_only(#[case] filename: &str, #[case] num_points: i32) {
 	assert_eq!(header.num_points, num_points);
 }
 
+#[test]
+fn test_header_from_compressed_reader() {
+	let spz_path = util::assets_dir().join("racoonfamily.spz");
+	let spz_infile = std::fs::read(&spz_path).expect("failed to read file");
+	let cursor = std::io::Cursor::new(&spz_infile[..512]);
+
+	let header = Header::from_compressed_reader(cursor).expect("failed to load header");
+
+	assert_eq!(
+		header,
+		Header::from_file(&spz_path).expect("failed to load header")
+	);
+}
+
+#[test]
+fn test_header_from_compressed_reader_incompressible() {
+	let filename = mktmp().join("header_from_compressed_reader_incompressible.spz");
+	let mut rng = StdRng::seed_from_u64(7);
+	let num_points = 20_000;
+	let gs = GaussianSplat {
+		header: Header {
+			num_points: num_points as i32,
+			..Default::default()
+		},
+		positions: (0..num_points * 3)
+			.map(|_| rng.random::<f32>() * 200.0 - 100.0)
+			.collect(),
+		scales: vec![0.0; num_points * 3],
+		rotations: [0.0, 0.0, 0.0, 1.0].repeat(num_points),
+		alphas: vec![0.0; num_points],
+		colors: vec![0.0; num_points * 3],
+		spherical_harmonics: vec![],
+	};
+
+	gs.save(&filename, &SaveOptions::default())
+		.expect("failed to save splat");
+
+	let file = std::fs::File::open(&filename).expect("failed to open file");
+	let header = Header::from_compressed_reader(file).expect("failed to load header");
+
+	assert_eq!(header.num_points, num_points as i32);
+	assert_eq!(
+		Header::from_file(&filename).expect("failed to load header"),
+		header
+	);
+	let _ = std::fs::remove_file(&filename);
+}
+
 #[rstest]
 #[case("hornedlizard.spz", 786233)]
 #[case("racoonfamily.spz", 932560)]
@@ -142,6 +224,46 @@ fn test_roundtrip_pack_unpack(#[case] filename: &str) {
 	}
 }
 
+#[rstest]
+#[case("racoonfamily.spz", CoordinateSystem::RightUpBack)]
+#[case("racoonfamily.spz", CoordinateSystem::RightDownFront)]
+fn test_decode_attributes_only(#[case] filename: &str, #[case] coord_sys: CoordinateSystem) {
+	let spz_path = util::assets_dir().join(filename);
+	let bytes = std::fs::read(&spz_path).expect("failed to read file");
+	let packed = PackedGaussianSplat::from_bytes(&bytes).expect("failed to parse packed splat");
+
+	let full = GaussianSplat::new_from_packed_gaussians(
+		&packed,
+		&LoadOptions::builder().coord_sys(coord_sys).build(),
+	)
+	.expect("failed to unpack");
+	let flips = CoordinateSystem::RightUpBack.axis_flips_to(coord_sys);
+
+	assert_eq!(packed.decode_positions_only(&flips), full.positions);
+	assert_eq!(packed.decode_colors_only(), full.colors);
+	assert_eq!(packed.decode_alphas_only(), full.alphas);
+}
+
+#[rstest]
+#[case("racoonfamily.spz")]
+fn test_builder_load_from_bytes(#[case] filename: &str) {
+	let spz_path = util::assets_dir().join(filename);
+	let bytes = std::fs::read(&spz_path).expect("failed to read file");
+
+	let from_path = GaussianSplat::builder()
+		.load(&spz_path)
+		.expect("failed to load gaussian splat");
+	let from_bytes = GaussianSplat::builder()
+		.load_from_bytes(&bytes)
+		.expect("failed to load gaussian splat from bytes");
+	let from_reader = GaussianSplat::builder()
+		.load_from_reader(bytes.as_slice())
+		.expect("failed to load gaussian splat from reader");
+
+	assert_eq!(from_bytes.header.num_points, from_path.header.num_points);
+	assert_eq!(from_reader.header.num_points, from_path.header.num_points);
+}
+
 #[rstest]
 #[case("hornedlizard.spz", util::SpzValues {
 	num_points: 786233,
@@ -325,15 +447,19 @@ fn sample_sh_coefficients() -> Vec<f32> {
 	},
 	SaveOptions { // Save as RUB and load as RDF (180 degree rotation about X)
 		coord_sys: CoordinateSystem::RightUpBack,
+		..Default::default()
 	},
 	LoadOptions {
 		coord_sys: CoordinateSystem::RightDownFront,
+		..Default::default()
 	},
 	SaveOptions {
 		coord_sys: CoordinateSystem::RightDownFront,
+		..Default::default()
 	},
 	LoadOptions {
 		coord_sys: CoordinateSystem::RightDownFront,
+		..Default::default()
 	}
 )]
 fn test_spherical_harmonics_coordinate_transformation(
@@ -581,3 +707,71 @@ fn test_compression_precision_validation(#[case] gs: GaussianSplat) {
 	);
 	let _ = std::fs::remove_file(&filename);
 }
+
+#[test]
+fn test_save_creates_parent_dirs() {
+	let root = mktmp().join("test_save_creates_parent_dirs");
+	let filename = root.join("a").join("b").join("out.spz");
+
+	let _ = std::fs::remove_dir_all(&root);
+
+	util::single_point_splat()
+		.save(&filename, &SaveOptions::default())
+		.expect("failed to save splat");
+
+	let loaded = GaussianSplat::load(&filename).expect("failed to load splat");
+
+	assert_eq!(loaded.header.num_points, 1);
+
+	let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_save_parent_is_file() {
+	let root = mktmp().join("test_save_parent_is_file");
+	let blocker = root.join("blocker");
+
+	let _ = std::fs::remove_dir_all(&root);
+	std::fs::create_dir_all(&root).unwrap();
+	std::fs::write(&blocker, b"").unwrap();
+
+	let err = util::single_point_splat()
+		.save(blocker.join("out.spz"), &SaveOptions::default())
+		.unwrap_err();
+
+	assert!(
+		err.to_string().contains("unable to create directory"),
+		"unexpected error: {err}"
+	);
+	let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_load_defaults_to_rub() {
+	let temp_dir = mktmp();
+	let filename = temp_dir.join("test_load_defaults_to_rub.spz");
+
+	// stored as is, i.e. in RUB: +x right, +y up, +z back
+	util::single_point_splat()
+		.save(&filename, &SaveOptions::default())
+		.expect("failed to save splat");
+
+	let loaded = GaussianSplat::load(&filename).expect("failed to load splat");
+	let rdf = GaussianSplat::load_with(
+		&filename,
+		&LoadOptions::builder()
+			.coord_sys(CoordinateSystem::RightDownFront)
+			.build(),
+	)
+	.expect("failed to load splat");
+
+	let _ = std::fs::remove_file(&filename);
+
+	assert_relative_eq!(loaded.positions[0], 1.0, epsilon = 1e-3);
+	assert_relative_eq!(loaded.positions[1], 2.0, epsilon = 1e-3);
+	assert_relative_eq!(loaded.positions[2], 3.0, epsilon = 1e-3);
+
+	assert_relative_eq!(rdf.positions[0], 1.0, epsilon = 1e-3);
+	assert_relative_eq!(rdf.positions[1], -2.0, epsilon = 1e-3);
+	assert_relative_eq!(rdf.positions[2], -3.0, epsilon = 1e-3);
+}
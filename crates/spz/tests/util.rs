@@ -1,7 +1,15 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+//! Helpers shared by the integration tests of `spz` and `spz-cli`, each of
+//! which only uses some of them.
+
+#![allow(dead_code)]
+
+use std::io::Read;
 use std::path::PathBuf;
 
+use spz::{gaussian_splat::GaussianSplat, header::Header};
+
 pub const SH_4BIT_EPSILON: f32 = 2.0 / 32.0 + 0.5 / 255.0;
 pub const SH_5BIT_EPSILON: f32 = 2.0 / 64.0 + 0.5 / 255.0;
 
@@ -19,6 +27,42 @@ pub fn mktmp() -> PathBuf {
 	tmp
 }
 
+/// A splat with a single gaussian at `(1, 2, 3)`.
+pub fn single_point_splat() -> GaussianSplat {
+	GaussianSplat {
+		header: Header {
+			num_points: 1,
+			..Default::default()
+		},
+		positions: vec![1.0, 2.0, 3.0],
+		scales: vec![0.0, 0.0, 0.0],
+		rotations: vec![0.0, 0.0, 0.0, 1.0],
+		alphas: vec![0.0],
+		colors: vec![0.0, 0.0, 0.0],
+		spherical_harmonics: vec![],
+	}
+}
+
+/// A splat with 8 gaussians at the corners of the unit cube.
+pub fn grid_splat() -> GaussianSplat {
+	let positions = (0..8)
+		.flat_map(|i| [(i & 1) as f32, (i >> 1 & 1) as f32, (i >> 2 & 1) as f32])
+		.collect::<Vec<_>>();
+
+	GaussianSplat {
+		header: Header {
+			num_points: 8,
+			..Default::default()
+		},
+		positions,
+		scales: vec![0.0; 24],
+		rotations: [0.0, 0.0, 0.0, 1.0].repeat(8),
+		alphas: vec![0.0; 8],
+		colors: vec![0.0; 24],
+		spherical_harmonics: vec![],
+	}
+}
+
 pub struct SpzValues {
 	pub num_points: i32,
 	pub bbox_x: [f32; 2],
@@ -49,3 +93,20 @@ pub fn quaternions_equivalent(q1: &[f32], q2: &[f32], tolerance: f32) -> bool {
 
 	same || negated
 }
+
+/// Hands out at most `chunk` bytes per read, like a slow socket would.
+pub struct ChunkedReader<R> {
+	pub inner: R,
+	pub chunk: usize,
+}
+
+impl<R> Read for ChunkedReader<R>
+where
+	R: Read,
+{
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		let n = buf.len().min(self.chunk);
+
+		self.inner.read(&mut buf[..n])
+	}
+}